@@ -0,0 +1,75 @@
+//! # Sui Token Generator RPC Server
+//!
+//! Self-hostable implementation of the `TokenGen` RPC service used by the `sui-token-gen` CLI.
+//! Point the CLI at it with `--rpc <host:port>`, or `--rpc tls://<host:port>` when serving over TLS.
+//!
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+use sui_token_gen::{
+    server::{spawn_server, spawn_tls_server, AccessPolicy},
+    utils::tls::server_acceptor,
+};
+
+/// Command-line arguments for the RPC server.
+#[derive(Parser, Debug)]
+#[command(
+    author = "Osec",
+    version,
+    about = "Serve the Sui Token Generator RPC service"
+)]
+struct Args {
+    /// Address to listen on, in the form `host:port`.
+    #[arg(short, long, default_value = "0.0.0.0:5001")]
+    listen: String,
+
+    /// PEM certificate chain of the server. Serves over TLS when set, for `tls://` clients.
+    #[arg(long, requires = "tls_key")]
+    tls_cert: Option<String>,
+
+    /// PEM private key of the server certificate.
+    #[arg(long, requires = "tls_cert")]
+    tls_key: Option<String>,
+
+    /// PEM bundle of CAs whose client certificates are accepted. Requires clients to authenticate.
+    #[arg(long, requires = "tls_cert")]
+    client_ca: Option<String>,
+
+    /// File of accepted API keys, one per line. Clients must authenticate with one of them when set.
+    #[arg(long)]
+    api_keys_file: Option<PathBuf>,
+
+    /// Maximum number of requests per API key and minute.
+    #[arg(long, requires = "api_keys_file")]
+    rate_limit: Option<u32>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let policy = match &args.api_keys_file {
+        Some(path) => AccessPolicy::new(AccessPolicy::load_keys(path)?, args.rate_limit),
+        None => AccessPolicy::default(),
+    };
+
+    let server = match (&args.tls_cert, &args.tls_key) {
+        (Some(cert), Some(key)) => {
+            let acceptor = server_acceptor(cert, key, args.client_ca.as_deref())?;
+            let (address, server) = spawn_tls_server(&args.listen, acceptor, policy).await?;
+            println!("TokenGen RPC server listening on tls://{}", address);
+            server
+        }
+        _ => {
+            if args.api_keys_file.is_some() {
+                eprintln!("Warning: API keys are checked without TLS, so clients send them in plaintext. Set --tls-cert and --tls-key to encrypt them.");
+            }
+            let (address, server) = spawn_server(&args.listen, policy).await?;
+            println!("TokenGen RPC server listening on {}", address);
+            server
+        }
+    };
+
+    server.await?;
+    Ok(())
+}
@@ -1,15 +1,18 @@
-use tarpc::context;
+use std::path::PathBuf;
 
 use crate::{
-    constants::{SUB_FOLDER, TEST_FOLDER},
+    constants::DEFAULT_ENVIRONMENT,
     errors::TokenGenErrors,
-    handlers::{handle_success, SuccessType},
+    handlers::{handle_status, handle_success, SuccessType},
     utils::{
         atomic::AtomicFileOperation,
-        client::rpc_client::TokenGenClient,
+        client::connection::RpcClient,
+        deployment::DeploymentRecord,
         generation::ContractGenerator,
         helpers::sanitize_name,
-        prompts::{get_user_prompt, TokenInfo},
+        manifest::{load_manifest, BatchEntry, BatchReport, BatchStatus},
+        prompts::{confirm_overwrite, get_user_prompt, TokenInfo},
+        templates::generate_contract,
     },
     CreateTokenParams, Result,
 };
@@ -18,61 +21,31 @@ use crate::{
 ///
 /// This function follows these steps:
 /// 1. Collects token configuration data from the user via interactive prompts.
-/// 2. Sends the collected data to the RPC server, which generates the contract's source code.
+/// 2. Sends the collected data to the RPC server, which generates the contract's source code,
+///    or renders it from the embedded templates when running offline.
 /// 3. Creates a project folder and writes the generated contract files to disk using an atomic operation for reliability.
 /// 4. Ensures proper error handling, including rollback in case of failures.
 ///
+/// An existing project folder is only written into with `--force` or after the user confirms it.
+///
 /// # Arguments
-/// * `client` - An instance of `TokenGenClient` that communicates with the RPC server.
+/// * `client` - An instance of `RpcClient` that communicates with the RPC server, or `None` to generate offline.
 /// * `params` - A reference to `CreateTokenParams` containing optional predefined token parameters.
 ///
 /// # Returns
 /// * `Ok(())` - If the token contract is successfully generated and saved.
 /// * `Err(TokenGenErrors)` - If any step in the process fails, returning a specific error variant.
-pub async fn create_token(client: TokenGenClient, params: &CreateTokenParams) -> Result<()> {
+pub async fn create_token(client: Option<RpcClient>, params: &CreateTokenParams) -> Result<()> {
     // Step 1: Collect token details from user input (or use predefined parameters).
     let token_data: TokenInfo = get_user_prompt(params)?;
-    println!("Sending request to RPC service...");
-
-    // Step 2: Request contract generation from the RPC server.
-    let (token_content, move_toml, test_token_content) = client
-        .create(
-            context::current(),
-            token_data.decimals,
-            token_data.name.clone(),
-            token_data.symbol.clone(),
-            token_data.description.clone(),
-            token_data.is_frozen,
-            token_data.environment.clone(),
-        )
-        .await
-        .map_err(TokenGenErrors::RpcError)? // Convert RPC-related errors to `TokenGenErrors`.
-        .map_err(|e| TokenGenErrors::FailedToCreateTokenContract(e.to_string()))?; // Handle failed contract generation.
-
-    // Step 3: Prepare project folder paths.
-    let project_folder: String = sanitize_name(&token_data.name).to_lowercase();
-    let current_dir = std::env::current_dir().map_err(|_| TokenGenErrors::CurrentDirectoryError)?;
-    let base_folder_path = current_dir.join(&project_folder);
 
-    // Ensure the folder path is valid and convertible to a string.
-    let base_folder = base_folder_path
-        .to_str()
-        .ok_or(TokenGenErrors::PathConversionError)?;
+    // Ask before writing into a folder that already exists.
+    let folder = package_folder(&token_data.name)?;
+    let overwrite = folder.exists() && confirm_overwrite(params, &folder)?;
 
-    // Step 4: Initialize an atomic file operation to prevent partial writes in case of failure.
-    let mut atomic_op = AtomicFileOperation::new(base_folder);
-
-    // Create contract generator instance.
-    let contract_generator = ContractGenerator::new(base_folder.to_string());
-
-    // Step 5: Create and populate contract files.
-    contract_generator.create_base_folder()?; // Ensure the base folder exists.
-    contract_generator.create_move_toml(&move_toml)?; // Write Move.toml configuration.
-    contract_generator.create_contract_file(&token_data.name, &token_content, SUB_FOLDER)?; // Write main contract.
-    contract_generator.create_contract_file(&token_data.name, &test_token_content, TEST_FOLDER)?; // Write test contract.
-
-    // Step 6: Finalize by committing the atomic operation.
-    atomic_op.commit();
+    // Steps 2-6: Generate the contract and write the package.
+    let (base_folder_path, files) =
+        generate_package(client.as_ref(), &token_data, overwrite).await?;
 
     // Step 7: Notify the user about the successful contract creation.
     handle_success(SuccessType::TokenCreated(
@@ -81,7 +54,170 @@ pub async fn create_token(client: TokenGenClient, params: &CreateTokenParams) ->
             "Contract has been generated at: {}",
             base_folder_path.display()
         ),
+        files,
     ));
 
     Ok(())
 }
+
+/// Creates every token listed in a manifest file, one package folder per entry.
+///
+/// The manifest is parsed and validated up front, so an invalid manifest, or one whose folders
+/// already exist without `--force`, creates nothing. Entries are then generated in order; a failed
+/// entry leaves no partial package behind. By default the remaining entries are skipped after the
+/// first failure, unless `continue_on_error` is set.
+///
+/// # Arguments
+/// * `client` - An instance of `RpcClient` that communicates with the RPC server, or `None` to generate offline.
+/// * `params` - A reference to `CreateTokenParams` with the `manifest` path, `continue_on_error`
+///   and the default `environment` for entries without one.
+///
+/// # Returns
+/// * `Ok(())` - If every entry was created.
+/// * `Err(TokenGenErrors::InvalidManifest)` - If the manifest cannot be parsed or lists invalid entries.
+/// * `Err(TokenGenErrors::BatchCreationFailed)` - If any entry failed, with the per-entry results.
+pub async fn create_tokens_from_manifest(
+    client: Option<RpcClient>,
+    params: &CreateTokenParams,
+) -> Result<()> {
+    let manifest = params
+        .manifest
+        .as_deref()
+        .ok_or_else(|| TokenGenErrors::InvalidInput("--manifest is required".to_string()))?;
+    let environment = params.environment.as_deref().unwrap_or(DEFAULT_ENVIRONMENT);
+
+    let tokens = load_manifest(manifest, environment, params.force)?;
+    let total = tokens.len();
+    let mut entries = Vec::with_capacity(total);
+    let mut stopped = false;
+
+    for (index, token) in tokens.into_iter().enumerate() {
+        if stopped {
+            entries.push(BatchEntry {
+                token,
+                status: BatchStatus::Skipped,
+                path: None,
+                files: Vec::new(),
+                error: None,
+            });
+            continue;
+        }
+
+        handle_status(&format!(
+            "[{}/{}] Creating {} ({})...",
+            index + 1,
+            total,
+            token.name,
+            token.symbol
+        ));
+
+        match generate_package(client.as_ref(), &token, params.force).await {
+            Ok((path, files)) => entries.push(BatchEntry {
+                token,
+                status: BatchStatus::Created,
+                path: Some(path.display().to_string()),
+                files,
+                error: None,
+            }),
+            Err(error) => {
+                entries.push(BatchEntry {
+                    token,
+                    status: BatchStatus::Failed,
+                    path: None,
+                    files: Vec::new(),
+                    error: Some(error.to_string()),
+                });
+                stopped = !params.continue_on_error;
+            }
+        }
+    }
+
+    let report = BatchReport {
+        manifest: manifest.to_string(),
+        entries,
+    };
+
+    if report.count(BatchStatus::Failed) > 0 {
+        return Err(TokenGenErrors::BatchCreationFailed(Box::new(report)));
+    }
+
+    handle_success(SuccessType::TokensCreated(report));
+    Ok(())
+}
+
+/// Generates the sources for a token and writes its package into the current directory.
+///
+/// # Arguments
+/// * `client` - The RPC client used to generate the sources, or `None` to render them offline.
+/// * `token_data` - The validated token configuration.
+/// * `overwrite` - Whether an existing package folder may be written into.
+///
+/// # Returns
+/// * `Ok((PathBuf, Vec<String>))` - The package folder and the paths of the written files.
+/// * `Err(TokenGenErrors::InvalidInput)` - If the package folder exists and `overwrite` is not set.
+/// * `Err(TokenGenErrors)` - If generation or writing fails; a folder created by this run is removed again.
+async fn generate_package(
+    client: Option<&RpcClient>,
+    token_data: &TokenInfo,
+    overwrite: bool,
+) -> Result<(PathBuf, Vec<String>)> {
+    // Step 1: Refuse to touch an existing folder unless allowed, before anything is generated.
+    let base_folder_path = package_folder(&token_data.name)?;
+    let existed = base_folder_path.exists();
+    if existed && !overwrite {
+        return Err(TokenGenErrors::InvalidInput(format!(
+            "The folder {} already exists. Use --force to overwrite it",
+            base_folder_path.display()
+        )));
+    }
+
+    // Step 2: Request contract generation from the RPC server, or render it locally when offline.
+    let generated_files = match client {
+        Some(client) => {
+            handle_status("Sending request to RPC service...");
+            client
+                .create(token_data)
+                .await?
+                // Convert failed contract generation to `TokenGenErrors`.
+                .map_err(|e| {
+                    TokenGenErrors::from_response(e, TokenGenErrors::FailedToCreateTokenContract)
+                })?
+                .files
+        }
+        None => generate_contract(token_data)?.into_files(&token_data.name),
+    };
+
+    // Step 3: Ensure the folder path is valid and convertible to a string.
+    let base_folder = base_folder_path
+        .to_str()
+        .ok_or(TokenGenErrors::PathConversionError)?;
+
+    // The deployments of an existing folder are kept, so its record is read before anything is written.
+    let mut record = DeploymentRecord::load(base_folder)?;
+
+    // Step 4: Initialize an atomic file operation to prevent partial writes in case of failure.
+    // A folder that existed before this run is never rolled back, so its contents survive a failure.
+    let mut atomic_op = (!existed).then(|| AtomicFileOperation::new(base_folder));
+
+    // Create contract generator instance.
+    let contract_generator = ContractGenerator::new(base_folder.to_string());
+
+    // Step 5: Write every generated file, keeping track of the written paths.
+    let files = contract_generator.write_files(&generated_files)?;
+
+    // Step 6: Record when the package was generated, for the deployments published from it.
+    record.record_generation(base_folder)?;
+
+    // Step 7: Finalize by committing the atomic operation.
+    if let Some(atomic_op) = atomic_op.as_mut() {
+        atomic_op.commit();
+    }
+
+    Ok((base_folder_path, files))
+}
+
+// Helper function to resolve the package folder for a token name in the current directory
+fn package_folder(name: &str) -> Result<PathBuf> {
+    let current_dir = std::env::current_dir().map_err(|_| TokenGenErrors::CurrentDirectoryError)?;
+    Ok(current_dir.join(sanitize_name(name).to_lowercase()))
+}
@@ -0,0 +1,33 @@
+use crate::{
+    handlers::{handle_success, SuccessType},
+    utils::client::connection::RpcClient,
+    Result,
+};
+
+/**
+ * Reports the versions of this client and the RPC service, and the environments it supports.
+ *
+ * The connection already checked that the service speaks the protocol of this client, so an
+ * incompatible service is reported as an error before this is called.
+ *
+ * # Parameters
+ * - `rpc_url`: The address of the RPC service, as given on the command line.
+ * - `client`: The connected RPC client.
+ *
+ * # Returns
+ * - `Ok(())` if the service reported its capabilities.
+ * - `Err(TokenGenErrors)` if the service could not be asked for them.
+ */
+pub async fn doctor(rpc_url: &str, client: RpcClient) -> Result<()> {
+    // Clients wrapping an existing connection skipped the handshake, so ask again.
+    let server = match client.capabilities() {
+        Some(server) => server,
+        None => client.fetch_capabilities().await?,
+    };
+
+    handle_success(SuccessType::Doctor {
+        rpc: rpc_url.to_string(),
+        server,
+    });
+    Ok(())
+}
@@ -3,7 +3,11 @@
 //! This module contains the implementation of CLI commands for:
 //! - Creating new token contracts (`create`)
 //! - Verifying existing token contracts (`verify`)
+//! - Checking the compatibility of the RPC service (`doctor`)
+//! - Publishing generated packages to a Sui network (`publish`)
 //!
 //! Each submodule handles specific command functionality and error handling.
 pub mod create;
+pub mod doctor;
+pub mod publish;
 pub mod verify;
@@ -0,0 +1,100 @@
+use crate::{
+    errors::TokenGenErrors,
+    handlers::{handle_success, SuccessType},
+    utils::{
+        deployment::{publish_package, Deployment, DeploymentRecord},
+        fullnode::FullnodeClient,
+        keystore::SuiKeypair,
+        onchain::BytecodeCompiler,
+        verify_helper::{parse_toml_environment, verify_path},
+    },
+    Result,
+};
+use std::io;
+
+/**
+ * Publishes a generated token package and records its deployment.
+ *
+ * This function performs the following steps:
+ * 1. Validates the package folder, which must contain a `Move.toml` and a `sources` folder.
+ * 2. Checks that the package was generated for the environment it is published to.
+ * 3. Reads the existing `deployments.toml`, so an invalid record is reported before publishing.
+ * 4. Compiles and publishes the package, signed by the given key.
+ * 5. Records the package ID and the IDs of the `TreasuryCap` and `CoinMetadata` of the coin for
+ *    the environment in `deployments.toml`, in the package folder, with the generator version and
+ *    time recorded by `create`. If the record cannot be
+ *    written, the error names these IDs, since the package is already published.
+ *
+ * # Parameters
+ * - `path`: The package folder, as written by `create`.
+ * - `environment`: The blockchain environment to publish to.
+ * - `fullnode`: The fullnode of the environment.
+ * - `compiler`: Compiles the package.
+ * - `signer`: The key of the publisher.
+ * - `gas_budget`: The gas budget, in MIST.
+ *
+ * # Returns
+ * - `Ok(())` if the package is published and its deployment recorded.
+ * - `Err(TokenGenErrors)` if any validation or publishing step fails.
+ */
+pub async fn publish_token(
+    path: &str,
+    environment: &str,
+    fullnode: &FullnodeClient,
+    compiler: &impl BytecodeCompiler,
+    signer: &SuiKeypair,
+    gas_budget: u64,
+) -> Result<()> {
+    let package = verify_path(path)?;
+
+    // The framework revision of generated packages names the environment they compile against.
+    if let Some(generated_for) = parse_toml_environment(&package.toml) {
+        if generated_for != environment {
+            return Err(TokenGenErrors::InvalidInput(format!(
+                "The package was generated for {}, not {}",
+                generated_for, environment
+            )));
+        }
+    }
+
+    let mut record = DeploymentRecord::load(path)?;
+
+    let deployment = Deployment {
+        generator_version: record.generator_version.clone(),
+        generated_at: record.generated_at.clone(),
+        ..publish_package(path, fullnode, compiler, signer, gas_budget).await?
+    };
+
+    record
+        .deployments
+        .insert(environment.to_string(), deployment.clone());
+    record
+        .save(path)
+        .map_err(|e| unrecorded_deployment(e, &deployment))?;
+
+    handle_success(SuccessType::TokenPublished {
+        path: path.to_string(),
+        environment: environment.to_string(),
+        deployment,
+    });
+
+    Ok(())
+}
+
+// Adds the IDs of a published deployment to the error that kept it from being recorded.
+fn unrecorded_deployment(error: TokenGenErrors, deployment: &Deployment) -> TokenGenErrors {
+    let (kind, reason) = match error {
+        TokenGenErrors::FileIoError(e) => (e.kind(), e.to_string()),
+        e => (io::ErrorKind::Other, e.to_string()),
+    };
+    TokenGenErrors::FileIoError(io::Error::new(
+        kind,
+        format!(
+            "{}. The package was published but not recorded: package ID {}, TreasuryCap ID {}, CoinMetadata ID {}",
+            reason,
+            deployment.package_id,
+            deployment.treasury_cap_id.as_deref().unwrap_or("none"),
+            deployment.metadata_id.as_deref().unwrap_or("none"),
+        ),
+    ))
+}
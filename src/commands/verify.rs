@@ -1,10 +1,23 @@
-use tarpc::context;
+use futures::{stream, StreamExt};
+use std::path::Path;
 
 use crate::{
+    constants::{DEPLOYMENTS_FILE, MAX_CONCURRENT_VERIFICATIONS, SUB_FOLDER},
     errors::TokenGenErrors,
     handlers::{handle_success, SuccessType},
-    utils::client::rpc_client::TokenGenClient,
-    utils::{helpers::is_valid_repository_url, verify_helper::verify_path},
+    utils::client::{connection::RpcClient, responses::RpcResponseErrors},
+    utils::{
+        deployment::DeploymentRecord,
+        diff_report::ModificationReport,
+        discovery::{discover_packages, VerifyEntry, VerifyReport, VerifyStatus},
+        fullnode::FullnodeClient,
+        git::{GitCheckout, GitSource},
+        helpers::{is_valid_repository_url, normalize_sui_address},
+        onchain::{verify_onchain_package, BytecodeCompiler},
+        verify_helper::{
+            parse_token_header, parse_toml_environment, verify_package_locally, verify_path,
+        },
+    },
     Result,
 };
 
@@ -13,75 +26,224 @@ use crate::{
  *
  * This function performs the following steps:
  * 1. Validates the provided file path to ensure it contains a valid `.move` contract file.
- * 2. Reads the contract content from the specified path.
- * 3. Sends the contract content to the RPC client for verification.
+ * 2. Reads `Move.toml` and every file in `sources/` and `tests/` from the specified path.
+ * 3. Sends the package files to the RPC client for verification, or verifies them against the
+ *    embedded templates when no client is provided.
  * 4. Logs the success if verification is successful, or returns an appropriate error if verification fails.
+ *    A modified package is reported with its missing, extra and modified files, and a diff
+ *    against the sources generated from its header.
  *
  * # Parameters
  * - `path`: A string slice that represents the local file path to the token contract.
- * - `client`: An instance of `TokenGenClient` used to interact with the verification RPC service.
+ * - `client`: An instance of `RpcClient` used to interact with the verification RPC service,
+ *   or `None` to verify offline.
  *
  * # Returns
  * - `Ok(())` if the contract is successfully verified.
  * - `Err(TokenGenErrors)` if any validation or verification step fails.
  */
-pub async fn verify_token_from_path(path: &str, client: TokenGenClient) -> Result<()> {
-    // Validate the file path and ensure it contains valid contract content.
-    let verify_data = verify_path(path)?;
-
-    // Send the contract content to the RPC client for verification.
-    client
-        .verify_content(context::current(), verify_data.content, verify_data.toml)
-        .await
-        .map_err(TokenGenErrors::RpcError)?
-        .map_err(|e| TokenGenErrors::VerificationError(e.to_string()))?;
+pub async fn verify_token_from_path(path: &str, client: Option<RpcClient>) -> Result<()> {
+    let (file_name, environment) = verify_package(path, client.as_ref()).await?;
 
     // Log success message if verification is successful.
     handle_success(SuccessType::TokenVerified {
         path: Some(path.to_string()),
         url: None,
-        address: None,
-        environment: None,
-        file_name: Some(verify_data.file_name),
+        file_name: Some(file_name),
+        environment,
+        commit: None,
+        link: None,
     });
 
     Ok(())
 }
 
 /**
- * Verifies a token contract using a URL pointing to a Git repository.
+ * Verifies every Move package below a folder, several at a time.
+ *
+ * Packages are folders with a `Move.toml` and a `sources` folder. Each one is verified as by
+ * `verify_token_from_path`, sharing the RPC client, and the results are reported in a summary
+ * table sorted by package path.
+ *
+ * # Parameters
+ * - `root`: The folder to search for packages.
+ * - `client`: An instance of `RpcClient` used to interact with the verification RPC service,
+ *   or `None` to verify offline.
+ *
+ * # Returns
+ * - `Ok(())` if every package is verified.
+ * - `Err(TokenGenErrors::InvalidPath)` if no package is found.
+ * - `Err(TokenGenErrors::PackagesNotVerified)` if any package is modified, not generated by this
+ *   tool or could not be verified, with the per-package results.
+ */
+pub async fn verify_packages_recursively(root: &str, client: Option<RpcClient>) -> Result<()> {
+    let root_path = Path::new(root);
+    if !root_path.is_dir() {
+        return Err(TokenGenErrors::InvalidPathNotDirectory);
+    }
+
+    let packages = discover_packages(root_path)?;
+    if packages.is_empty() {
+        return Err(TokenGenErrors::InvalidPath(format!(
+            "No Move packages found in {}",
+            root
+        )));
+    }
+
+    // Verify several packages at a time, keeping the results in path order.
+    let entries = stream::iter(packages)
+        .map(|package| {
+            let client = client.as_ref();
+            async move {
+                let path = package.display().to_string();
+                let result = verify_package(&path, client).await;
+                verify_entry(path.clone(), result.map(|(file_name, _)| file_name))
+            }
+        })
+        .buffered(MAX_CONCURRENT_VERIFICATIONS)
+        .collect()
+        .await;
+
+    let report = VerifyReport {
+        root: root.to_string(),
+        entries,
+    };
+
+    if !report.all_verified() {
+        return Err(TokenGenErrors::PackagesNotVerified(Box::new(report)));
+    }
+
+    handle_success(SuccessType::PackagesVerified(report));
+    Ok(())
+}
+
+// Classifies the result of verifying a single package.
+fn verify_entry(path: String, result: Result<String>) -> VerifyEntry {
+    let mut entry = VerifyEntry {
+        path,
+        status: VerifyStatus::Verified,
+        file_name: None,
+        report: None,
+        error: None,
+    };
+
+    match result {
+        Ok(file_name) => entry.file_name = Some(file_name),
+        Err(TokenGenErrors::ContractModified(report)) if report.claimed.is_none() => {
+            entry.status = VerifyStatus::NotGenerated;
+        }
+        Err(TokenGenErrors::ContractModified(report)) => {
+            entry.status = VerifyStatus::Modified;
+            entry.report = Some(*report);
+        }
+        Err(error) => {
+            entry.status = VerifyStatus::Failed;
+            entry.error = Some(error.to_string());
+        }
+    }
+    entry
+}
+
+/**
+ * Verifies the package at a path.
+ *
+ * # Parameters
+ * - `path`: The package folder.
+ * - `client`: The RPC client, or `None` to verify offline.
+ *
+ * # Returns
+ * - `Ok((String, Option<String>))` with the file name of the main module and the environment of
+ *   the framework revision in `Move.toml` if the package is verified.
+ * - `Err(TokenGenErrors::ContractModified)` if the package differs from the generated one.
+ * - `Err(TokenGenErrors)` if the path is invalid or verification fails.
+ */
+async fn verify_package(
+    path: &str,
+    client: Option<&RpcClient>,
+) -> Result<(String, Option<String>)> {
+    // Validate the file path and ensure it contains valid contract content.
+    let verify_data = verify_path(path)?;
+
+    let result = match client {
+        // Send the package files to the RPC client for verification.
+        Some(client) => client.verify_package(&verify_data.files).await?,
+        // Re-render the package locally and compare it with the package files.
+        None => verify_package_locally(&verify_data.files),
+    };
+
+    match result {
+        Ok(()) => {}
+        // Report which lines differ from the generated source.
+        Err(RpcResponseErrors::ProgramModified) => {
+            return Err(TokenGenErrors::ContractModified(Box::new(
+                ModificationReport::new(&verify_data),
+            )));
+        }
+        // The service compared the package and reports a mismatch.
+        Err(RpcResponseErrors::VerifyResultError(message)) => {
+            return Err(TokenGenErrors::VerificationError(message));
+        }
+        // Any other error means the service could not verify the package at all.
+        Err(e) => {
+            return Err(TokenGenErrors::from_response(
+                e,
+                TokenGenErrors::ServerError,
+            ))
+        }
+    }
+
+    Ok((
+        verify_data.file_name,
+        parse_toml_environment(&verify_data.toml),
+    ))
+}
+
+/**
+ * Verifies a token contract in a Git repository.
  *
  * This function performs the following steps:
  * 1. Validates the provided URL to ensure it is a valid Git repository URL.
- * 2. Sends the URL to the RPC client for verification.
- * 3. Logs the success if verification is successful, or returns an appropriate error if verification fails.
+ * 2. Clones the repository into a temporary directory and checks out the requested branch, tag or commit.
+ * 3. Verifies the package in the requested folder of the checkout, as by `verify_token_from_path`.
+ * 4. Logs the success with the verified commit, or returns an appropriate error if verification fails.
  *
  * # Parameters
- * - `url`: A string slice that represents the URL pointing to the token contract (e.g., GitHub or GitLab repository).
- * - `client`: An instance of `TokenGenClient` used to interact with the verification RPC service.
+ * - `source`: The repository URL (any Git server, over HTTP(S), SSH or `file://`), the reference
+ *   to check out and the package folder inside the repository.
+ * - `client`: An instance of `RpcClient` used to interact with the verification RPC service,
+ *   or `None` to verify offline.
  *
  * # Returns
  * - `Ok(())` if the contract is successfully verified.
  * - `Err(TokenGenErrors)` if any validation or verification step fails.
  */
-pub async fn verify_token_using_url(url: &str, client: TokenGenClient) -> Result<()> {
+pub async fn verify_token_using_url(source: &GitSource, client: Option<RpcClient>) -> Result<()> {
     // Validate the URL to ensure it is a valid Git repository URL.
-    is_valid_repository_url(url)?;
+    is_valid_repository_url(&source.url)?;
 
-    // Send the URL to the RPC client for verification.
-    let verification_result = client
-        .verify_url(context::current(), url.to_string())
-        .await
-        .map_err(TokenGenErrors::RpcError)?
-        .map_err(|e| TokenGenErrors::VerificationError(e.to_string()))?;
+    // Cloning is blocking, so keep it off the async runtime.
+    let checkout = {
+        let source = source.clone();
+        tokio::task::spawn_blocking(move || GitCheckout::new(&source))
+            .await
+            .map_err(|e| git2::Error::from_str(&format!("Checkout task failed: {}", e)))??
+    };
+    let path = checkout
+        .package_path
+        .to_str()
+        .ok_or(TokenGenErrors::PathConversionError)?;
+
+    let (file_name, environment) = verify_package(path, client.as_ref()).await?;
+    let link = source.file_link(&format!("{}/{}", SUB_FOLDER, file_name), &checkout.commit);
 
     // Log success message if verification is successful.
     handle_success(SuccessType::TokenVerified {
         path: None,
-        url: Some(url.to_string()),
-        address: None,
-        environment: None,
-        file_name: Some(verification_result),
+        url: Some(source.url.clone()),
+        file_name: Some(file_name),
+        environment,
+        commit: Some(checkout.commit.clone()),
+        link,
     });
 
     Ok(())
@@ -91,14 +253,17 @@ pub async fn verify_token_using_url(url: &str, client: TokenGenClient) -> Result
  * Verifies a token contract using its blockchain address and environment.
  *
  * This function performs the following steps:
- * 1. Validates the provided blockchain address format.
- * 2. Sends the address and environment to the RPC client for verification.
- * 3. Logs the success if verification is successful, or returns an appropriate error if verification fails.
+ * 1. Validates the provided package address format.
+ * 2. Fetches the published module bytecode and the `CoinMetadata` of its coin from the fullnode.
+ * 3. Compiles the package this tool generates for the metadata and compares it with the published module.
+ * 4. Logs the name, symbol, decimals and frozen status of the coin if verification is successful,
+ *    or returns an appropriate error if verification fails.
  *
  * # Parameters
- * - `address`: A string slice representing the token contract's blockchain address.
+ * - `address`: A string slice representing the token package's blockchain address.
  * - `environment`: A string slice representing the blockchain environment (`mainnet`, `devnet`, `testnet`).
- * - `client`: An instance of `TokenGenClient` used to interact with the verification RPC service.
+ * - `fullnode`: The Sui fullnode of the environment the package is published on.
+ * - `compiler`: Compiles the generated package to bytecode.
  *
  * # Returns
  * - `Ok(())` if the token address is successfully verified.
@@ -107,25 +272,136 @@ pub async fn verify_token_using_url(url: &str, client: TokenGenClient) -> Result
 pub async fn verify_token_address(
     address: &str,
     environment: &str,
-    client: TokenGenClient,
+    fullnode: &FullnodeClient,
+    compiler: &impl BytecodeCompiler,
 ) -> Result<()> {
-    // Send the address and environment to the RPC client for verification.
-    client
-        .verify_address(
-            context::current(),
-            address.to_string(),
-            environment.to_string(),
+    let onchain = verify_onchain_package(address, environment, fullnode, compiler).await?;
+
+    // Log success message if verification is successful.
+    handle_success(SuccessType::AddressVerified(onchain));
+
+    Ok(())
+}
+
+/**
+ * Verifies a local package and every deployment recorded in its `deployments.toml`.
+ *
+ * This function performs the following steps:
+ * 1. Verifies the package at the path, as by `verify_token_from_path`.
+ * 2. Reads the deployments recorded by `publish` in the package folder.
+ * 3. Checks that each deployment is recorded for the environment of the framework revision in
+ *    `Move.toml`, then verifies its package ID on the fullnode of that environment, as by
+ *    `verify_token_address`.
+ * 4. Checks that each published coin has the parameters claimed by the local contract header,
+ *    and the recorded `CoinMetadata`.
+ *
+ * # Parameters
+ * - `path`: The package folder.
+ * - `client`: An instance of `RpcClient` used to verify the local package, or `None` to verify
+ *   it offline.
+ * - `fullnode`: Returns the Sui fullnode of an environment.
+ * - `compiler`: Compiles the generated package to bytecode.
+ *
+ * # Returns
+ * - `Ok(())` if the package and every deployment are verified.
+ * - `Err(TokenGenErrors::InvalidInput)` if no deployment is recorded.
+ * - `Err(TokenGenErrors)` if the package or a deployment fails verification, naming the
+ *   environment of the deployment.
+ */
+pub async fn verify_token_deployments(
+    path: &str,
+    client: Option<RpcClient>,
+    fullnode: impl Fn(&str) -> Result<FullnodeClient>,
+    compiler: &impl BytecodeCompiler,
+) -> Result<()> {
+    let (file_name, _) = verify_package(path, client.as_ref()).await?;
+
+    let record = DeploymentRecord::load(path)?;
+    if record.deployments.is_empty() {
+        return Err(TokenGenErrors::InvalidInput(format!(
+            "No deployments are recorded in {}",
+            Path::new(path).join(DEPLOYMENTS_FILE).display()
+        )));
+    }
+
+    // The package is verified, so its header records the parameters it was generated with.
+    let package = verify_path(path)?;
+    let local = parse_token_header(&package.content).ok_or_else(|| {
+        TokenGenErrors::VerificationError(format!("{} has no generator header", file_name))
+    })?;
+    let generated_for = parse_toml_environment(&package.toml);
+
+    let mut deployments = Vec::new();
+    for (environment, deployment) in &record.deployments {
+        let failed = |message: String| {
+            TokenGenErrors::VerificationError(format!(
+                "{} deployment {}: {}",
+                environment, deployment.package_id, message
+            ))
+        };
+
+        // A package only compiles to the published bytecode against its own framework revision.
+        if let Some(generated_for) = generated_for.as_ref().filter(|&env| env != environment) {
+            return Err(failed(format!(
+                "The package was generated for {}, not {}",
+                generated_for, environment
+            )));
+        }
+
+        let onchain = verify_onchain_package(
+            &deployment.package_id,
+            environment,
+            &fullnode(environment)?,
+            compiler,
         )
         .await
-        .map_err(TokenGenErrors::RpcError)?
-        .map_err(|e| TokenGenErrors::VerificationError(e.to_string()))?;
+        .map_err(|e| match e {
+            TokenGenErrors::VerificationError(message) => failed(message),
+            e => e,
+        })?;
+
+        let token = &onchain.token;
+        if (
+            &token.name,
+            &token.symbol,
+            token.decimals,
+            &token.description,
+            &token.icon_url,
+            token.max_supply,
+            token.is_frozen,
+        ) != (
+            &local.name,
+            &local.symbol,
+            local.decimals,
+            &local.description,
+            &local.icon_url,
+            local.max_supply,
+            local.is_frozen,
+        ) {
+            return Err(failed(format!(
+                "The published coin is {} ({}), not the token in {}",
+                token.name, token.symbol, path
+            )));
+        }
+
+        let recorded_metadata = deployment.metadata_id.as_deref().map(normalize_sui_address);
+        let published_metadata = onchain.metadata_id.as_deref().map(normalize_sui_address);
+        if let (Some(recorded), Some(published)) = (recorded_metadata, published_metadata) {
+            if recorded? != published? {
+                return Err(failed(
+                    "The CoinMetadata differs from the recorded one".to_string(),
+                ));
+            }
+        }
+
+        deployments.push(onchain);
+    }
+
     // Log success message if verification is successful.
-    handle_success(SuccessType::TokenVerified {
-        path: None,
-        url: None,
-        address: Some(address.to_string()),
-        environment: Some(environment.to_string()),
-        file_name: None,
+    handle_success(SuccessType::DeploymentsVerified {
+        path: path.to_string(),
+        file_name,
+        deployments,
     });
 
     Ok(())
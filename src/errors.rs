@@ -1,21 +1,31 @@
 //! Custom error types for the Sui Token Generator
 //!
 //! # Error Categories
-//! - Input validation errors (InvalidInput, InvalidGitUrl)
+//! - Input validation errors (InvalidInput, InvalidGitUrl, InvalidManifest, InvalidConfig, InvalidKeystore)
 //! - File system errors (FileIoError)
-//! - RPC communication errors (RpcError)
+//! - RPC communication errors (RpcError, ServerError, InvalidTlsConfig, Unauthorized, RateLimited, IncompatibleServer, FullnodeError)
 //! - Git operation errors (GitError)
-//! - Token verification errors (VerificationError)
+//! - Token verification errors (VerificationError, ContractModified, PackagesNotVerified)
 //! - User interaction errors (PromptError)
+//! - Batch creation errors (BatchCreationFailed)
+//! - Publishing errors (PublishError)
+//! - Compiler errors (CompilerError)
 //!
 //! Each error type includes context about what went wrong and how to potentially
-//! fix the issue.
+//! fix the issue, and belongs to an `ErrorCategory` that determines the process exit code.
 
 use inquire::error::InquireError;
 use std::io;
 use tarpc::client::RpcError;
 use thiserror::Error;
 
+use crate::utils::{
+    client::responses::RpcResponseErrors,
+    diff_report::ModificationReport,
+    discovery::{VerifyReport, VerifyStatus},
+    manifest::{BatchReport, BatchStatus},
+};
+
 /**
  * Enum representing all possible errors that can occur in the token generation process.
  *
@@ -31,14 +41,38 @@ pub enum TokenGenErrors {
     #[error("Unable to access the current working directory. Please ensure you have the required permissions.")]
     CurrentDirectoryError,
 
-    /// Error returned when the RPC server is not running or refuses the connection
-    #[error("Unable to connect to the RPC service.")]
-    FailedToConnectRpc,
+    /// Error returned when the RPC server is not running or refuses the connection, with the I/O error.
+    #[error("Unable to connect to the RPC service: {0}")]
+    FailedToConnectRpc(#[source] io::Error),
 
     /// Error returned when given RPC url is invalid
     #[error("Invalid RPC url")]
     InvalidRpcUrl,
 
+    /// Error returned when a TLS certificate or key cannot be loaded or used.
+    #[error("Invalid TLS configuration: {0}")]
+    InvalidTlsConfig(String),
+
+    /// Error returned when the RPC service rejects the API key, or none was given.
+    #[error("The RPC service requires a valid API key. Set one with --api-key or SUI_TOKEN_GEN_API_KEY.")]
+    Unauthorized,
+
+    /// Error returned when the API key exceeded the request quota of the RPC service.
+    #[error("The RPC service rate limit was exceeded. Retry in {0} seconds.")]
+    RateLimited(u64),
+
+    /// Error returned when the RPC service speaks another protocol version than this client.
+    #[error("The RPC service is incompatible with this client: {0}")]
+    IncompatibleServer(String),
+
+    /// Error returned when the RPC service fails to handle a request, e.g. it cannot clone or read a package.
+    #[error("The RPC service failed: {0}")]
+    ServerError(String),
+
+    /// Error returned when a request to a Sui fullnode fails or returns an error.
+    #[error("Sui fullnode request failed: {0}")]
+    FullnodeError(String),
+
     /// Error returned when desktop directory not found.
     #[error("Unable to locate the desktop directory")]
     DesktopDirectoryNotFound,
@@ -55,7 +89,7 @@ pub enum TokenGenErrors {
     #[error("Specified path is not a directory")]
     InvalidPathNotDirectory,
 
-    /// Error returned when the provided URL is not a valid GitHub or GitLab URL.
+    /// Error returned when the provided URL cannot be cloned as a Git repository.
     #[error("The provided URL is not a valid Git URL.")]
     InvalidGitUrl,
 
@@ -98,6 +132,194 @@ pub enum TokenGenErrors {
     /// Error returned for general verification failures, with details provided in the message.
     #[error("Verification failed: {0}")]
     VerificationError(String),
+
+    /// Error returned when a local contract differs from the generated source, with a diff report.
+    #[error("Verification failed: Contract has been modified\n{0}")]
+    ContractModified(Box<ModificationReport>),
+
+    /// Error returned when at least one package of a recursive verification failed, with per-package results.
+    #[error("Verification failed for {failed} of {total} packages\n{0}", failed = .0.entries.len() - .0.count(VerifyStatus::Verified), total = .0.entries.len())]
+    PackagesNotVerified(Box<VerifyReport>),
+
+    /// Error returned when a token manifest cannot be parsed or lists invalid entries.
+    #[error("Invalid manifest: {0}")]
+    InvalidManifest(String),
+
+    /// Error returned when a config file, profile or `SUI_TOKEN_GEN_*` variable is invalid.
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    /// Error returned when a Sui keystore cannot be read or holds no usable key.
+    #[error("Invalid keystore: {0}")]
+    InvalidKeystore(String),
+
+    /// Error returned when a package cannot be published, or its transaction failed on chain.
+    #[error("Failed to publish the package: {0}")]
+    PublishError(String),
+
+    /// Error returned when the Move compiler cannot be run, or fails to build a package.
+    #[error("The Move compiler failed: {0}")]
+    CompilerError(String),
+
+    /// Error returned when at least one manifest entry could not be created, with per-entry results.
+    #[error("Batch creation failed for {failed} of {total} tokens\n{0}", failed = .0.count(BatchStatus::Failed), total = .0.entries.len())]
+    BatchCreationFailed(Box<BatchReport>),
+}
+
+/// Category of an error, which determines the process exit code.
+///
+/// The exit codes are part of the CLI's stable interface so that scripts can tell, for example,
+/// a tampered contract apart from an unreachable RPC service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// Any failure not covered by a more specific category. Exit code `1`.
+    Failure,
+    /// Invalid command-line usage or input. Exit code `2`, matching argument parsing errors.
+    Usage,
+    /// The RPC service is unreachable or the connection failed. Exit code `3`.
+    Rpc,
+    /// The contract does not match the source generated by this tool. Exit code `4`.
+    Verification,
+    /// A filesystem operation failed. Exit code `5`.
+    FileSystem,
+    /// The user canceled an interactive prompt. Exit code `130`, as for an interrupted process.
+    Cancelled,
+}
+
+impl ErrorCategory {
+    /// Returns the process exit code reported for this category.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorCategory::Failure => 1,
+            ErrorCategory::Usage => 2,
+            ErrorCategory::Rpc => 3,
+            ErrorCategory::Verification => 4,
+            ErrorCategory::FileSystem => 5,
+            ErrorCategory::Cancelled => 130,
+        }
+    }
+
+    /// Returns a stable, machine-readable name for the category.
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorCategory::Failure => "failure",
+            ErrorCategory::Usage => "usage",
+            ErrorCategory::Rpc => "rpc",
+            ErrorCategory::Verification => "verification",
+            ErrorCategory::FileSystem => "filesystem",
+            ErrorCategory::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl TokenGenErrors {
+    /// Returns the category of the error, which determines the process exit code.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            TokenGenErrors::InvalidRpcUrl
+            | TokenGenErrors::InvalidPathNoMoveFiles
+            | TokenGenErrors::InvalidPathNotDirectory
+            | TokenGenErrors::InvalidGitUrl
+            | TokenGenErrors::InvalidInput(_)
+            | TokenGenErrors::InvalidPath(_)
+            | TokenGenErrors::InvalidUrl(_)
+            | TokenGenErrors::InvalidManifest(_)
+            | TokenGenErrors::InvalidConfig(_)
+            | TokenGenErrors::InvalidKeystore(_)
+            | TokenGenErrors::InvalidTlsConfig(_)
+            | TokenGenErrors::PromptError(InquireError::NotTTY) => ErrorCategory::Usage,
+
+            TokenGenErrors::FailedToConnectRpc(_)
+            | TokenGenErrors::RpcError(_)
+            | TokenGenErrors::Unauthorized
+            | TokenGenErrors::RateLimited(_)
+            | TokenGenErrors::IncompatibleServer(_)
+            | TokenGenErrors::ServerError(_)
+            | TokenGenErrors::FullnodeError(_) => ErrorCategory::Rpc,
+
+            TokenGenErrors::VerificationError(_)
+            | TokenGenErrors::ContractModified(_)
+            | TokenGenErrors::PackagesNotVerified(_) => ErrorCategory::Verification,
+
+            TokenGenErrors::CurrentDirectoryError
+            | TokenGenErrors::DesktopDirectoryNotFound
+            | TokenGenErrors::PathConversionError
+            | TokenGenErrors::FileIoError(_)
+            | TokenGenErrors::PromptError(InquireError::IO(_)) => ErrorCategory::FileSystem,
+
+            TokenGenErrors::PromptError(InquireError::OperationCanceled) => {
+                ErrorCategory::Cancelled
+            }
+
+            TokenGenErrors::FailedToCreateTokenContract(_)
+            | TokenGenErrors::GitError(_)
+            | TokenGenErrors::TeraError(_)
+            | TokenGenErrors::BatchCreationFailed(_)
+            | TokenGenErrors::PublishError(_)
+            | TokenGenErrors::CompilerError(_)
+            | TokenGenErrors::PromptError(_) => ErrorCategory::Failure,
+        }
+    }
+
+    /**
+     * Converts an error returned by the RPC service.
+     *
+     * Rejected API keys and exceeded quotas are reported as `Unauthorized` and `RateLimited`
+     * whatever the request; any other error is converted with `other` from its message.
+     */
+    pub fn from_response(
+        error: RpcResponseErrors,
+        other: impl FnOnce(String) -> TokenGenErrors,
+    ) -> TokenGenErrors {
+        match error {
+            RpcResponseErrors::Unauthorized => TokenGenErrors::Unauthorized,
+            RpcResponseErrors::RateLimited(seconds) => TokenGenErrors::RateLimited(seconds),
+            error => other(error.to_string()),
+        }
+    }
+
+    /// Returns the process exit code for the error.
+    pub fn exit_code(&self) -> i32 {
+        self.category().exit_code()
+    }
+
+    /// Returns a stable, machine-readable code identifying the error variant.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TokenGenErrors::CurrentDirectoryError => "CURRENT_DIRECTORY_ERROR",
+            TokenGenErrors::FailedToConnectRpc(_) => "FAILED_TO_CONNECT_RPC",
+            TokenGenErrors::InvalidRpcUrl => "INVALID_RPC_URL",
+            TokenGenErrors::InvalidTlsConfig(_) => "INVALID_TLS_CONFIG",
+            TokenGenErrors::Unauthorized => "UNAUTHORIZED",
+            TokenGenErrors::RateLimited(_) => "RATE_LIMITED",
+            TokenGenErrors::IncompatibleServer(_) => "INCOMPATIBLE_SERVER",
+            TokenGenErrors::ServerError(_) => "SERVER_ERROR",
+            TokenGenErrors::FullnodeError(_) => "FULLNODE_ERROR",
+            TokenGenErrors::DesktopDirectoryNotFound => "DESKTOP_DIRECTORY_NOT_FOUND",
+            TokenGenErrors::PathConversionError => "PATH_CONVERSION_ERROR",
+            TokenGenErrors::InvalidPathNoMoveFiles => "INVALID_PATH_NO_MOVE_FILES",
+            TokenGenErrors::InvalidPathNotDirectory => "INVALID_PATH_NOT_DIRECTORY",
+            TokenGenErrors::InvalidGitUrl => "INVALID_GIT_URL",
+            TokenGenErrors::FailedToCreateTokenContract(_) => "FAILED_TO_CREATE_TOKEN_CONTRACT",
+            TokenGenErrors::InvalidInput(_) => "INVALID_INPUT",
+            TokenGenErrors::InvalidPath(_) => "INVALID_PATH",
+            TokenGenErrors::InvalidUrl(_) => "INVALID_URL",
+            TokenGenErrors::GitError(_) => "GIT_ERROR",
+            TokenGenErrors::FileIoError(_) => "FILE_IO_ERROR",
+            TokenGenErrors::TeraError(_) => "TEMPLATE_ERROR",
+            TokenGenErrors::PromptError(_) => "PROMPT_ERROR",
+            TokenGenErrors::RpcError(_) => "RPC_ERROR",
+            TokenGenErrors::VerificationError(_) => "VERIFICATION_FAILED",
+            TokenGenErrors::ContractModified(_) => "CONTRACT_MODIFIED",
+            TokenGenErrors::PackagesNotVerified(_) => "PACKAGES_NOT_VERIFIED",
+            TokenGenErrors::InvalidManifest(_) => "INVALID_MANIFEST",
+            TokenGenErrors::InvalidConfig(_) => "INVALID_CONFIG",
+            TokenGenErrors::InvalidKeystore(_) => "INVALID_KEYSTORE",
+            TokenGenErrors::PublishError(_) => "PUBLISH_FAILED",
+            TokenGenErrors::CompilerError(_) => "COMPILER_ERROR",
+            TokenGenErrors::BatchCreationFailed(_) => "BATCH_CREATION_FAILED",
+        }
+    }
 }
 
 /// Implements conversion from `TokenGenErrors` to `io::Error`.
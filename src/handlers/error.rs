@@ -1,15 +1,18 @@
 use colored::*;
+use serde_json::{json, Value};
 
+use super::output::{output_format, OutputFormat};
 use crate::errors::TokenGenErrors;
 
 /**
  * Centralized error handler for managing errors in a consistent way across the application.
  *
  * This function provides a streamlined mechanism for handling errors by:
- * 1. Logging the error message in a formatted and styled manner for better visibility.
- * 2. Terminating the process with an exit code of `1` to indicate failure.
+ * 1. Logging the error message in a formatted and styled manner for better visibility,
+ *    or as a JSON object with a stable error code when JSON output is selected.
+ * 2. Terminating the process with the exit code of the error's category to indicate failure.
  *
- * The handler eliminates the need for manually calling `.log()` and `std::process::exit(1)`
+ * The handler eliminates the need for manually calling `.log()` and `std::process::exit`
  * in multiple places, ensuring a single point of error handling logic.
  *
  * # Arguments
@@ -21,7 +24,8 @@ use crate::errors::TokenGenErrors;
  *
  * # Behavior on Error
  * - If the result is `Err`, it logs the error message to `stderr` with a red, bold "ERROR: "
- *   prefix and terminates the process with an exit code of `1`.
+ *   prefix (or the JSON object to `stdout`) and terminates the process with the exit code of the
+ *   error's category (see `ErrorCategory`).
  */
 pub fn handle_error<T>(result: Result<T, TokenGenErrors>) -> T {
     match result {
@@ -30,9 +34,36 @@ pub fn handle_error<T>(result: Result<T, TokenGenErrors>) -> T {
 
         // If the result is `Err`, log the error and terminate the process
         Err(error) => {
-            let error_prefix = "ERROR: ".red().bold(); // Styled prefix for the error message
-            eprintln!("{} {}", error_prefix, error); // Log the error message to `stderr`
-            std::process::exit(1); // Terminate the process with an exit code of `1`
+            match output_format() {
+                OutputFormat::Json => println!("{}", error_json(&error)), // Log the error object to `stdout`
+                OutputFormat::Text => {
+                    let error_prefix = "ERROR: ".red().bold(); // Styled prefix for the error message
+                    eprintln!("{} {}", error_prefix, error); // Log the error message to `stderr`
+                }
+            }
+            std::process::exit(error.exit_code()); // Terminate the process with the category's exit code
         }
     }
 }
+
+/// Builds the JSON object reported for an error, including the diff report for modified contracts
+/// and the per-entry results of a failed batch creation or recursive verification.
+pub fn error_json(error: &TokenGenErrors) -> Value {
+    let message = error.to_string();
+    let mut value = json!({
+        "status": "error",
+        "code": error.code(),
+        "category": error.category().name(),
+        "exit_code": error.exit_code(),
+        "message": message.lines().next().unwrap_or_default(),
+    });
+
+    match error {
+        TokenGenErrors::ContractModified(report) => value["report"] = json!(report),
+        TokenGenErrors::BatchCreationFailed(report) => value["report"] = json!(report),
+        TokenGenErrors::PackagesNotVerified(report) => value["report"] = json!(report),
+        _ => {}
+    }
+
+    value
+}
@@ -3,11 +3,14 @@
 //! This module contains handlers for managing command responses:
 //! - Error handling (`error`)
 //! - Success handling (`success`)
+//! - Output format selection (`output`)
 //!
 //! Each submodule provides specific response handling functionality.
 
 mod error;
+mod output;
 mod success;
 
-pub use error::handle_error;
-pub use success::{handle_success, SuccessType};
+pub use error::{error_json, handle_error};
+pub use output::{handle_status, output_format, set_output_format, OutputFormat};
+pub use success::{handle_success, success_json, SuccessType};
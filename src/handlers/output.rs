@@ -0,0 +1,35 @@
+use clap::ValueEnum;
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+
+// Output format selected for the current process, set once from the command line.
+static OUTPUT_FORMAT: OnceCell<OutputFormat> = OnceCell::new();
+
+/// Format used to report command results and errors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Coloured, human-readable text.
+    #[default]
+    Text,
+
+    /// A single JSON object per result or error, written to `stdout`.
+    Json,
+}
+
+/// Sets the output format for the rest of the process. Later calls are ignored.
+pub fn set_output_format(format: OutputFormat) {
+    let _ = OUTPUT_FORMAT.set(format);
+}
+
+/// Returns the output format selected for the process, defaulting to text.
+pub fn output_format() -> OutputFormat {
+    OUTPUT_FORMAT.get().copied().unwrap_or_default()
+}
+
+/// Prints a progress message in text mode; suppressed in JSON mode to keep `stdout` parseable.
+pub fn handle_status(message: &str) {
+    if output_format() == OutputFormat::Text {
+        println!("{}", message);
+    }
+}
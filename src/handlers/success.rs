@@ -1,11 +1,23 @@
-use crate::{constants::DEFAULT_ENVIRONMENT, utils::prompts::TokenInfo};
+use crate::{
+    constants::{PROTOCOL_VERSION, VERSION},
+    utils::{
+        client::responses::ServerCapabilities, deployment::Deployment, discovery::VerifyReport,
+        manifest::BatchReport, onchain::OnChainToken, prompts::TokenInfo,
+    },
+};
 use colored::*;
+use serde_json::{json, Value};
+
+use super::output::{output_format, OutputFormat};
 
 /// Enum to define different types of success events that can occur during token generation or verification.
 #[derive(Debug)]
 pub enum SuccessType {
-    /// Represents the success of a token creation process with token details.
-    TokenCreated(TokenInfo, String),
+    /// Represents the success of a token creation process with token details, a message and the written file paths.
+    TokenCreated(TokenInfo, String, Vec<String>),
+
+    /// Represents the success of a batch creation from a manifest, with the per-entry results.
+    TokensCreated(BatchReport),
 
     /// Represents the success of a token verification process, which could be from a path or URL.
     TokenVerified {
@@ -15,79 +27,316 @@ pub enum SuccessType {
         /// Optional URL where the token was verified.
         url: Option<String>,
 
-        /// Optional address where the token was verified.
-        address: Option<String>,
+        /// Optional verified file name.
+        file_name: Option<String>,
 
-        /// Optional environment where the token was deployed.
+        /// Optional environment the package was generated for, from its `Move.toml`.
         environment: Option<String>,
 
-        /// Optional verified file name.
-        file_name: Option<String>,
+        /// Optional commit the repository was verified at.
+        commit: Option<String>,
+
+        /// Optional web link of the verified file in the repository.
+        link: Option<String>,
+    },
+
+    /// Represents the success of an on-chain verification, with the coin read from the chain.
+    AddressVerified(OnChainToken),
+
+    /// Represents the success of a local package verification and of every recorded deployment.
+    DeploymentsVerified {
+        /// Folder of the verified package.
+        path: String,
+
+        /// Verified file name.
+        file_name: String,
+
+        /// Every recorded deployment, as verified on chain.
+        deployments: Vec<OnChainToken>,
+    },
+
+    /// Represents the success of a recursive verification, with the per-package results.
+    PackagesVerified(VerifyReport),
+
+    /// Represents a package published to an environment, with the objects it created.
+    TokenPublished {
+        /// Folder of the published package.
+        path: String,
+
+        /// Environment the package was published to.
+        environment: String,
+
+        /// Recorded deployment of the package.
+        deployment: Deployment,
+    },
+
+    /// Represents a successful `doctor` check of the RPC service at the given address.
+    Doctor {
+        /// Address of the RPC service.
+        rpc: String,
+
+        /// What the RPC service reported in the handshake.
+        server: ServerCapabilities,
     },
 }
 
 /// Centralized success handler that formats and prints a success message based on the success type.
+///
+/// With JSON output selected, a single JSON object describing the result is printed instead.
 pub fn handle_success(success_type: SuccessType) {
+    if output_format() == OutputFormat::Json {
+        println!("{}", success_json(&success_type));
+        return;
+    }
+
     let success_prefix = "SUCCESS: ".green().bold();
 
     let message = match success_type {
-        SuccessType::TokenCreated(token_info, message) => format!(
-            "{}\nToken Details:\n  Name: {}\n  Symbol: {}\n  Decimals: {}\n  Description: {}\n  Frozen: {}\n  Environment: {}\n{}{}",
+        SuccessType::TokenCreated(token_info, message, _) => format!(
+            "{}\nToken Details:\n  Name: {}\n  Symbol: {}\n  Decimals: {}\n  Description: {}\n  Icon URL: {}\n  Max supply: {}\n  Frozen: {}\n  Environment: {}\n{}{}",
             message,
             token_info.name,
             token_info.symbol,
             token_info.decimals,
             token_info.description.if_empty("None"),
+            token_info.icon_url.as_deref().unwrap_or("None"),
+            token_info
+                .max_supply
+                .map_or("Unlimited".to_string(), |supply| supply.to_string()),
             token_info.is_frozen.to_yes_no(),
             token_info.environment,
             "Note: ".yellow(),
             "These files are automatically generated and are not intended for manual editing.",
         ),
 
+        SuccessType::TokensCreated(report) => format!(
+            "Created {} tokens from: {}\n{}\n{}{}",
+            report.entries.len(),
+            report.manifest,
+            report,
+            "Note: ".yellow(),
+            "These files are automatically generated and are not intended for manual editing.",
+        ),
+
         SuccessType::TokenVerified {
             path,
             url,
-            address,
-            environment,
             file_name,
+            environment,
+            commit,
+            link,
         } => {
-            if let Some(addr) = address {
-                format!(
-                    "Verified: {} coin on {} was generated using the SUI Token Gen CLI.",
-                    addr,
-                    environment.unwrap_or_else(|| DEFAULT_ENVIRONMENT.to_string())
-                )
-            } else {
-                let source = path.or(url.clone()).unwrap_or_default();
-                let file_info = file_name.as_ref().map_or_else(
-                    || format!("Verified successfully from: {}", source),
-                    |file| {
-                        // If URL exists, make the file name clickable, otherwise just show text
-                        let clickable_file = url
-                            .as_ref()
-                            .map(|repo_url| format!(
-                                "\x1b]8;;{}/blob/main/sources/{}\x1b\\{}\x1b]8;;\x1b\\",
-                                repo_url, file, file
-                            ))
-                            .unwrap_or_else(|| file.clone());
-
-                        format!("Verified source file: {} from {}", clickable_file.blue(), source)
-                    },
-                );
-
-                format!(
-                    "{}\n{}{}",
-                    file_info,
-                    "Note: ".yellow(),
-                    "This code is generated by this tool and remains unmodified. Verification ensures it matches the tool's output but may not fully align with the published module."
-                )
-            }
+            let source = match (path.or(url), commit) {
+                (Some(source), Some(commit)) => format!("{} at {}", source, commit),
+                (source, _) => source.unwrap_or_default(),
+            };
+            let file_info = file_name.as_ref().map_or_else(
+                || format!("Verified successfully from: {}", source),
+                |file| {
+                    // If the file has a web link, make the file name clickable, otherwise just show text
+                    let clickable_file = link
+                        .as_ref()
+                        .map(|link| format!(
+                            "\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\",
+                            link, file
+                        ))
+                        .unwrap_or_else(|| file.clone());
+
+                    format!("Verified source file: {} from {}", clickable_file.blue(), source)
+                },
+            );
+
+            let environment = environment
+                .as_ref()
+                .map_or_else(String::new, |environment| {
+                    format!("Environment: {}\n", environment)
+                });
+
+            format!(
+                "{}\n{}{}{}",
+                file_info,
+                environment,
+                "Note: ".yellow(),
+                "This code is generated by this tool and remains unmodified. Verification ensures it matches the tool's output but may not fully align with the published module."
+            )
         }
+
+        SuccessType::AddressVerified(onchain) => format!(
+            "Verified: {} coin on {} was generated using the SUI Token Gen CLI.\nToken Details:\n  Name: {}\n  Symbol: {}\n  Decimals: {}\n  Description: {}\n  Icon URL: {}\n  Max supply: {}\n  Frozen: {}\n  Coin type: {}",
+            onchain.package_id,
+            onchain.token.environment,
+            onchain.token.name,
+            onchain.token.symbol,
+            onchain.token.decimals,
+            onchain.token.description.if_empty("None"),
+            onchain.token.icon_url.as_deref().unwrap_or("None"),
+            onchain
+                .token
+                .max_supply
+                .map_or("Unlimited".to_string(), |supply| supply.to_string()),
+            onchain.token.is_frozen.to_yes_no(),
+            onchain.coin_type,
+        ),
+
+        SuccessType::DeploymentsVerified {
+            path,
+            file_name,
+            deployments,
+        } => format!(
+            "Verified source file: {} from {}\nVerified deployments:\n{}",
+            file_name.blue(),
+            path,
+            deployments
+                .iter()
+                .map(|onchain| format!(
+                    "  {}: {} ({})",
+                    onchain.token.environment, onchain.package_id, onchain.coin_type
+                ))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+
+        SuccessType::PackagesVerified(report) => format!(
+            "Verified {} packages in: {}\n{}",
+            report.entries.len(),
+            report.root,
+            report
+        ),
+
+        SuccessType::TokenPublished {
+            path,
+            environment,
+            deployment,
+        } => format!(
+            "Published {} to {}.\n  Package ID: {}\n  TreasuryCap: {}\n  CoinMetadata: {}\n  Transaction: {}\n{}{}",
+            path,
+            environment,
+            deployment.package_id,
+            deployment.treasury_cap_id.as_deref().unwrap_or("None"),
+            deployment.metadata_id.as_deref().unwrap_or("None"),
+            deployment.digest,
+            "Note: ".yellow(),
+            "The deployment is recorded in deployments.toml in the package folder.",
+        ),
+
+        SuccessType::Doctor { rpc, server } => format!(
+            "The RPC service is compatible with this client.\n  Client version: {} (protocol {})\n  RPC service: {}\n  Server version: {} (protocol {})\n  Environments: {}\n  API key required: {}",
+            VERSION,
+            PROTOCOL_VERSION,
+            rpc,
+            server.version,
+            server.protocol_version,
+            server.environments.join(", "),
+            server.requires_api_key.to_yes_no(),
+        ),
     };
 
     println!("{}{}", success_prefix, message);
 }
 
+/// Builds the JSON object reported for a successful command.
+pub fn success_json(success_type: &SuccessType) -> Value {
+    match success_type {
+        SuccessType::TokenCreated(token_info, message, files) => json!({
+            "status": "success",
+            "command": "create",
+            "message": message,
+            "token": token_info,
+            "files": files,
+        }),
+
+        SuccessType::TokensCreated(report) => json!({
+            "status": "success",
+            "command": "create",
+            "manifest": report.manifest,
+            "entries": report.entries,
+        }),
+
+        SuccessType::TokenVerified {
+            path,
+            url,
+            file_name,
+            environment,
+            commit,
+            link,
+        } => {
+            let (source_type, source) = if let Some(url) = url {
+                ("url", url.as_str())
+            } else {
+                ("path", path.as_ref().map_or("", |path| path.as_str()))
+            };
+
+            json!({
+                "status": "success",
+                "command": "verify",
+                "source_type": source_type,
+                "source": source,
+                "file_name": file_name,
+                "environment": environment,
+                "commit": commit,
+                "link": link,
+                "result": "verified",
+            })
+        }
+
+        SuccessType::AddressVerified(onchain) => json!({
+            "status": "success",
+            "command": "verify",
+            "source_type": "address",
+            "source": onchain.package_id,
+            "environment": onchain.token.environment,
+            "token": onchain.token,
+            "coin_type": onchain.coin_type,
+            "metadata_id": onchain.metadata_id,
+            "result": "verified",
+        }),
+
+        SuccessType::DeploymentsVerified {
+            path,
+            file_name,
+            deployments,
+        } => json!({
+            "status": "success",
+            "command": "verify",
+            "source_type": "path",
+            "source": path,
+            "file_name": file_name,
+            "deployments": deployments,
+            "result": "verified",
+        }),
+
+        SuccessType::PackagesVerified(report) => json!({
+            "status": "success",
+            "command": "verify",
+            "root": report.root,
+            "entries": report.entries,
+        }),
+
+        SuccessType::TokenPublished {
+            path,
+            environment,
+            deployment,
+        } => json!({
+            "status": "success",
+            "command": "publish",
+            "path": path,
+            "environment": environment,
+            "deployment": deployment,
+        }),
+
+        SuccessType::Doctor { rpc, server } => json!({
+            "status": "success",
+            "command": "doctor",
+            "client": {
+                "version": VERSION,
+                "protocol_version": PROTOCOL_VERSION,
+            },
+            "rpc": rpc,
+            "server": server,
+        }),
+    }
+}
+
 /// Utility extension for `String` to return a fallback value if empty.
 trait IfEmpty {
     fn if_empty(&self, fallback: &str) -> String;
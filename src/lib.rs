@@ -0,0 +1,87 @@
+//! # Sui Token Generator
+//!
+//! Library backing the `sui-token-gen` CLI and the `sui-token-gen-server` RPC server.
+//!
+//! - `commands`: Implementations of the CLI subcommands.
+//! - `server`: Reference implementation of the `TokenGen` RPC service.
+//! - `utils`: Contract generation, verification, prompts and the RPC client.
+//!
+use clap::Parser;
+use errors::TokenGenErrors;
+pub use utils::constants;
+
+pub mod commands;
+pub mod errors;
+pub mod handlers;
+pub mod server;
+#[cfg(test)]
+pub mod tests;
+pub mod utils;
+
+/// Result type for the application, using custom error handling.
+pub type Result<T> = std::result::Result<T, TokenGenErrors>;
+
+/// Optional token parameters for the `create` command; missing values are prompted for.
+#[derive(Debug, Clone, Parser)]
+pub struct CreateTokenParams {
+    /// Optional RPC URL for this command.
+    #[arg(short, long)]
+    pub rpc: Option<String>,
+
+    /// Token name.
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// Token symbol.
+    #[arg(short, long)]
+    pub symbol: Option<String>,
+
+    /// Token decimals.
+    #[arg(long)]
+    pub decimals: Option<u8>,
+
+    /// Token description.
+    #[arg(short, long)]
+    pub description: Option<String>,
+
+    /// URL of the token icon shown in wallets.
+    #[arg(long)]
+    pub icon_url: Option<String>,
+
+    /// Total supply in whole tokens, minted to the publisher once, after which minting is disabled.
+    /// Omit for an unlimited supply.
+    #[arg(long)]
+    pub max_supply: Option<u64>,
+
+    /// Whether metadata is frozen.
+    #[arg(short, long)]
+    pub is_frozen: Option<bool>,
+
+    /// Token creation environment.
+    #[arg(short, long)]
+    pub environment: Option<String>,
+
+    /// Generate the contract locally from the embedded templates instead of calling the RPC service.
+    #[arg(long, conflicts_with = "rpc")]
+    pub offline: bool,
+
+    /// Never prompt; fail if a required value is missing. Implied when stdin is not a terminal.
+    #[arg(long, visible_alias = "yes")]
+    pub non_interactive: bool,
+
+    /// Write into package folders that already exist instead of failing.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Create every token listed in a TOML, JSON or YAML manifest instead of a single token.
+    /// `--environment` then applies to entries that do not specify one.
+    #[arg(
+        long,
+        conflicts_with_all = ["name", "symbol", "decimals", "description", "icon_url", "max_supply", "is_frozen"]
+    )]
+    pub manifest: Option<String>,
+
+    /// With `--manifest`, keep creating the remaining tokens after one fails.
+    #[arg(long, requires = "manifest")]
+    pub continue_on_error: bool,
+}
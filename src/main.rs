@@ -3,28 +3,32 @@
 //! The Sui Token Generator CLI is a command-line tool designed for developers to:
 //! - Create custom Sui token contracts with flexible parameters.
 //! - Verify existing token contracts via local files, Git repositories, or blockchain addresses.
+//! - Publish generated token packages to a Sui network.
 //!
 //! The tool operates on a client-server architecture, communicating with an RPC server for token creation and verification tasks.
 //!
-use clap::{Parser, Subcommand};
-use commands::{create, verify};
-use errors::TokenGenErrors;
-use handlers::handle_error;
-pub use utils::constants;
-use utils::{
-    client::rpc_client::{initiate_client, TokenGenClient}, constants::DEFAULT_ENVIRONMENT, helpers::validate_rpc_url
+use clap::{ArgGroup, Parser, Subcommand};
+use std::time::Duration;
+use sui_token_gen::{
+    commands::{create, doctor, publish, verify},
+    constants,
+    errors::TokenGenErrors,
+    handlers::{handle_error, set_output_format, OutputFormat},
+    utils::{
+        client::connection::{CallOptions, RpcClient},
+        config::Settings,
+        constants::DEFAULT_ENVIRONMENT,
+        fullnode::FullnodeClient,
+        git::GitSource,
+        keystore::{default_keystore_path, load_keypair},
+        onchain::SuiCliCompiler,
+        tls::TlsOptions,
+        validation::validate_environment,
+        verify_helper,
+    },
+    CreateTokenParams, Result,
 };
 
-mod commands;
-mod errors;
-mod handlers;
-#[cfg(test)]
-pub mod tests;
-mod utils;
-
-/// Result type for the application, using custom error handling.
-pub type Result<T> = std::result::Result<T, TokenGenErrors>;
-
 /// # Sui Token Generator CLI Tool
 ///
 /// A command-line interface (CLI) tool to create and verify Sui token contracts.
@@ -37,7 +41,7 @@ pub type Result<T> = std::result::Result<T, TokenGenErrors>;
 #[derive(Parser, Debug)]
 #[command(
     author = "Osec",
-    version = "1.0.0",
+    version,
     about = "Create and verify Sui Coin contracts",
     long_about = "Sui Token Generator is a CLI tool for developers to create and verify token contracts effortlessly."
 )]
@@ -45,37 +49,38 @@ struct Cli {
     /// Available subcommands for the CLI tool.
     #[command(subcommand)]
     command: Commands,
-}
 
-#[derive(Debug, Clone, Parser)]
-pub struct CreateTokenParams {
-    /// Optional RPC URL for this command.
-    #[arg(short, long)]
-    rpc: Option<String>,
+    /// Output format for results and errors. Defaults to `text`.
+    #[arg(long, global = true, value_enum)]
+    output: Option<OutputFormat>,
+
+    /// Named profile from the config files to use for defaults.
+    #[arg(long, global = true)]
+    profile: Option<String>,
 
-    /// Token name.
-    #[arg(short, long)]
-    name: Option<String>,
+    /// PEM bundle of CA certificates trusted for a `tls://` RPC address. Defaults to the system roots.
+    #[arg(long, global = true)]
+    ca_cert: Option<String>,
 
-    /// Token symbol.
-    #[arg(short, long)]
-    symbol: Option<String>,
+    /// PEM client certificate presented to a `tls://` RPC address.
+    #[arg(long, global = true, requires = "client_key")]
+    client_cert: Option<String>,
 
-    /// Token decimals.
-    #[arg(long)]
-    decimals: Option<u8>,
+    /// PEM private key of the client certificate.
+    #[arg(long, global = true, requires = "client_cert")]
+    client_key: Option<String>,
 
-    /// Token description.
-    #[arg(short, long)]
-    description: Option<String>,
+    /// API key for RPC services that restrict access. Prefer `SUI_TOKEN_GEN_API_KEY` on shared machines.
+    #[arg(long, global = true)]
+    api_key: Option<String>,
 
-    /// Whether metadata is frozen.
-    #[arg(short, long)]
-    is_frozen: Option<bool>,
+    /// Deadline of each RPC call and connection attempt, in seconds. Defaults to 10.
+    #[arg(long, global = true, value_parser = clap::value_parser!(u64).range(1..=constants::MAX_RPC_TIMEOUT_SECS))]
+    timeout: Option<u64>,
 
-    /// Token creation environment.
-    #[arg(short, long)]
-    environment: Option<String>,
+    /// Number of retries of verification calls after a timeout or dropped connection. Defaults to 2.
+    #[arg(long, global = true, value_parser = clap::value_parser!(u32).range(0..=constants::MAX_RPC_RETRIES as i64))]
+    retries: Option<u32>,
 }
 
 /// Enum for supported subcommands.
@@ -87,6 +92,7 @@ enum Commands {
 
     /// Verify an existing Sui token contract from a repository, local file, or blockchain address.
     #[command(about = "Verifies an existing contract from a repo, local file, or token address.")]
+    #[command(group(ArgGroup::new("onchain").args(["address", "all_deployments"]).multiple(true)))]
     Verify {
         /// Optional RPC URL for this command.
         #[arg(short, long)]
@@ -96,10 +102,18 @@ enum Commands {
         #[arg(short, long)]
         path: Option<String>,
 
-        /// URL of the repository containing the contract.
+        /// URL of the Git repository containing the contract, over HTTP(S), SSH or `file://`.
         #[arg(short, long)]
         url: Option<String>,
 
+        /// Branch, tag or commit of the repository to verify. Defaults to the default branch.
+        #[arg(long = "ref", requires = "url")]
+        reference: Option<String>,
+
+        /// Folder of the package inside the repository. Defaults to the repository root.
+        #[arg(long, requires = "url")]
+        subdir: Option<String>,
+
         /// Token contract address on the blockchain.
         #[arg(short, long)]
         address: Option<String>,
@@ -107,22 +121,86 @@ enum Commands {
         /// Blockchain environment (mainnet, devnet, testnet).
         #[arg(short, long)]
         environment: Option<String>,
+
+        /// Sui fullnode JSON-RPC URL used with `--address` and `--all-deployments`. Defaults to the public fullnode of the environment.
+        #[arg(long, requires = "onchain")]
+        fullnode: Option<String>,
+
+        /// Verify the local or cloned package against the embedded templates instead of calling the RPC service.
+        #[arg(long, conflicts_with_all = ["rpc", "address"])]
+        offline: bool,
+
+        /// Verify every Move package below `--path`, reporting a summary of all of them.
+        #[arg(long, requires = "path", conflicts_with_all = ["url", "address"])]
+        recursive: bool,
+
+        /// Also verify every deployment recorded in the `deployments.toml` of the package at `--path`.
+        #[arg(long, requires = "path", conflicts_with_all = ["url", "address", "recursive"])]
+        all_deployments: bool,
+    },
+
+    /// Publish a generated token package to a Sui network and record its deployment.
+    #[command(about = "Publishes a generated token package and records its deployment.")]
+    Publish {
+        /// Folder of the package, as written by `create`.
+        #[arg(short, long, default_value = ".")]
+        path: String,
+
+        /// Blockchain environment (mainnet, devnet, testnet). Defaults to the environment the package was generated for.
+        #[arg(short, long)]
+        environment: Option<String>,
+
+        /// Sui fullnode JSON-RPC URL. Defaults to the public fullnode of the environment.
+        #[arg(long)]
+        fullnode: Option<String>,
+
+        /// Sui keystore holding the publisher's key. Defaults to the keystore of the Sui CLI.
+        #[arg(long)]
+        keystore: Option<String>,
+
+        /// Address of the publisher in the keystore. Defaults to its first key.
+        #[arg(long)]
+        sender: Option<String>,
+
+        /// Gas budget of the publish transaction, in MIST.
+        #[arg(long, default_value_t = constants::DEFAULT_GAS_BUDGET)]
+        gas_budget: u64,
+    },
+
+    /// Check that the RPC service is reachable and compatible with this client.
+    #[command(about = "Reports the client and RPC service versions and supported environments.")]
+    Doctor {
+        /// Optional RPC URL for this command.
+        #[arg(short, long)]
+        rpc: Option<String>,
     },
 }
 
 /// Main asynchronous entry point.
 ///
-/// Parses command-line arguments, executes the selected subcommand, and handles errors.
+/// Parses command-line arguments, loads the config files, executes the selected subcommand, and handles errors.
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
-    handle_error(run_cli(cli).await);
+    let settings = Settings::load(cli.profile.as_deref());
+
+    // The output format is needed to report config errors, so it falls back to the flag alone
+    let output = cli
+        .output
+        .or_else(|| settings.as_ref().ok().and_then(|settings| settings.output));
+    set_output_format(output.unwrap_or_default());
+
+    handle_error(match settings {
+        Ok(settings) => run_cli(cli, settings).await,
+        Err(error) => Err(error),
+    });
 }
 
 /// Executes the selected CLI subcommand based on user input.
 ///
 /// # Arguments
 /// * `cli` - The parsed CLI arguments containing the subcommand and its options
+/// * `settings` - Defaults from the config files and environment, overridden by the flags
 ///
 /// # Returns
 /// * `Ok(())` - Command executed successfully
@@ -130,43 +208,78 @@ async fn main() {
 ///
 /// # Details
 /// Handles three main verification methods:
-/// * `Verify` with `--path`: Validates a contract from a local file.
-/// * `Verify` with `--url`: Validates a contract from a repository.
-/// * `Verify` with `--address` and `--environment`: Verifies a token contract by its blockchain address.
-async fn run_cli(cli: Cli) -> Result<()> {
+/// * `Verify` with `--path`: Validates a contract from a local file, optionally `--offline`.
+/// * `Verify` with `--path` and `--recursive`: Validates every package below a folder.
+/// * `Verify` with `--path` and `--all-deployments`: Validates a package and every deployment recorded by `publish`.
+/// * `Verify` with `--url`: Clones a repository, optionally at `--ref`, and validates the package at `--subdir`.
+/// * `Verify` with `--address` and `--environment`: Verifies a published package against the Sui fullnode of the environment.
+/// * `Publish`: Publishes a package through the Sui fullnode of the environment and records its deployment.
+/// * `Doctor`: Reports the client and RPC service versions after the connection handshake.
+async fn run_cli(cli: Cli, settings: Settings) -> Result<()> {
+    // Certificates for `tls://` RPC addresses, from the flags or the settings
+    let tls = TlsOptions {
+        ca_cert: cli.ca_cert.clone().or(settings.ca_cert),
+        client_cert: cli.client_cert.clone().or(settings.client_cert),
+        client_key: cli.client_key.clone().or(settings.client_key),
+    };
+    let api_key = cli.api_key.clone().or(settings.api_key);
+
+    // Deadline and retries of RPC calls, from the flags or the settings
+    let defaults = CallOptions::default();
+    let options = CallOptions {
+        timeout: cli
+            .timeout
+            .or(settings.timeout)
+            .map_or(defaults.timeout, Duration::from_secs),
+        retries: cli.retries.or(settings.retries).unwrap_or(defaults.retries),
+        ..defaults
+    };
+
     match &cli.command {
         Commands::Create(params) => {
-            let rpc_url = params
-                .rpc
-                .clone()
-                .unwrap_or_else(|| constants::ADDRESS.to_string());
-
-            let rpc_url = validate_rpc_url(&rpc_url)?;
+            // Fill the RPC address and environment from the settings when no flag is given
+            let params = &CreateTokenParams {
+                rpc: params.rpc.clone().or(settings.rpc),
+                environment: params.environment.clone().or(settings.environment),
+                ..params.clone()
+            };
 
-            let client: TokenGenClient = initiate_client(&rpc_url)
-                .await
-                .map_err(|_| TokenGenErrors::FailedToConnectRpc)?;
+            // Offline generation renders the templates locally and never connects to the RPC service
+            let client = if params.offline {
+                None
+            } else {
+                Some(connect(params.rpc.clone(), &tls, api_key, options).await?)
+            };
 
-            create::create_token(client, params).await?;
+            // Create every token in the manifest, or a single token from the flags and prompts
+            if params.manifest.is_some() {
+                create::create_tokens_from_manifest(client, params).await?;
+            } else {
+                create::create_token(client, params).await?;
+            }
         }
         Commands::Verify {
             rpc,
             path,
             url,
+            reference,
+            subdir,
             address,
             environment,
+            fullnode,
+            offline,
+            recursive,
+            all_deployments,
         } => {
-            // Use the provided RPC URL or fall back to the default
-            let rpc_url = rpc
-                .clone()
-                .unwrap_or_else(|| constants::ADDRESS.to_string());
-
-            // Validate the RPC URL
-            let rpc_url = validate_rpc_url(&rpc_url)?;
+            // Fill the RPC address, environment and fullnode from the settings when no flag is given
+            let rpc = rpc.clone().or(settings.rpc);
+            let environment = environment.clone().or(settings.environment);
+            let fullnode = fullnode.clone().or(settings.fullnode);
 
-            let client: TokenGenClient = initiate_client(&rpc_url)
-                .await
-                .map_err(|_| TokenGenErrors::FailedToConnectRpc)?;
+            // Reject unknown environments before connecting to the RPC service
+            if let Some(environment) = &environment {
+                validate_environment(environment).map_err(TokenGenErrors::InvalidInput)?;
+            }
 
             // Ensure at least one verification parameter is provided
             if path.is_none() && url.is_none() && address.is_none() {
@@ -175,23 +288,117 @@ async fn run_cli(cli: Cli) -> Result<()> {
                 ));
             }
 
-            // Verify by local file path
+            // Offline verification only supports local and cloned packages and never connects to the
+            // RPC service, which address verification does not use either
+            let client = if *offline || (path.is_none() && url.is_none()) {
+                None
+            } else {
+                Some(connect(rpc, &tls, api_key, options).await?)
+            };
+
+            // Verify by local file path, or every package below it with `--recursive`
             if let Some(path) = path {
-                verify::verify_token_from_path(path, client.clone()).await?;
+                if *all_deployments {
+                    let fullnode = |env: &str| {
+                        Ok(FullnodeClient::for_environment(env, fullnode.as_deref())?
+                            .with_timeout(options.timeout))
+                    };
+                    verify::verify_token_deployments(
+                        path,
+                        client.clone(),
+                        fullnode,
+                        &SuiCliCompiler::default(),
+                    )
+                    .await?;
+                } else if *recursive {
+                    verify::verify_packages_recursively(path, client.clone()).await?;
+                } else {
+                    verify::verify_token_from_path(path, client.clone()).await?;
+                }
             }
 
-            // Verify by repository URL
+            // Verify by repository URL, cloned locally at the requested reference
             if let Some(url) = url {
-                verify::verify_token_using_url(url, client.clone()).await?;
+                let source = GitSource {
+                    url: url.clone(),
+                    reference: reference.clone(),
+                    subdir: subdir.clone(),
+                };
+                verify::verify_token_using_url(&source, client.clone()).await?;
             }
 
-            // Verify by token address and environment
-            // Verify by token address with a default environment of DEFAULT_ENVIRONMENT
+            // Verify by token address against the fullnode of the environment,
+            // with a default environment of DEFAULT_ENVIRONMENT
             if let Some(address) = address {
-                let env = environment.clone().unwrap_or_else(|| DEFAULT_ENVIRONMENT.to_string());
-                verify::verify_token_address(address, &env, client).await?;
+                let env = environment.unwrap_or_else(|| DEFAULT_ENVIRONMENT.to_string());
+                let fullnode = FullnodeClient::for_environment(&env, fullnode.as_deref())?
+                    .with_timeout(options.timeout);
+                verify::verify_token_address(address, &env, &fullnode, &SuiCliCompiler::default())
+                    .await?;
             }
         }
+        Commands::Publish {
+            path,
+            environment,
+            fullnode,
+            keystore,
+            sender,
+            gas_budget,
+        } => {
+            // Fill the environment, fullnode and keystore from the settings when no flag is given,
+            // falling back to the environment the package was generated for
+            let environment = match environment.clone().or(settings.environment) {
+                Some(environment) => environment,
+                None => verify_helper::verify_path(path)
+                    .ok()
+                    .and_then(|package| verify_helper::parse_toml_environment(&package.toml))
+                    .unwrap_or_else(|| DEFAULT_ENVIRONMENT.to_string()),
+            };
+            validate_environment(&environment).map_err(TokenGenErrors::InvalidInput)?;
+
+            let keystore = keystore
+                .clone()
+                .or(settings.keystore)
+                .or_else(|| default_keystore_path().map(|path| path.display().to_string()))
+                .ok_or_else(|| {
+                    TokenGenErrors::InvalidKeystore(
+                        "No keystore given and the home folder is unknown".to_string(),
+                    )
+                })?;
+            let signer = load_keypair(&keystore, sender.as_deref())?;
+
+            let fullnode = fullnode.clone().or(settings.fullnode);
+            let fullnode = FullnodeClient::for_environment(&environment, fullnode.as_deref())?
+                .with_timeout(options.timeout);
+            publish::publish_token(
+                path,
+                &environment,
+                &fullnode,
+                &SuiCliCompiler::default(),
+                &signer,
+                *gas_budget,
+            )
+            .await?;
+        }
+        Commands::Doctor { rpc } => {
+            let rpc_url = rpc
+                .clone()
+                .or(settings.rpc)
+                .unwrap_or_else(|| constants::ADDRESS.to_string());
+            let client = connect(Some(rpc_url.clone()), &tls, api_key, options).await?;
+            doctor::doctor(&rpc_url, client).await?;
+        }
     }
     Ok(())
 }
+
+/// Connects to the RPC service at the given address, or the default address if none is given.
+async fn connect(
+    rpc: Option<String>,
+    tls: &TlsOptions,
+    api_key: Option<String>,
+    options: CallOptions,
+) -> Result<RpcClient> {
+    let rpc_url = rpc.unwrap_or_else(|| constants::ADDRESS.to_string());
+    RpcClient::connect(&rpc_url, tls, api_key, options).await
+}
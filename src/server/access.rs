@@ -0,0 +1,215 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tarpc::context;
+
+use super::service::TokenGenServer;
+use crate::utils::client::{
+    responses::{CreateTokenResponse, GeneratedFile, RpcResponseErrors, ServerCapabilities},
+    rpc_client::TokenGen,
+};
+
+// Length of the window over which `--rate-limit` counts the requests of a key.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// API keys accepted by the server and the request quota of each key.
+///
+/// The default policy is open: every connection may call every method without authenticating.
+#[derive(Clone, Debug, Default)]
+pub struct AccessPolicy {
+    keys: Arc<HashSet<String>>,
+    rate_limit: Option<u32>,
+    usage: Arc<Mutex<HashMap<String, (Instant, u32)>>>,
+}
+
+impl AccessPolicy {
+    /**
+     * Creates a policy that only serves connections authenticated with one of the given keys.
+     *
+     * # Parameters
+     * - `keys`: The accepted API keys. An empty list leaves the server open.
+     * - `rate_limit`: Maximum number of requests per key and minute, if limited.
+     */
+    pub fn new(keys: impl IntoIterator<Item = String>, rate_limit: Option<u32>) -> Self {
+        Self {
+            keys: Arc::new(keys.into_iter().collect()),
+            rate_limit,
+            usage: Arc::default(),
+        }
+    }
+
+    /**
+     * Reads API keys from a file with one key per line.
+     *
+     * Blank lines and lines starting with `#` are ignored.
+     *
+     * # Returns
+     * - `Ok(Vec<String>)`: The keys in file order.
+     * - `Err(io::Error)`: If the file cannot be read or lists no keys.
+     */
+    pub fn load_keys(path: &Path) -> io::Result<Vec<String>> {
+        let keys: Vec<String> = fs::read_to_string(path)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+
+        if keys.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("No API keys found in {}", path.display()),
+            ));
+        }
+        Ok(keys)
+    }
+
+    /// Starts the session of a new connection, which is unauthenticated until it sends a key.
+    pub fn session(&self) -> Session {
+        Session {
+            policy: self.clone(),
+            api_key: Arc::default(),
+        }
+    }
+
+    // Checks that a request with the given session key is allowed, counting it against the quota.
+    fn authorize(&self, api_key: Option<&str>) -> Result<(), RpcResponseErrors> {
+        if self.keys.is_empty() {
+            return Ok(());
+        }
+        let api_key = api_key.ok_or(RpcResponseErrors::Unauthorized)?;
+
+        let Some(limit) = self.rate_limit else {
+            return Ok(());
+        };
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let (start, count) = usage.entry(api_key.to_string()).or_insert((now, 0));
+
+        // Start a new window once the previous one has elapsed.
+        if now.duration_since(*start) >= RATE_LIMIT_WINDOW {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= limit {
+            let reset = RATE_LIMIT_WINDOW.saturating_sub(now.duration_since(*start));
+            return Err(RpcResponseErrors::RateLimited(reset.as_secs().max(1)));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// The `TokenGen` service for a single connection, enforcing an `AccessPolicy`.
+///
+/// Requests are served by `TokenGenServer` once the connection has authenticated. The key arrives
+/// once per connection through `authenticate` and is kept here, since tarpc's request context has
+/// no field to carry it with every request. Serve over TLS when keys are required, so the key is
+/// never sent in plaintext.
+#[derive(Clone, Debug)]
+pub struct Session {
+    policy: AccessPolicy,
+    api_key: Arc<Mutex<Option<String>>>,
+}
+
+impl Session {
+    // Checks that the connection may make another request.
+    fn authorize(&self) -> Result<(), RpcResponseErrors> {
+        let api_key = self.api_key.lock().unwrap_or_else(|e| e.into_inner());
+        self.policy.authorize(api_key.as_deref())
+    }
+}
+
+impl TokenGen for Session {
+    async fn capabilities(self, context: context::Context) -> ServerCapabilities {
+        // Clients call this before authenticating, so it is always allowed.
+        ServerCapabilities {
+            requires_api_key: !self.policy.keys.is_empty(),
+            ..TokenGenServer.capabilities(context).await
+        }
+    }
+
+    async fn authenticate(
+        self,
+        _: context::Context,
+        api_key: String,
+    ) -> Result<(), RpcResponseErrors> {
+        if !self.policy.keys.is_empty() && !self.policy.keys.contains(&api_key) {
+            return Err(RpcResponseErrors::Unauthorized);
+        }
+        *self.api_key.lock().unwrap_or_else(|e| e.into_inner()) = Some(api_key);
+        Ok(())
+    }
+
+    async fn create(
+        self,
+        context: context::Context,
+        decimals: u8,
+        name: String,
+        symbol: String,
+        description: String,
+        is_frozen: bool,
+        environment: String,
+        icon_url: Option<String>,
+        max_supply: Option<u64>,
+    ) -> Result<CreateTokenResponse, RpcResponseErrors> {
+        self.authorize()?;
+        TokenGenServer
+            .create(
+                context,
+                decimals,
+                name,
+                symbol,
+                description,
+                is_frozen,
+                environment,
+                icon_url,
+                max_supply,
+            )
+            .await
+    }
+
+    async fn verify_url(
+        self,
+        context: context::Context,
+        url: String,
+    ) -> Result<String, RpcResponseErrors> {
+        self.authorize()?;
+        TokenGenServer.verify_url(context, url).await
+    }
+
+    async fn verify_content(
+        self,
+        context: context::Context,
+        content: String,
+        toml: String,
+    ) -> Result<(), RpcResponseErrors> {
+        self.authorize()?;
+        TokenGenServer.verify_content(context, content, toml).await
+    }
+
+    async fn verify_package(
+        self,
+        context: context::Context,
+        files: Vec<GeneratedFile>,
+    ) -> Result<(), RpcResponseErrors> {
+        self.authorize()?;
+        TokenGenServer.verify_package(context, files).await
+    }
+
+    async fn verify_address(
+        self,
+        context: context::Context,
+        address: String,
+        environment: String,
+    ) -> Result<(), RpcResponseErrors> {
+        self.authorize()?;
+        TokenGenServer
+            .verify_address(context, address, environment)
+            .await
+    }
+}
@@ -0,0 +1,114 @@
+use futures::{future, Future, StreamExt};
+use std::{io, net::SocketAddr, sync::Arc};
+use tarpc::{
+    serde_transport::tcp,
+    server::{BaseChannel, Channel},
+    tokio_serde::formats::Json,
+};
+use tokio::{net::TcpListener, sync::Semaphore, task::JoinHandle};
+use tokio_rustls::TlsAcceptor;
+
+use super::access::AccessPolicy;
+use crate::{
+    constants::MAX_RPC_FRAME_BYTES,
+    utils::client::rpc_client::{json_transport, TokenGen},
+};
+
+// Maximum number of client connections served concurrently.
+const MAX_CONNECTIONS: usize = 1024;
+
+/**
+ * Binds the `TokenGen` service to the given address and starts serving connections in the background.
+ *
+ * Connections use the same JSON-over-TCP transport as `initiate_client`, so any client of the
+ * public RPC service can connect to the returned address.
+ *
+ * # Parameters
+ * - `address`: The `host:port` address to listen on. Port `0` binds a random free port.
+ * - `policy`: The API keys and quota enforced on every connection.
+ *
+ * # Returns
+ * - `Ok((SocketAddr, JoinHandle))`: The bound address and the handle of the task serving connections.
+ * - `Err(io::Error)`: If the address cannot be bound.
+ */
+pub async fn spawn_server(
+    address: &str,
+    policy: AccessPolicy,
+) -> io::Result<(SocketAddr, JoinHandle<()>)> {
+    let mut listener = tcp::listen(address, Json::default).await?;
+
+    // Bound the frames every unauthenticated peer can make the server buffer.
+    listener.config_mut().max_frame_length(MAX_RPC_FRAME_BYTES);
+    let local_addr = listener.local_addr();
+
+    let handle = tokio::spawn(
+        listener
+            // Ignore connections that fail during the handshake.
+            .filter_map(|connection| future::ready(connection.ok()))
+            .map(BaseChannel::with_defaults)
+            .map(move |channel| channel.execute(policy.session().serve()).for_each(spawn))
+            .buffer_unordered(MAX_CONNECTIONS)
+            .for_each(|_| async {}),
+    );
+
+    Ok((local_addr, handle))
+}
+
+/**
+ * Binds the `TokenGen` service to the given address and serves it over TLS in the background.
+ *
+ * Connections use the same JSON framing as `spawn_server`, inside a TLS session. Connections
+ * that fail the TLS handshake, including clients without an accepted certificate when client
+ * authentication is required, are dropped.
+ *
+ * # Parameters
+ * - `address`: The `host:port` address to listen on. Port `0` binds a random free port.
+ * - `acceptor`: The TLS configuration, see `utils::tls::server_acceptor`.
+ * - `policy`: The API keys and quota enforced on every connection.
+ *
+ * # Returns
+ * - `Ok((SocketAddr, JoinHandle))`: The bound address and the handle of the task serving connections.
+ * - `Err(io::Error)`: If the address cannot be bound.
+ */
+pub async fn spawn_tls_server(
+    address: &str,
+    acceptor: TlsAcceptor,
+    policy: AccessPolicy,
+) -> io::Result<(SocketAddr, JoinHandle<()>)> {
+    let listener = TcpListener::bind(address).await?;
+    let local_addr = listener.local_addr()?;
+
+    // Limits the connections served concurrently, as `spawn_server` does.
+    let connections = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+
+    let handle = tokio::spawn(async move {
+        loop {
+            let Ok(permit) = connections.clone().acquire_owned().await else {
+                return;
+            };
+            let Ok((stream, _)) = listener.accept().await else {
+                continue;
+            };
+            let acceptor = acceptor.clone();
+            let session = policy.session();
+
+            tokio::spawn(async move {
+                // Ignore connections that fail during the handshake.
+                if let Ok(stream) = acceptor.accept(stream).await {
+                    BaseChannel::with_defaults(json_transport(stream))
+                        .execute(session.serve())
+                        .for_each(spawn)
+                        .await;
+                }
+                drop(permit);
+            });
+        }
+    });
+
+    Ok((local_addr, handle))
+}
+
+/// Runs each request on its own task so slow requests do not block the connection.
+async fn spawn(fut: impl Future<Output = ()> + Send + 'static) {
+    tokio::spawn(fut);
+}
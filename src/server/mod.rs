@@ -0,0 +1,17 @@
+//! Reference `TokenGen` RPC server
+//!
+//! This module contains a self-hostable implementation of the `TokenGen` service:
+//! - The service implementation (`service`)
+//! - API key authentication and rate limiting of connections (`access`)
+//! - The TCP listeners serving it over the tarpc JSON transport, in plaintext or TLS (`listener`)
+//!
+//! The server speaks the same protocol as the public RPC service, so it can be used as the
+//! target of `--rpc` without any client changes.
+
+mod access;
+mod listener;
+mod service;
+
+pub use access::{AccessPolicy, Session};
+pub use listener::{spawn_server, spawn_tls_server};
+pub use service::TokenGenServer;
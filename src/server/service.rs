@@ -0,0 +1,185 @@
+use tarpc::context;
+
+use crate::{
+    constants::{ALLOWED_GIT_HOSTS, PROTOCOL_VERSION, SUPPORTED_ENVIRONMENTS, VERSION},
+    errors::TokenGenErrors,
+    utils::{
+        client::{
+            responses::{
+                CreateTokenResponse, GeneratedFile, RpcResponseErrors, ServerCapabilities,
+            },
+            rpc_client::TokenGen,
+        },
+        git::{GitCheckout, GitSource},
+        helpers::{is_local_repository_url, is_valid_repository_url, repository_host},
+        prompts::TokenInfo,
+        templates::generate_contract,
+        validation::{
+            validate_decimals, validate_description, validate_environment, validate_icon_url,
+            validate_max_supply, validate_name, validate_symbol,
+        },
+        verify_helper::{verify_content_locally, verify_package_locally, verify_path},
+    },
+};
+
+// Message of the `verify_content` error for a modified contract, as returned by the public service.
+const CONTENT_MISMATCH: &str = "Content mismatch detected";
+
+/// Reference implementation of the `TokenGen` service, backed by the embedded templates.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokenGenServer;
+
+impl TokenGen for TokenGenServer {
+    async fn capabilities(self, _: context::Context) -> ServerCapabilities {
+        ServerCapabilities {
+            version: VERSION.to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            environments: SUPPORTED_ENVIRONMENTS.map(str::to_string).to_vec(),
+            requires_api_key: false,
+        }
+    }
+
+    async fn authenticate(
+        self,
+        _: context::Context,
+        _api_key: String,
+    ) -> Result<(), RpcResponseErrors> {
+        // Access control is enforced by the `Session` wrapping this service, if any.
+        Ok(())
+    }
+
+    async fn create(
+        self,
+        _: context::Context,
+        decimals: u8,
+        name: String,
+        symbol: String,
+        description: String,
+        is_frozen: bool,
+        environment: String,
+        icon_url: Option<String>,
+        max_supply: Option<u64>,
+    ) -> Result<CreateTokenResponse, RpcResponseErrors> {
+        // Apply the same rules as the CLI, so nothing is generated that the CLI would reject.
+        validate_decimals(decimals).map_err(|_| RpcResponseErrors::InvalidDecimals)?;
+        validate_name(&name).map_err(|_| RpcResponseErrors::InvalidName)?;
+        validate_symbol(&symbol).map_err(|_| RpcResponseErrors::InvalidSymbol)?;
+        validate_description(&description).map_err(|_| RpcResponseErrors::InvalidDescription)?;
+        if let Some(icon_url) = &icon_url {
+            validate_icon_url(icon_url).map_err(RpcResponseErrors::InvalidUrl)?;
+        }
+        if let Some(max_supply) = max_supply {
+            validate_max_supply(max_supply, decimals)
+                .map_err(RpcResponseErrors::InvalidMaxSupply)?;
+        }
+        validate_environment(&environment).map_err(RpcResponseErrors::InvalidEnvironment)?;
+
+        let token = TokenInfo {
+            decimals,
+            symbol,
+            name,
+            description,
+            is_frozen,
+            environment,
+            icon_url,
+            max_supply,
+        };
+        let generated = generate_contract(&token)
+            .map_err(|e| RpcResponseErrors::GeneralError(e.to_string()))?;
+
+        Ok(CreateTokenResponse {
+            files: generated.into_files(&token.name),
+        })
+    }
+
+    async fn verify_url(
+        self,
+        _: context::Context,
+        url: String,
+    ) -> Result<String, RpcResponseErrors> {
+        is_valid_repository_url(&url).map_err(|e| RpcResponseErrors::InvalidUrl(e.to_string()))?;
+
+        // Local repositories would expose the server's own files, so only remote ones are cloned.
+        if is_local_repository_url(&url) {
+            return Err(RpcResponseErrors::InvalidUrl(
+                "Local repositories are not supported by this server".to_string(),
+            ));
+        }
+
+        // Only clone from public forges, so a request cannot reach hosts on the server's network.
+        let host = repository_host(&url).unwrap_or_default();
+        if !ALLOWED_GIT_HOSTS.contains(&host.as_str()) {
+            return Err(RpcResponseErrors::InvalidUrl(format!(
+                "Repositories on '{}' are not supported by this server, expected one of: {}",
+                host,
+                ALLOWED_GIT_HOSTS.join(", ")
+            )));
+        }
+
+        // Cloning is blocking, so keep it off the async runtime.
+        tokio::task::spawn_blocking(move || verify_repository(&url))
+            .await
+            .map_err(|e| RpcResponseErrors::GeneralError(e.to_string()))?
+    }
+
+    async fn verify_content(
+        self,
+        _: context::Context,
+        content: String,
+        toml: String,
+    ) -> Result<(), RpcResponseErrors> {
+        // Report a mismatch as the public service does, which existing clients expect.
+        verify_content_locally(&content, &toml).map_err(|e| match e {
+            RpcResponseErrors::ProgramModified => {
+                RpcResponseErrors::VerifyResultError(CONTENT_MISMATCH.to_string())
+            }
+            e => e,
+        })
+    }
+
+    async fn verify_package(
+        self,
+        _: context::Context,
+        files: Vec<GeneratedFile>,
+    ) -> Result<(), RpcResponseErrors> {
+        verify_package_locally(&files)
+    }
+
+    async fn verify_address(
+        self,
+        _: context::Context,
+        _address: String,
+        _environment: String,
+    ) -> Result<(), RpcResponseErrors> {
+        Err(RpcResponseErrors::GeneralError(
+            "Address verification is not supported by this server".to_string(),
+        ))
+    }
+}
+
+/**
+ * Clones a repository into a temporary directory and verifies the package at its root.
+ *
+ * # Parameters
+ * - `url`: The URL of the Git repository to verify.
+ *
+ * # Returns
+ * - `Ok(String)`: The name of the verified `.move` file.
+ * - `Err(RpcResponseErrors)`: If cloning fails, the repository is not a Move package, or any
+ *   file of the package is missing, extra or modified.
+ */
+fn verify_repository(url: &str) -> Result<String, RpcResponseErrors> {
+    let checkout = GitCheckout::new(&GitSource::new(url)).map_err(|e| match e {
+        TokenGenErrors::GitError(e) => RpcResponseErrors::GitError(e.message().to_string()),
+        e => RpcResponseErrors::InvalidPath(e.to_string()),
+    })?;
+    let clone_path = checkout.package_path.to_str().ok_or_else(|| {
+        RpcResponseErrors::InvalidPath(checkout.package_path.display().to_string())
+    })?;
+
+    let verify_data =
+        verify_path(clone_path).map_err(|e| RpcResponseErrors::InvalidPath(e.to_string()))?;
+    verify_package_locally(&verify_data.files)?;
+
+    Ok(verify_data.file_name)
+}
@@ -0,0 +1,172 @@
+use std::fs;
+use tarpc::context;
+
+use super::common::{
+    setup_mock_client, setup_package_repository, setup_test_client, MockServer, Script,
+};
+use crate::{
+    commands::{
+        create::create_token,
+        verify::{verify_token_from_path, verify_token_using_url},
+    },
+    constants::DEFAULT_ENVIRONMENT,
+    errors::TokenGenErrors,
+    server::{spawn_server, AccessPolicy},
+    utils::{
+        client::{responses::RpcResponseErrors, rpc_client::TokenGenClient},
+        git::GitSource,
+    },
+    CreateTokenParams, Result,
+};
+
+// Helper function to start the reference server with the given policy and connect a client to it
+async fn start_server(policy: AccessPolicy) -> Result<TokenGenClient> {
+    let (address, _server) = spawn_server("127.0.0.1:0", policy).await?;
+    setup_test_client(&address.to_string()).await
+}
+
+// Helper function to make a request that is cheap for the reference server to answer
+async fn request(client: &TokenGenClient) -> Result<std::result::Result<(), RpcResponseErrors>> {
+    Ok(client
+        .verify_address(context::current(), "0x1".to_string(), "devnet".to_string())
+        .await?
+        .or_else(|e| match e {
+            // The reference server does not support address verification
+            RpcResponseErrors::GeneralError(_) => Ok(()),
+            e => Err(e),
+        }))
+}
+
+// Test case to verify that a server with API keys only serves authenticated connections
+#[tokio::test]
+async fn server_requires_api_key() -> Result<()> {
+    let client = start_server(AccessPolicy::new(["secret".to_string()], None)).await?;
+
+    // Requests are rejected until the connection authenticates
+    assert!(matches!(
+        request(&client).await?,
+        Err(RpcResponseErrors::Unauthorized)
+    ));
+
+    let result = client
+        .authenticate(context::current(), "wrong".to_string())
+        .await?;
+    assert!(matches!(result, Err(RpcResponseErrors::Unauthorized)));
+    assert!(matches!(
+        request(&client).await?,
+        Err(RpcResponseErrors::Unauthorized)
+    ));
+
+    let result = client
+        .authenticate(context::current(), "secret".to_string())
+        .await?;
+    assert!(result.is_ok());
+    assert!(request(&client).await?.is_ok());
+
+    // The session belongs to the connection, so a new connection must authenticate again
+    let (address, _server) = spawn_server(
+        "127.0.0.1:0",
+        AccessPolicy::new(["secret".to_string()], None),
+    )
+    .await?;
+    let other = setup_test_client(&address.to_string()).await?;
+    assert!(matches!(
+        request(&other).await?,
+        Err(RpcResponseErrors::Unauthorized)
+    ));
+    Ok(())
+}
+
+// Test case to verify that a server without API keys accepts every connection
+#[tokio::test]
+async fn open_server_accepts_any_key() -> Result<()> {
+    let client = start_server(AccessPolicy::default()).await?;
+    assert!(request(&client).await?.is_ok());
+
+    let result = client
+        .authenticate(context::current(), "anything".to_string())
+        .await?;
+    assert!(result.is_ok());
+    Ok(())
+}
+
+// Test case to verify that requests beyond the quota of a key are rate limited
+#[tokio::test]
+async fn server_rate_limits_api_key() -> Result<()> {
+    let client = start_server(AccessPolicy::new(["secret".to_string()], Some(2))).await?;
+    client
+        .authenticate(context::current(), "secret".to_string())
+        .await?
+        .map_err(|_| TokenGenErrors::Unauthorized)?;
+
+    assert!(request(&client).await?.is_ok());
+    assert!(request(&client).await?.is_ok());
+    assert!(matches!(
+        request(&client).await?,
+        Err(RpcResponseErrors::RateLimited(seconds)) if (1..=60).contains(&seconds)
+    ));
+    Ok(())
+}
+
+// Test case to verify that API keys are read from a file, skipping comments and blank lines
+#[test]
+fn api_keys_are_loaded_from_file() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("sui-token-gen-keys-{}", std::process::id()));
+
+    fs::write(&path, "# CI\nci-key\n\n  team-key  \n")?;
+    let keys = AccessPolicy::load_keys(&path);
+    fs::write(&path, "# no keys\n")?;
+    let empty = AccessPolicy::load_keys(&path);
+    fs::remove_file(&path)?;
+
+    assert_eq!(keys?, vec!["ci-key".to_string(), "team-key".to_string()]);
+    assert!(empty.is_err());
+    Ok(())
+}
+
+// Test case to verify that access errors surface as distinct errors whatever the command
+#[tokio::test]
+async fn access_errors_are_distinct() -> Result<()> {
+    let params = CreateTokenParams {
+        rpc: None,
+        name: Some("Unauthorized Token".to_string()),
+        symbol: Some("UNA".to_string()),
+        decimals: Some(6),
+        description: Some(String::new()),
+        icon_url: None,
+        max_supply: None,
+        is_frozen: Some(false),
+        environment: Some(DEFAULT_ENVIRONMENT.to_string()),
+        offline: false,
+        non_interactive: true,
+        force: false,
+        manifest: None,
+        continue_on_error: false,
+    };
+    let client =
+        setup_mock_client(MockServer::new().create(Script::Fail(RpcResponseErrors::Unauthorized)));
+    let result = create_token(Some(client.into()), &params).await;
+    assert!(matches!(
+        result,
+        Err(ref error @ TokenGenErrors::Unauthorized)
+            if error.code() == "UNAUTHORIZED" && error.exit_code() == 3
+    ));
+
+    let url = setup_package_repository("auth_rate_limited_repository", ".", "Auth Token")?;
+    let client = setup_mock_client(
+        MockServer::new().verify_package(Script::Fail(RpcResponseErrors::RateLimited(30))),
+    );
+    let result = verify_token_using_url(&GitSource::new(&url), Some(client.into())).await;
+    assert!(matches!(
+        result,
+        Err(ref error @ TokenGenErrors::RateLimited(30)) if error.code() == "RATE_LIMITED"
+    ));
+
+    let client = setup_mock_client(
+        MockServer::new().verify_package(Script::Fail(RpcResponseErrors::Unauthorized)),
+    );
+    let result = verify_token_from_path("auth_rate_limited_repository", Some(client.into())).await;
+    fs::remove_dir_all("auth_rate_limited_repository")?;
+    assert!(matches!(result, Err(TokenGenErrors::Unauthorized)));
+    Ok(())
+}
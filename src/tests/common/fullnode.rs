@@ -0,0 +1,237 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{json, Value};
+use std::{collections::HashMap, sync::Arc};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{
+    utils::{
+        fullnode::{CoinMetadata, FullnodeClient},
+        onchain::{BytecodeCompiler, CompiledPackage},
+    },
+    Result,
+};
+
+/// Builds a stand-in for a Move module named `module`, whose self address is `package_id`.
+///
+/// It starts with the Move magic bytes and holds the address twice, like a real module.
+pub fn module_bytecode(module: &str, package_id: &[u8]) -> Vec<u8> {
+    [
+        &[0xa1, 0x1c, 0xeb, 0x0b][..],
+        package_id,
+        module.as_bytes(),
+        package_id,
+    ]
+    .concat()
+}
+
+/// Encodes the transaction data a fullnode builds to publish `package` from `sender`.
+///
+/// It publishes the modules, transfers the `UpgradeCap` to the sender and pays with one gas coin.
+pub fn publish_transaction(sender: &str, package: &CompiledPackage, gas_budget: u64) -> Vec<u8> {
+    let address = |address: &str| {
+        let hex = format!("{:0>64}", address.trim_start_matches("0x"));
+        (0..32)
+            .map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap_or_default())
+            .collect::<Vec<u8>>()
+    };
+    let sender = address(sender);
+
+    // TransactionData::V1, ProgrammableTransaction with the sender as its only pure input
+    let mut tx = vec![0, 0, 1, 0, 32];
+    tx.extend(&sender);
+    // Publish command with the modules and dependencies
+    tx.extend([2, 4, package.modules.len() as u8]);
+    for module in &package.modules {
+        tx.push(module.len() as u8);
+        tx.extend(module);
+    }
+    tx.push(package.dependencies.len() as u8);
+    for dependency in &package.dependencies {
+        tx.extend(address(dependency));
+    }
+    // TransferObjects of Result(0) to Input(0)
+    tx.extend([1, 1, 2, 0, 0, 1, 0, 0]);
+    tx.extend(&sender);
+    // Gas data: one payment coin, owner, price and budget, then no expiration
+    tx.push(1);
+    tx.extend([9; 32]);
+    tx.extend(1u64.to_le_bytes());
+    tx.push(32);
+    tx.extend([3; 32]);
+    tx.extend(&sender);
+    tx.extend(1000u64.to_le_bytes());
+    tx.extend(gas_budget.to_le_bytes());
+    tx.push(0);
+    tx
+}
+
+/// Builds the `CoinMetadata` of a published coin, without description or icon.
+pub fn coin_metadata(id: &str, name: &str, symbol: &str, decimals: u8) -> CoinMetadata {
+    CoinMetadata {
+        id: Some(id.to_string()),
+        decimals,
+        name: name.to_string(),
+        symbol: symbol.to_string(),
+        description: String::new(),
+        icon_url: None,
+    }
+}
+
+/// Sui fullnode JSON-RPC server answering from canned chain data, for on-chain verification tests.
+#[derive(Clone, Debug, Default)]
+pub struct FixtureFullnode {
+    // Results by method and first parameter.
+    results: HashMap<(String, String), Value>,
+}
+
+impl FixtureFullnode {
+    /// Creates a fullnode with an empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers `method` called with `param` as its first parameter with `result`.
+    pub fn respond(mut self, method: &str, param: &str, result: Value) -> Self {
+        self.results
+            .insert((method.to_string(), param.to_string()), result);
+        self
+    }
+
+    /// Adds a published package with a single coin module and the `CoinMetadata` of its coin,
+    /// with no coins minted yet.
+    pub fn with_coin(
+        self,
+        package_id: &str,
+        module: &str,
+        bytecode: &[u8],
+        metadata: &CoinMetadata,
+        is_frozen: bool,
+    ) -> Self {
+        let coin_type = format!("{}::{}::{}", package_id, module, module.to_uppercase());
+        let metadata_id = metadata.id.clone().unwrap_or_default();
+        let owner = if is_frozen {
+            json!("Immutable")
+        } else {
+            json!({ "Shared": { "initial_shared_version": 1 } })
+        };
+
+        self.respond(
+            "sui_getObject",
+            package_id,
+            json!({
+                "data": {
+                    "objectId": package_id,
+                    "bcs": {
+                        "dataType": "package",
+                        "id": package_id,
+                        "moduleMap": { module: STANDARD.encode(bytecode) },
+                    },
+                },
+            }),
+        )
+        .respond("suix_getCoinMetadata", &coin_type, json!(metadata))
+        .respond("suix_getTotalSupply", &coin_type, json!({ "value": "0" }))
+        .respond(
+            "sui_getObject",
+            &metadata_id,
+            json!({ "data": { "objectId": metadata_id, "owner": owner } }),
+        )
+    }
+
+    /// Answers a publish by `sender` with `tx_bytes`, and their execution with `response`.
+    pub fn with_publish(self, sender: &str, tx_bytes: &[u8], response: Value) -> Self {
+        let encoded = STANDARD.encode(tx_bytes);
+        self.respond("unsafe_publish", sender, json!({ "txBytes": encoded }))
+            .respond("sui_executeTransactionBlock", &encoded, response)
+    }
+
+    /// Starts serving on a random local port and returns a client connected to it.
+    pub async fn connect(self) -> Result<FullnodeClient> {
+        FullnodeClient::new(&self.start().await?)
+    }
+
+    /// Starts serving on a random local port and returns the URL of the JSON-RPC API.
+    pub async fn start(self) -> Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let fullnode = Arc::new(self);
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let fullnode = fullnode.clone();
+                tokio::spawn(async move { fullnode.serve(stream).await });
+            }
+        });
+        Ok(url)
+    }
+
+    // Answers a single request, with a chunked body, and closes the connection.
+    async fn serve(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let mut request = Vec::new();
+        let mut buffer = [0; 4096];
+        let body = loop {
+            let read = stream.read(&mut buffer).await?;
+            if read == 0 {
+                return Ok(());
+            }
+            request.extend_from_slice(&buffer[..read]);
+
+            let Some(header_end) = request.windows(4).position(|window| window == b"\r\n\r\n")
+            else {
+                continue;
+            };
+            let length = String::from_utf8_lossy(&request[..header_end])
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or_default();
+            if request.len() >= header_end + 4 + length {
+                break request[header_end + 4..header_end + 4 + length].to_vec();
+            }
+        };
+
+        let call: Value = serde_json::from_slice(&body).unwrap_or_default();
+        let method = call["method"].as_str().unwrap_or_default();
+        let param = call["params"][0].as_str().unwrap_or_default();
+        let result = self
+            .results
+            .get(&(method.to_string(), param.to_string()))
+            .cloned()
+            .unwrap_or_else(|| match method {
+                "sui_getObject" => json!({ "error": { "code": "notExists", "object_id": param } }),
+                _ => Value::Null,
+            });
+        let response = json!({ "jsonrpc": "2.0", "id": call["id"], "result": result }).to_string();
+
+        // Split the body in two chunks to exercise chunked decoding.
+        let (first, second) = response.split_at(response.len() / 2);
+        let chunked = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n{:x}\r\n{}\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+            first.len(),
+            first,
+            second.len(),
+            second
+        );
+        stream.write_all(chunked.as_bytes()).await?;
+        stream.shutdown().await
+    }
+}
+
+/// Compiler returning fixed bytecode for any package, standing in for `sui move build`.
+#[derive(Clone, Debug)]
+pub struct FixedCompiler(pub Vec<Vec<u8>>);
+
+impl BytecodeCompiler for FixedCompiler {
+    fn build(&self, _package_path: &str) -> Result<CompiledPackage> {
+        Ok(CompiledPackage {
+            modules: self.0.clone(),
+            dependencies: vec!["0x1".to_string(), "0x2".to_string()],
+        })
+    }
+}
@@ -0,0 +1,247 @@
+use futures::StreamExt;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tarpc::{
+    client, context,
+    server::{BaseChannel, Channel},
+    transport::channel,
+};
+
+use crate::{
+    server::TokenGenServer,
+    utils::client::{
+        responses::{CreateTokenResponse, GeneratedFile, RpcResponseErrors, ServerCapabilities},
+        rpc_client::{TokenGen, TokenGenClient},
+    },
+};
+
+// File name reported by a scripted successful `verify_url` call.
+pub const MOCK_FILE_NAME: &str = "mocktoken.move";
+
+/// Scripted response for a single RPC method of the mock server.
+#[derive(Clone, Debug, Default)]
+pub enum Script {
+    /// Delegate to the reference `TokenGenServer` implementation.
+    #[default]
+    Passthrough,
+    /// Respond successfully without inspecting the request.
+    Succeed,
+    /// Respond with the given error.
+    Fail(RpcResponseErrors),
+}
+
+/// In-memory `TokenGen` server with scriptable responses for deterministic tests.
+#[derive(Clone, Debug, Default)]
+pub struct MockServer {
+    authenticate: Script,
+    create: Script,
+    verify_url: Script,
+    verify_content: Script,
+    verify_package: Script,
+    verify_address: Script,
+    delay: Option<Duration>,
+    drop_connection: bool,
+    requests: Arc<AtomicUsize>,
+}
+
+impl MockServer {
+    /// Creates a mock server that behaves like the reference server for every method.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scripts the response of `authenticate`; `Passthrough` accepts any key, like the reference server.
+    pub fn authenticate(mut self, script: Script) -> Self {
+        self.authenticate = script;
+        self
+    }
+
+    /// Scripts the response of `create`; `Succeed` behaves like `Passthrough`.
+    pub fn create(mut self, script: Script) -> Self {
+        self.create = script;
+        self
+    }
+
+    /// Scripts the response of `verify_url`.
+    pub fn verify_url(mut self, script: Script) -> Self {
+        self.verify_url = script;
+        self
+    }
+
+    /// Scripts the response of `verify_content`.
+    pub fn verify_content(mut self, script: Script) -> Self {
+        self.verify_content = script;
+        self
+    }
+
+    /// Scripts the response of `verify_package`.
+    pub fn verify_package(mut self, script: Script) -> Self {
+        self.verify_package = script;
+        self
+    }
+
+    /// Scripts the response of `verify_address`.
+    pub fn verify_address(mut self, script: Script) -> Self {
+        self.verify_address = script;
+        self
+    }
+
+    /// Delays every response by the given duration.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Drops the connection as soon as the first request arrives.
+    pub fn drop_connection(mut self) -> Self {
+        self.drop_connection = true;
+        self
+    }
+
+    /// Returns the number of requests received so far, shared by every clone of the server.
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+
+    /// Starts the server on an in-memory transport and returns a client connected to it.
+    pub fn client(self) -> TokenGenClient {
+        let (client_transport, mut server_transport) = channel::unbounded();
+
+        if self.drop_connection {
+            tokio::spawn(async move {
+                let _ = server_transport.next().await;
+            });
+        } else {
+            let server = BaseChannel::with_defaults(server_transport);
+            tokio::spawn(server.execute(self.serve()).for_each(|response| async {
+                tokio::spawn(response);
+            }));
+        }
+
+        TokenGenClient::new(client::Config::default(), client_transport).spawn()
+    }
+
+    // Counts the request and waits for the configured delay, if any.
+    async fn wait(&self) {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        if let Some(delay) = self.delay {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+impl TokenGen for MockServer {
+    async fn capabilities(self, context: context::Context) -> ServerCapabilities {
+        self.wait().await;
+        TokenGenServer.capabilities(context).await
+    }
+
+    async fn authenticate(
+        self,
+        context: context::Context,
+        api_key: String,
+    ) -> Result<(), RpcResponseErrors> {
+        self.wait().await;
+        match self.authenticate {
+            Script::Passthrough => TokenGenServer.authenticate(context, api_key).await,
+            Script::Succeed => Ok(()),
+            Script::Fail(error) => Err(error),
+        }
+    }
+
+    async fn create(
+        self,
+        context: context::Context,
+        decimals: u8,
+        name: String,
+        symbol: String,
+        description: String,
+        is_frozen: bool,
+        environment: String,
+        icon_url: Option<String>,
+        max_supply: Option<u64>,
+    ) -> Result<CreateTokenResponse, RpcResponseErrors> {
+        self.wait().await;
+        match self.create {
+            Script::Passthrough | Script::Succeed => {
+                TokenGenServer
+                    .create(
+                        context,
+                        decimals,
+                        name,
+                        symbol,
+                        description,
+                        is_frozen,
+                        environment,
+                        icon_url,
+                        max_supply,
+                    )
+                    .await
+            }
+            Script::Fail(error) => Err(error),
+        }
+    }
+
+    async fn verify_url(
+        self,
+        context: context::Context,
+        url: String,
+    ) -> Result<String, RpcResponseErrors> {
+        self.wait().await;
+        match self.verify_url {
+            Script::Passthrough => TokenGenServer.verify_url(context, url).await,
+            Script::Succeed => Ok(MOCK_FILE_NAME.to_string()),
+            Script::Fail(error) => Err(error),
+        }
+    }
+
+    async fn verify_content(
+        self,
+        context: context::Context,
+        content: String,
+        toml: String,
+    ) -> Result<(), RpcResponseErrors> {
+        self.wait().await;
+        match self.verify_content {
+            Script::Passthrough => TokenGenServer.verify_content(context, content, toml).await,
+            Script::Succeed => Ok(()),
+            Script::Fail(error) => Err(error),
+        }
+    }
+
+    async fn verify_package(
+        self,
+        context: context::Context,
+        files: Vec<GeneratedFile>,
+    ) -> Result<(), RpcResponseErrors> {
+        self.wait().await;
+        match self.verify_package {
+            Script::Passthrough => TokenGenServer.verify_package(context, files).await,
+            Script::Succeed => Ok(()),
+            Script::Fail(error) => Err(error),
+        }
+    }
+
+    async fn verify_address(
+        self,
+        context: context::Context,
+        address: String,
+        environment: String,
+    ) -> Result<(), RpcResponseErrors> {
+        self.wait().await;
+        match self.verify_address {
+            Script::Passthrough => {
+                TokenGenServer
+                    .verify_address(context, address, environment)
+                    .await
+            }
+            Script::Succeed => Ok(()),
+            Script::Fail(error) => Err(error),
+        }
+    }
+}
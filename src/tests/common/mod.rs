@@ -1,3 +1,9 @@
+pub mod fullnode;
+pub mod mock_server;
 pub mod test_utils;
 
-pub use test_utils::setup_test_client;
+pub use fullnode::{
+    coin_metadata, module_bytecode, publish_transaction, FixedCompiler, FixtureFullnode,
+};
+pub use mock_server::{MockServer, Script};
+pub use test_utils::{commit_all, setup_mock_client, setup_package_repository, setup_test_client};
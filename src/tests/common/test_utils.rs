@@ -1,6 +1,15 @@
+use git2::{Commit, IndexAddOption, Oid, Repository, Signature};
+use std::{fs, path::Path};
+
+use super::mock_server::MockServer;
 use crate::{
     errors::TokenGenErrors,
-    utils::client::rpc_client::{initiate_client, TokenGenClient},
+    utils::{
+        client::rpc_client::{initiate_client, TokenGenClient},
+        generation::ContractGenerator,
+        prompts::TokenInfo,
+        templates::generate_contract,
+    },
     Result,
 };
 
@@ -10,5 +19,52 @@ use crate::{
 pub async fn setup_test_client(address: &str) -> Result<TokenGenClient> {
     initiate_client(address)
         .await
-        .map_err(|_| TokenGenErrors::FailedToConnectRpc)
+        .map_err(TokenGenErrors::FailedToConnectRpc)
+}
+
+// Helper function to set up a test client connected to an in-process mock server
+// The server runs on an in-memory transport, so tests do not depend on the network or the production RPC service.
+pub fn setup_mock_client(server: MockServer) -> TokenGenClient {
+    server.client()
+}
+
+// Helper function to create a Git repository holding a generated package and return its `file://` URL
+// The package is written to `subdir` inside the repository and committed on the default branch.
+pub fn setup_package_repository(dir: &str, subdir: &str, name: &str) -> Result<String> {
+    if Path::new(dir).exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    let repo = Repository::init(dir)?;
+
+    let token = TokenInfo {
+        name: name.to_string(),
+        symbol: "GIT".to_string(),
+        decimals: 6,
+        ..TokenInfo::default()
+    };
+    let files = generate_contract(&token)?.into_files(&token.name);
+    ContractGenerator::new(format!("{}/{}", dir, subdir)).write_files(&files)?;
+    commit_all(&repo, "Add generated package")?;
+
+    Ok(format!("file://{}", fs::canonicalize(dir)?.display()))
+}
+
+// Helper function to commit every file of a repository on its current branch
+pub fn commit_all(repo: &Repository, message: &str) -> Result<Oid> {
+    let mut index = repo.index()?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    let signature = Signature::now("Sui Token Gen", "tests@sui-token-gen.local")?;
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<&Commit> = parent.iter().collect();
+    Ok(repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )?)
 }
@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{errors::TokenGenErrors, handlers::OutputFormat, utils::config::Settings, Result};
+
+// Helper function to write config files, resolve settings from them and remove them again
+fn resolve_with(
+    files: &[(&str, &str)],
+    project: Option<(&str, &str)>,
+    profile: Option<&str>,
+    vars: &[(&str, &str)],
+) -> Result<Result<Settings>> {
+    let paths: Vec<PathBuf> = files.iter().map(|(name, _)| PathBuf::from(name)).collect();
+    for (name, content) in files.iter().chain(&project) {
+        fs::write(name, content)?;
+    }
+
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    let settings = Settings::resolve(
+        &paths,
+        project.map(|(name, _)| Path::new(name)),
+        profile,
+        |name| vars.get(name).cloned(),
+    );
+
+    for (name, _) in files.iter().chain(&project) {
+        fs::remove_file(name)?;
+    }
+    Ok(settings)
+}
+
+const USER_CONFIG: &str = r#"
+rpc = "10.0.0.1:5001"
+environment = "testnet"
+
+[profiles.staging]
+rpc = "10.0.0.2:5001"
+output = "json"
+"#;
+
+const PROJECT_CONFIG: &str = r#"
+environment = "mainnet"
+
+[profiles.staging]
+environment = "devnet"
+"#;
+
+// Test case to verify that missing config files leave every setting unset
+#[test]
+fn settings_default_without_config() -> Result<()> {
+    let settings = Settings::resolve(
+        &[PathBuf::from("config_missing.toml")],
+        Some(Path::new("config_missing_project.toml")),
+        None,
+        |_| None,
+    )?;
+    assert_eq!(settings, Settings::default());
+    Ok(())
+}
+
+// Test case to verify the precedence of config files, profiles and environment variables
+#[test]
+fn settings_are_layered() -> Result<()> {
+    // The project file overrides the top-level values of the user file
+    let settings = resolve_with(
+        &[("config_layered_user.toml", USER_CONFIG)],
+        Some(("config_layered_project.toml", PROJECT_CONFIG)),
+        None,
+        &[],
+    )??;
+    assert_eq!(settings.rpc.as_deref(), Some("10.0.0.1:5001"));
+    assert_eq!(settings.environment.as_deref(), Some("mainnet"));
+    assert_eq!(settings.output, None);
+
+    // Profile values override the top-level values of every file
+    let settings = resolve_with(
+        &[("config_profile_user.toml", USER_CONFIG)],
+        Some(("config_profile_project.toml", PROJECT_CONFIG)),
+        Some("staging"),
+        &[],
+    )??;
+    assert_eq!(settings.rpc.as_deref(), Some("10.0.0.2:5001"));
+    assert_eq!(settings.environment.as_deref(), Some("devnet"));
+    assert_eq!(settings.output, Some(OutputFormat::Json));
+
+    // Environment variables override every file, and can select the profile
+    let settings = resolve_with(
+        &[("config_env_user.toml", USER_CONFIG)],
+        None,
+        None,
+        &[
+            ("SUI_TOKEN_GEN_PROFILE", "staging"),
+            ("SUI_TOKEN_GEN_RPC", "127.0.0.1:5001"),
+            ("SUI_TOKEN_GEN_OUTPUT", "TEXT"),
+            ("SUI_TOKEN_GEN_API_KEY", "secret"),
+        ],
+    )??;
+    assert_eq!(settings.rpc.as_deref(), Some("127.0.0.1:5001"));
+    assert_eq!(settings.api_key.as_deref(), Some("secret"));
+    assert_eq!(settings.environment.as_deref(), Some("testnet"));
+    assert_eq!(settings.output, Some(OutputFormat::Text));
+    Ok(())
+}
+
+// Test case to verify that invalid configuration is reported
+#[test]
+fn invalid_config_is_rejected() -> Result<()> {
+    // Unknown profile
+    let result = resolve_with(
+        &[("config_unknown_profile.toml", USER_CONFIG)],
+        None,
+        Some("production"),
+        &[],
+    )?;
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::InvalidConfig(ref msg))
+            if msg == "Profile 'production' is not defined in any config file"
+    ));
+
+    // Unknown key, reported with the file name
+    let result = resolve_with(
+        &[("config_unknown_key.toml", "rpc_url = \"10.0.0.1:5001\"\n")],
+        None,
+        None,
+        &[],
+    )?;
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::InvalidConfig(ref msg)) if msg.starts_with("config_unknown_key.toml: ")
+    ));
+
+    // Invalid output format in the environment
+    let result = resolve_with(&[], None, None, &[("SUI_TOKEN_GEN_OUTPUT", "yaml")])?;
+    assert!(matches!(
+        result,
+        Err(ref error @ TokenGenErrors::InvalidConfig(_)) if error.exit_code() == 2
+    ));
+    Ok(())
+}
+
+// Test case to verify that timeouts and retries from config files and variables are range-checked
+#[test]
+fn out_of_range_limits_are_rejected() -> Result<()> {
+    let result = resolve_with(
+        &[("config_zero_timeout.toml", "timeout = 0\n")],
+        None,
+        None,
+        &[],
+    )?;
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::InvalidConfig(ref msg))
+            if msg == "config_zero_timeout.toml: Invalid value '0' for timeout, expected between 1 and 86400 seconds"
+    ));
+
+    let result = resolve_with(
+        &[(
+            "config_profile_retries.toml",
+            "[profiles.ci]\nretries = 1000\n",
+        )],
+        None,
+        None,
+        &[],
+    )?;
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::InvalidConfig(ref msg))
+            if msg == "config_profile_retries.toml: Invalid value '1000' for retries in [profiles.ci], expected at most 10"
+    ));
+
+    let result = resolve_with(&[], None, None, &[("SUI_TOKEN_GEN_TIMEOUT", "0")])?;
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::InvalidConfig(ref msg))
+            if msg == "Invalid value '0' for SUI_TOKEN_GEN_TIMEOUT, expected between 1 and 86400 seconds"
+    ));
+
+    let result = resolve_with(&[], None, None, &[("SUI_TOKEN_GEN_TIMEOUT", "86400")])?;
+    assert_eq!(result?.timeout, Some(86_400));
+    Ok(())
+}
+
+// Test case to verify that the project config cannot set servers, credentials or signing keys
+#[test]
+fn project_config_is_restricted() -> Result<()> {
+    for (name, content) in [
+        ("config_project_rpc.toml", "rpc = \"10.0.0.9:5001\"\n"),
+        (
+            "config_project_fullnode.toml",
+            "fullnode = \"http://10.0.0.9:9000\"\n",
+        ),
+        (
+            "config_project_profile.toml",
+            "environment = \"devnet\"\n\n[profiles.staging]\napi_key = \"secret\"\n",
+        ),
+    ] {
+        let result = resolve_with(&[], Some((name, content)), None, &[])?;
+        assert!(
+            matches!(
+                result,
+                Err(TokenGenErrors::InvalidConfig(ref msg))
+                    if msg.starts_with(name) && msg.contains("cannot be set in the project config")
+            ),
+            "Expected {} to be rejected, but got {:?}",
+            name,
+            result
+        );
+    }
+
+    // The same keys are accepted from the user config
+    let settings = resolve_with(
+        &[("config_user_keystore.toml", "keystore = \"sui.keystore\"\n")],
+        Some(("config_project_output.toml", "output = \"json\"\n")),
+        None,
+        &[],
+    )??;
+    assert_eq!(settings.keystore.as_deref(), Some("sui.keystore"));
+    assert_eq!(settings.output, Some(OutputFormat::Json));
+    Ok(())
+}
@@ -0,0 +1,261 @@
+use std::{error::Error, io, time::Duration};
+use tarpc::client::RpcError;
+use tokio::{io::AsyncReadExt, net::TcpListener};
+
+use super::common::{setup_mock_client, MockServer, Script};
+use crate::{
+    constants::{PROTOCOL_VERSION, SUPPORTED_ENVIRONMENTS, VERSION},
+    errors::TokenGenErrors,
+    server::{spawn_server, AccessPolicy},
+    utils::{
+        client::{
+            connection::{check_compatibility, CallOptions, RpcClient},
+            responses::{RpcResponseErrors, ServerCapabilities},
+        },
+        prompts::TokenInfo,
+        tls::TlsOptions,
+    },
+    Result,
+};
+
+// Call options with a short deadline and backoff, so retries finish quickly
+fn fast_options(retries: u32) -> CallOptions {
+    CallOptions {
+        timeout: Duration::from_millis(50),
+        retries,
+        backoff: Duration::from_millis(1),
+    }
+}
+
+// Test case to verify that a failed connection keeps the underlying I/O error
+#[tokio::test]
+async fn connection_failure_keeps_io_error() {
+    let result = RpcClient::connect(
+        "127.0.0.1:5002",
+        &TlsOptions::default(),
+        None,
+        CallOptions::default(),
+    )
+    .await;
+
+    match result {
+        Err(error @ TokenGenErrors::FailedToConnectRpc(_)) => {
+            let source = error.source().and_then(|e| e.downcast_ref::<io::Error>());
+            assert!(matches!(
+                source.map(io::Error::kind),
+                Some(io::ErrorKind::ConnectionRefused)
+            ));
+            assert!(error
+                .to_string()
+                .starts_with("Unable to connect to the RPC service: "));
+        }
+        Err(error) => panic!("Unexpected error: {:?}", error),
+        Ok(_) => panic!("Connected to a closed port"),
+    }
+}
+
+// Test case to verify that verification calls are retried after a timeout, and `create` is not
+#[tokio::test]
+async fn verification_is_retried_after_timeout() -> Result<()> {
+    let server = MockServer::new()
+        .verify_url(Script::Succeed)
+        .delay(Duration::from_millis(300));
+    let client = RpcClient::new(setup_mock_client(server.clone()), fast_options(2));
+
+    let result = client
+        .verify_url("https://github.com/osec/slow-token")
+        .await;
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::RpcError(RpcError::DeadlineExceeded))
+    ));
+    assert_eq!(server.requests(), 3);
+
+    let result = client.create(&TokenInfo::default()).await;
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::RpcError(RpcError::DeadlineExceeded))
+    ));
+    assert_eq!(server.requests(), 4);
+
+    // Errors reported by the service are final and not retried
+    let server = MockServer::new().verify_url(Script::Fail(RpcResponseErrors::ProgramModified));
+    let client = RpcClient::new(setup_mock_client(server.clone()), fast_options(2));
+    let result = client.verify_url("https://github.com/osec/token").await?;
+    assert!(matches!(result, Err(RpcResponseErrors::ProgramModified)));
+    assert_eq!(server.requests(), 1);
+    Ok(())
+}
+
+// Test case to verify that the client reconnects when the transport drops
+#[tokio::test]
+async fn reconnects_after_dropped_transport() -> Result<()> {
+    let (address, server) = spawn_server("127.0.0.1:0", AccessPolicy::default()).await?;
+    let client = RpcClient::connect(
+        &address.to_string(),
+        &TlsOptions::default(),
+        None,
+        CallOptions {
+            backoff: Duration::from_millis(10),
+            ..CallOptions::default()
+        },
+    )
+    .await?;
+
+    // Stopping the server closes the connection; a new server listens on the same address
+    server.abort();
+    let _ = server.await;
+    let (_, _server) = spawn_server(&address.to_string(), AccessPolicy::default()).await?;
+
+    // The reference server answers, even though it does not support address verification
+    let result = client.verify_address("0x1", "devnet").await?;
+    assert!(matches!(result, Err(RpcResponseErrors::GeneralError(_))));
+    Ok(())
+}
+
+// Test case to verify that connecting reports the capabilities of the server
+#[tokio::test]
+async fn handshake_reports_server_capabilities() -> Result<()> {
+    let (address, _server) = spawn_server("127.0.0.1:0", AccessPolicy::default()).await?;
+    let client = RpcClient::connect(
+        &address.to_string(),
+        &TlsOptions::default(),
+        None,
+        CallOptions::default(),
+    )
+    .await?;
+
+    let capabilities = client.capabilities();
+    assert_eq!(
+        capabilities,
+        Some(ServerCapabilities {
+            version: VERSION.to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            environments: SUPPORTED_ENVIRONMENTS
+                .iter()
+                .map(|e| e.to_string())
+                .collect(),
+            requires_api_key: false,
+        })
+    );
+
+    // Clients wrapping an existing connection ask when needed
+    let client = RpcClient::from(setup_mock_client(MockServer::new()));
+    assert_eq!(client.capabilities(), None);
+    assert_eq!(client.fetch_capabilities().await?, capabilities.unwrap());
+    Ok(())
+}
+
+// Test case to verify that servers with another protocol version are rejected
+#[test]
+fn protocol_mismatch_is_incompatible() {
+    let server = ServerCapabilities {
+        version: "9.0.0".to_string(),
+        protocol_version: PROTOCOL_VERSION + 1,
+        environments: vec![],
+        requires_api_key: false,
+    };
+
+    match check_compatibility(&server) {
+        Err(error @ TokenGenErrors::IncompatibleServer(_)) => {
+            assert_eq!(error.code(), "INCOMPATIBLE_SERVER");
+            assert_eq!(error.exit_code(), 3);
+            assert!(error.to_string().contains("Upgrade this client"));
+        }
+        result => panic!("Unexpected result: {:?}", result),
+    }
+}
+
+// Test case to verify that a server without the handshake is reported as incompatible
+// Servers that predate it drop the connection when they cannot decode the request.
+#[tokio::test]
+async fn server_without_handshake_is_incompatible() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    tokio::spawn(async move {
+        if let Ok((mut stream, _)) = listener.accept().await {
+            let _ = stream.read(&mut [0; 1024]).await;
+        }
+    });
+
+    let result = RpcClient::connect(
+        &address.to_string(),
+        &TlsOptions::default(),
+        None,
+        CallOptions::default(),
+    )
+    .await;
+    assert!(matches!(result, Err(TokenGenErrors::IncompatibleServer(_))));
+    Ok(())
+}
+
+// Test case to verify that a connection reset during the handshake is a connection failure
+// It is not mistaken for an incompatible server, so the connection can be retried.
+#[tokio::test]
+async fn reset_during_handshake_is_connection_failure() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    tokio::spawn(async move {
+        if let Ok((mut stream, _)) = listener.accept().await {
+            // Closing with a zero linger resets the connection instead of closing it cleanly
+            let _ = stream.set_linger(Some(Duration::ZERO));
+            let _ = stream.read(&mut [0; 1024]).await;
+        }
+    });
+
+    let result = RpcClient::connect(
+        &address.to_string(),
+        &TlsOptions::default(),
+        None,
+        CallOptions::default(),
+    )
+    .await;
+    assert!(
+        matches!(result, Err(TokenGenErrors::FailedToConnectRpc(_))),
+        "Expected a connection failure, but got {:?}",
+        result.err()
+    );
+    Ok(())
+}
+
+// Test case to verify that a server requiring an API key is rejected early without one
+#[tokio::test]
+async fn missing_api_key_is_rejected_on_connect() -> Result<()> {
+    let policy = AccessPolicy::new(["secret".to_string()], None);
+    let (address, _server) = spawn_server("127.0.0.1:0", policy).await?;
+    let (address, tls) = (address.to_string(), TlsOptions::default());
+
+    let connect = |api_key: Option<&str>| {
+        RpcClient::connect(
+            &address,
+            &tls,
+            api_key.map(str::to_string),
+            CallOptions::default(),
+        )
+    };
+    assert!(matches!(
+        connect(None).await,
+        Err(TokenGenErrors::Unauthorized)
+    ));
+
+    let client = connect(Some("secret")).await?;
+    assert!(client.capabilities().is_some_and(|c| c.requires_api_key));
+    Ok(())
+}
+
+// Test case to verify that an API key is never sent in plaintext to a remote host
+#[tokio::test]
+async fn api_key_requires_tls_for_remote_hosts() -> Result<()> {
+    let result = RpcClient::connect(
+        "192.0.2.1:5001",
+        &TlsOptions::default(),
+        Some("secret".to_string()),
+        CallOptions::default(),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::InvalidTlsConfig(ref msg)) if msg.contains("tls://192.0.2.1:5001")
+    ));
+    Ok(())
+}
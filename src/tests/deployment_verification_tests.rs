@@ -0,0 +1,178 @@
+use std::fs;
+
+use super::common::{coin_metadata, module_bytecode, FixedCompiler, FixtureFullnode};
+use crate::{
+    commands::verify::verify_token_deployments,
+    errors::TokenGenErrors,
+    utils::{
+        deployment::{Deployment, DeploymentRecord},
+        fullnode::{CoinMetadata, FullnodeClient},
+        generation::ContractGenerator,
+        prompts::TokenInfo,
+        templates::generate_contract,
+    },
+    Result,
+};
+
+const PACKAGE_ID: &str = "0x0000000000000000000000000000000000000000000000000000000000000d31";
+const METADATA_ID: &str = "0x0000000000000000000000000000000000000000000000000000000000000d32";
+
+// Helper function to write a generated package recording a devnet deployment into a folder
+fn write_deployed_package(folder: &str, metadata_id: &str) -> Result<()> {
+    let token = TokenInfo {
+        name: "Deployed Token".to_string(),
+        symbol: "DPL".to_string(),
+        decimals: 6,
+        ..TokenInfo::default()
+    };
+    let files = generate_contract(&token)?.into_files(&token.name);
+    ContractGenerator::new(folder.to_string()).write_files(&files)?;
+
+    let mut record = DeploymentRecord::default();
+    record.deployments.insert(
+        "devnet".to_string(),
+        Deployment {
+            package_id: PACKAGE_ID.to_string(),
+            metadata_id: Some(metadata_id.to_string()),
+            digest: "DeployDigest".to_string(),
+            ..Deployment::default()
+        },
+    );
+    record.save(folder)
+}
+
+// Helper function to verify a package against a fullnode holding the published coin
+async fn verify_deployments(folder: &str, metadata: &CoinMetadata) -> Result<()> {
+    let mut published_id = [0; 32];
+    published_id[30] = 0x0d;
+    published_id[31] = 0x31;
+    let url = FixtureFullnode::new()
+        .with_coin(
+            PACKAGE_ID,
+            "deployedtoken",
+            &module_bytecode("deployedtoken", &published_id),
+            metadata,
+            false,
+        )
+        .start()
+        .await?;
+
+    verify_token_deployments(
+        folder,
+        None,
+        |_| FullnodeClient::new(&url),
+        &FixedCompiler(vec![module_bytecode("deployedtoken", &[0; 32])]),
+    )
+    .await
+}
+
+// Test case to verify that the local package and its recorded deployment are verified together
+#[tokio::test]
+async fn verifies_package_and_deployments() -> Result<()> {
+    let folder = "deployments_verified_package";
+    write_deployed_package(folder, METADATA_ID)?;
+
+    let result = verify_deployments(
+        folder,
+        &coin_metadata(METADATA_ID, "Deployed Token", "DPL", 6),
+    )
+    .await;
+    fs::remove_dir_all(folder)?;
+
+    assert!(result.is_ok(), "Verification failed: {:?}", result);
+    Ok(())
+}
+
+// Test case to verify that a deployment of another token fails, naming its environment
+#[tokio::test]
+async fn rejects_deployment_of_other_token() -> Result<()> {
+    let folder = "deployments_other_token_package";
+    write_deployed_package(folder, METADATA_ID)?;
+
+    let result = verify_deployments(
+        folder,
+        &coin_metadata(METADATA_ID, "Deployed Token", "OTHER", 6),
+    )
+    .await;
+    fs::remove_dir_all(folder)?;
+
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::VerificationError(message))
+            if message.starts_with("devnet deployment") && message.contains("OTHER")
+    ));
+    Ok(())
+}
+
+// Test case to verify that a deployment with another CoinMetadata than recorded fails
+#[tokio::test]
+async fn rejects_unrecorded_metadata() -> Result<()> {
+    let folder = "deployments_other_metadata_package";
+    write_deployed_package(folder, "0xd33")?;
+
+    let result = verify_deployments(
+        folder,
+        &coin_metadata(METADATA_ID, "Deployed Token", "DPL", 6),
+    )
+    .await;
+    fs::remove_dir_all(folder)?;
+
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::VerificationError(message)) if message.contains("CoinMetadata")
+    ));
+    Ok(())
+}
+
+// Test case to verify that a deployment recorded for another environment than the package's fails
+#[tokio::test]
+async fn rejects_deployment_of_other_environment() -> Result<()> {
+    let folder = "deployments_other_environment_package";
+    write_deployed_package(folder, METADATA_ID)?;
+    let mut record = DeploymentRecord::load(folder)?;
+    record.deployments.insert(
+        "testnet".to_string(),
+        Deployment {
+            package_id: PACKAGE_ID.to_string(),
+            ..Deployment::default()
+        },
+    );
+    record.save(folder)?;
+
+    let result = verify_deployments(
+        folder,
+        &coin_metadata(METADATA_ID, "Deployed Token", "DPL", 6),
+    )
+    .await;
+    fs::remove_dir_all(folder)?;
+
+    assert!(matches!(
+        result,
+        Err(ref error @ TokenGenErrors::VerificationError(ref message))
+            if message.starts_with("testnet deployment")
+                && message.contains("generated for devnet")
+                && error.exit_code() == 4
+    ));
+    Ok(())
+}
+
+// Test case to verify that a package without recorded deployments is rejected
+#[tokio::test]
+async fn requires_recorded_deployments() -> Result<()> {
+    let folder = "deployments_missing_package";
+    write_deployed_package(folder, METADATA_ID)?;
+    fs::remove_file(format!("{}/deployments.toml", folder))?;
+
+    let result = verify_deployments(
+        folder,
+        &coin_metadata(METADATA_ID, "Deployed Token", "DPL", 6),
+    )
+    .await;
+    fs::remove_dir_all(folder)?;
+
+    assert!(matches!(
+        result,
+        Err(ref error @ TokenGenErrors::InvalidInput(_)) if error.exit_code() == 2
+    ));
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use std::{fs, path::Path};
+
+use crate::{
+    commands::verify::verify_token_from_path,
+    errors::TokenGenErrors,
+    utils::{
+        client::responses::{FileRole, GeneratedFile},
+        diff_report::ModificationReport,
+        generation::ContractGenerator,
+        prompts::TokenInfo,
+        templates::generate_contract,
+        verify_helper::VerifyPathStruct,
+    },
+    Result,
+};
+
+// Token used by the diff report tests
+fn report_token() -> TokenInfo {
+    TokenInfo {
+        decimals: 6,
+        symbol: "DIFF".to_string(),
+        name: "Diff Token".to_string(),
+        description: "Reports tampering".to_string(),
+        is_frozen: true,
+        ..TokenInfo::default()
+    }
+}
+
+// Test case to verify that a tampered line shows up in the diff and the claimed fields are reported
+#[test]
+fn report_lists_tampered_lines_and_claims() -> Result<()> {
+    let token = TokenInfo {
+        icon_url: Some("https://example.com/diff.png".to_string()),
+        max_supply: Some(1_000_000),
+        ..report_token()
+    };
+    let mut files = generate_contract(&token)?.into_files(&token.name);
+    for file in files
+        .iter_mut()
+        .filter(|file| file.role == FileRole::Module)
+    {
+        file.contents = file
+            .contents
+            .replace("transfer::public_freeze_object(metadata);", "// removed");
+    }
+    let package = VerifyPathStruct::from_files(files)?;
+
+    let report = ModificationReport::new(&package);
+    let rendered = report.render(false);
+
+    // Claimed header fields
+    let claimed = report.claimed.as_ref().expect("Header not parsed");
+    assert_eq!(claimed.name, "Diff Token");
+    assert!(rendered.contains("  Symbol: DIFF\n"));
+    assert!(rendered.contains("  Decimals: 6\n"));
+    assert!(rendered.contains("  Icon URL: https://example.com/diff.png\n"));
+    assert!(rendered.contains("  Max supply: 1000000\n"));
+    assert!(rendered.contains("  Frozen: Yes\n"));
+
+    // Only the contract differs, with the removed and added lines
+    assert_eq!(report.modified, vec!["sources/difftoken.move".to_string()]);
+    assert!(report.missing.is_empty() && report.extra.is_empty());
+    assert!(rendered.contains("Modified files:\n  sources/difftoken.move\n"));
+    assert!(rendered.contains("--- generated/sources/difftoken.move"));
+    assert!(rendered.contains("-        transfer::public_freeze_object(metadata);"));
+    assert!(rendered.contains("+        // removed"));
+    assert!(!rendered.contains("Move.toml"));
+
+    // Plain rendering has no ANSI escape codes
+    assert!(!rendered.contains('\x1b'));
+    Ok(())
+}
+
+// Test case to verify the report for a contract without the generator header
+#[test]
+fn report_without_header() -> Result<()> {
+    let package = VerifyPathStruct::from_files(vec![
+        GeneratedFile {
+            path: "Move.toml".to_string(),
+            contents: "[package]\n".to_string(),
+            role: FileRole::Manifest,
+        },
+        GeneratedFile {
+            path: "sources/bar.move".to_string(),
+            contents: "module foo::bar {}\n".to_string(),
+            role: FileRole::Module,
+        },
+    ])?;
+    assert_eq!(package.file_name, "bar.move");
+
+    let report = ModificationReport::new(&package);
+
+    assert!(report.claimed.is_none());
+    assert!(report.diff.is_empty());
+    assert!(report
+        .render(false)
+        .contains("Contract has no generator header block."));
+    Ok(())
+}
+
+// Test case to verify that extra, missing and modified files are reported individually
+// The generated module is untouched, so only the other files of the package give it away.
+#[tokio::test]
+async fn verify_path_reports_package_files() -> Result<()> {
+    let test_folder = "package_report_token";
+    let token = report_token();
+    let files = generate_contract(&token)?.into_files(&token.name);
+    ContractGenerator::new(test_folder.to_string()).write_files(&files)?;
+
+    // A second module sorts before the generated one, which must still be found by its header
+    fs::write(
+        format!("{}/sources/aaa_backdoor.move", test_folder),
+        "module DiffToken::aaa_backdoor {}\n",
+    )?;
+    fs::create_dir_all(format!("{}/sources/nested", test_folder))?;
+    fs::write(format!("{}/sources/nested/notes.txt", test_folder), "notes")?;
+    fs::remove_file(format!("{}/tests/difftoken.move", test_folder))?;
+    fs::write(
+        format!("{}/Move.toml", test_folder),
+        format!("{}\n# edited\n", files[0].contents),
+    )?;
+
+    let result = verify_token_from_path(test_folder, None).await;
+    fs::remove_dir_all(test_folder)?;
+
+    match result {
+        Err(TokenGenErrors::ContractModified(report)) => {
+            assert_eq!(
+                report.claimed.map(|token| token.name).as_deref(),
+                Some("Diff Token")
+            );
+            assert_eq!(report.missing, vec!["tests/difftoken.move".to_string()]);
+            assert_eq!(
+                report.extra,
+                vec![
+                    "sources/aaa_backdoor.move".to_string(),
+                    "sources/nested/notes.txt".to_string()
+                ]
+            );
+            assert_eq!(report.modified, vec!["Move.toml".to_string()]);
+            assert!(report.diff.contains("+# edited"));
+        }
+        other => panic!("Expected a modification report, got {:?}", other),
+    }
+    Ok(())
+}
+
+// Test case to verify that verifying a tampered package returns the diff report
+#[tokio::test]
+async fn verify_path_returns_modification_report() -> Result<()> {
+    let test_folder = "diff_report_token";
+    let token = report_token();
+    let mut files = generate_contract(&token)?.into_files(&token.name);
+    for file in files
+        .iter_mut()
+        .filter(|file| file.role == FileRole::Module)
+    {
+        file.contents = file.contents.replace("b\"DIFF\"", "b\"FAKE\"");
+    }
+
+    ContractGenerator::new(test_folder.to_string()).write_files(&files)?;
+
+    let result = verify_token_from_path(test_folder, None).await;
+    if Path::new(test_folder).exists() {
+        fs::remove_dir_all(test_folder)?;
+    }
+
+    match result {
+        Err(TokenGenErrors::ContractModified(report)) => {
+            assert!(report.diff.contains("+            witness, 6, b\"FAKE\""));
+            let message = TokenGenErrors::ContractModified(report).to_string();
+            assert!(message.starts_with("Verification failed: Contract has been modified\n"));
+        }
+        other => panic!("Expected a modification report, but got {:?}", other),
+    }
+    Ok(())
+}
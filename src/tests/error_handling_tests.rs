@@ -1,9 +1,14 @@
 use tarpc::context;
 
-use super::common::test_utils::setup_test_client;
+use inquire::error::InquireError;
+use std::io;
+
+use super::common::{setup_mock_client, MockServer};
 use crate::{
     commands::verify::verify_token_using_url,
-    constants::{ADDRESS, DEFAULT_ENVIRONMENT},
+    constants::DEFAULT_ENVIRONMENT,
+    errors::{ErrorCategory, TokenGenErrors},
+    utils::{client::responses::RpcResponseErrors, git::GitSource},
     Result,
 };
 
@@ -11,16 +16,17 @@ use crate::{
 // It checks the behavior when invalid or malformed URLs are passed to the verification function.
 #[tokio::test]
 async fn verify_token_rpc_error_mapping() -> Result<()> {
-    let client = setup_test_client(ADDRESS).await?;
+    let client = setup_mock_client(MockServer::new());
 
     // Test with an invalid URL
     let invalid_url = "https://invalid-url-that-does-not-exist";
-    let result = verify_token_using_url(invalid_url, client.to_owned()).await;
+    let result =
+        verify_token_using_url(&GitSource::new(invalid_url), Some(client.clone().into())).await;
     assert!(result.is_err()); // Assert that the result is an error
 
     // Test with a malformed URL
     let malformed_url = "not-a-url";
-    let result = verify_token_using_url(malformed_url, client).await;
+    let result = verify_token_using_url(&GitSource::new(malformed_url), Some(client.into())).await;
     assert!(result.is_err()); // Assert that the result is an error
 
     Ok(())
@@ -30,7 +36,7 @@ async fn verify_token_rpc_error_mapping() -> Result<()> {
 // It checks different types of invalid input (such as invalid decimals, empty token name, and invalid environment).
 #[tokio::test]
 async fn error_propagation_flow() -> Result<()> {
-    let client = setup_test_client(ADDRESS).await?;
+    let client = setup_mock_client(MockServer::new());
 
     // Test invalid decimal places (255 is too high)
     let result = client
@@ -42,6 +48,8 @@ async fn error_propagation_flow() -> Result<()> {
             "Description".to_string(),
             false,
             DEFAULT_ENVIRONMENT.to_string(),
+            None,
+            None,
         )
         .await?;
     assert!(result.is_err()); // Assert that an error occurs due to invalid decimals
@@ -56,11 +64,13 @@ async fn error_propagation_flow() -> Result<()> {
             "Description".to_string(),
             false,
             DEFAULT_ENVIRONMENT.to_string(),
+            None,
+            None,
         )
         .await?;
     assert!(result.is_err()); // Assert that an error occurs due to empty name
 
-    // Test invalid environment (environment should fall back to 'devnet' if invalid)
+    // Test invalid environment (rejected instead of falling back to the default one)
     let result = client
         .create(
             context::current(),
@@ -70,9 +80,89 @@ async fn error_propagation_flow() -> Result<()> {
             "Description".to_string(),
             false,
             "invalid_env".to_string(), // Invalid environment
+            None,
+            None,
         )
         .await?;
-    assert!(result.is_ok()); // Assert that the result is ok even for an invalid environment (defaulting to 'devnet')
+    assert!(matches!(
+        result,
+        Err(RpcResponseErrors::InvalidEnvironment(_))
+    )); // Assert that the environment is rejected
 
     Ok(())
 }
+
+// Test case to verify that each error category maps to its documented exit code
+// Scripts rely on these codes to tell a tampered contract apart from an unreachable server.
+#[test]
+fn error_categories_exit_codes() {
+    let cases = vec![
+        (
+            TokenGenErrors::InvalidInput("bad".to_string()),
+            ErrorCategory::Usage,
+            2,
+        ),
+        (TokenGenErrors::InvalidRpcUrl, ErrorCategory::Usage, 2),
+        (TokenGenErrors::InvalidGitUrl, ErrorCategory::Usage, 2),
+        (
+            TokenGenErrors::FailedToConnectRpc(io::ErrorKind::ConnectionRefused.into()),
+            ErrorCategory::Rpc,
+            3,
+        ),
+        (
+            TokenGenErrors::ServerError("clone failed".to_string()),
+            ErrorCategory::Rpc,
+            3,
+        ),
+        (
+            TokenGenErrors::CompilerError("sui not found".to_string()),
+            ErrorCategory::Failure,
+            1,
+        ),
+        (
+            TokenGenErrors::VerificationError("mismatch".to_string()),
+            ErrorCategory::Verification,
+            4,
+        ),
+        (
+            TokenGenErrors::FileIoError(io::Error::other("disk full")),
+            ErrorCategory::FileSystem,
+            5,
+        ),
+        (
+            TokenGenErrors::CurrentDirectoryError,
+            ErrorCategory::FileSystem,
+            5,
+        ),
+        (
+            TokenGenErrors::PromptError(InquireError::OperationCanceled),
+            ErrorCategory::Cancelled,
+            130,
+        ),
+        (
+            TokenGenErrors::PromptError(InquireError::NotTTY),
+            ErrorCategory::Usage,
+            2,
+        ),
+        (
+            TokenGenErrors::FailedToCreateTokenContract("failed".to_string()),
+            ErrorCategory::Failure,
+            1,
+        ),
+    ];
+
+    for (error, category, exit_code) in cases {
+        assert_eq!(
+            error.category(),
+            category,
+            "Unexpected category for {}",
+            error
+        );
+        assert_eq!(
+            error.exit_code(),
+            exit_code,
+            "Unexpected exit code for {}",
+            error
+        );
+    }
+}
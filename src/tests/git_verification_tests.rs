@@ -0,0 +1,212 @@
+use git2::Repository;
+use std::fs;
+
+use super::common::{commit_all, setup_package_repository};
+use crate::{
+    commands::verify::verify_token_using_url,
+    errors::TokenGenErrors,
+    utils::{
+        git::{GitCheckout, GitSource},
+        helpers::{is_local_repository_url, is_valid_repository_url, repository_host},
+    },
+    Result,
+};
+
+// Helper function to create a repository whose default branch modifies the package after tagging it
+// The `v1` tag and the `release` branch point at the unmodified commit.
+fn setup_tagged_repository(dir: &str) -> Result<(String, String)> {
+    let url = setup_package_repository(dir, "token", "Tagged Token")?;
+    let repo = Repository::open(dir)?;
+    let generated = repo.head()?.peel_to_commit()?;
+    repo.tag_lightweight("v1", generated.as_object(), false)?;
+    repo.branch("release", &generated, false)?;
+
+    fs::write(
+        format!("{}/token/sources/extra.move", dir),
+        "module TaggedToken::extra {}\n",
+    )?;
+    commit_all(&repo, "Add extra module")?;
+
+    Ok((url, generated.id().to_string()))
+}
+
+// Test case to verify that repository URLs of any Git server and transport are accepted
+#[test]
+fn accepts_git_urls() {
+    for url in [
+        "https://github.com/osec/token",
+        "https://gitea.example.com/team/coins.git",
+        "http://bitbucket.org/team/coins",
+        "ssh://git@git.example.com:2222/team/coins.git",
+        "git://git.example.com/coins",
+        "git@bitbucket.org:team/coins.git",
+        "file:///srv/git/coins",
+    ] {
+        assert!(is_valid_repository_url(url).is_ok(), "Rejected {}", url);
+    }
+
+    for url in [
+        "not-a-url",
+        "https://invalid-url",
+        "ftp://example.com/team/coins",
+        "git@example.com:",
+    ] {
+        assert!(is_valid_repository_url(url).is_err(), "Accepted {}", url);
+    }
+}
+
+// Test case to verify that local repositories are detected in any spelling, and hosts are extracted
+#[test]
+fn detects_local_urls_and_hosts() {
+    for url in [
+        "file:///srv/git/coins",
+        "FILE:///etc/coins",
+        "file:/srv/git/coins",
+    ] {
+        assert!(
+            is_local_repository_url(url),
+            "Not detected as local: {}",
+            url
+        );
+    }
+    assert!(!is_local_repository_url("https://github.com/osec/token"));
+
+    assert_eq!(
+        repository_host("https://GitHub.com/osec/token").as_deref(),
+        Some("github.com")
+    );
+    assert_eq!(
+        repository_host("https://github.com@internal.example/osec/token").as_deref(),
+        Some("internal.example")
+    );
+    assert_eq!(
+        repository_host("git@bitbucket.org:team/coins.git").as_deref(),
+        Some("bitbucket.org")
+    );
+    assert_eq!(repository_host("file:///srv/git/coins"), None);
+}
+
+// Test case to verify that file links are only built for GitHub and GitLab HTTP(S) repositories
+#[test]
+fn builds_file_links() {
+    let source = GitSource {
+        url: "https://github.com/osec/coins.git".to_string(),
+        reference: Some("v1".to_string()),
+        subdir: Some("coins/token/".to_string()),
+    };
+    assert_eq!(
+        source.file_link("sources/token.move", "abc123").as_deref(),
+        Some("https://github.com/osec/coins/blob/abc123/coins/token/sources/token.move")
+    );
+
+    let source = GitSource::new("https://gitlab.com/osec/token/");
+    assert_eq!(
+        source.file_link("sources/token.move", "abc123").as_deref(),
+        Some("https://gitlab.com/osec/token/blob/abc123/sources/token.move")
+    );
+
+    assert!(GitSource::new("git@github.com:osec/token.git")
+        .file_link("sources/token.move", "abc123")
+        .is_none());
+    assert!(GitSource::new("https://gitea.example.com/osec/token")
+        .file_link("sources/token.move", "abc123")
+        .is_none());
+}
+
+// Test case to verify that branches, tags and commit hashes are checked out
+#[test]
+fn checks_out_references() -> Result<()> {
+    let dir = "git_checkout_references";
+    let (url, generated) = setup_tagged_repository(dir)?;
+
+    let checkout = |reference: Option<&str>| {
+        GitCheckout::new(&GitSource {
+            url: url.clone(),
+            reference: reference.map(str::to_string),
+            subdir: Some("token".to_string()),
+        })
+    };
+
+    let head = checkout(None);
+    let tag = checkout(Some("v1"));
+    let branch = checkout(Some("release"));
+    let commit = checkout(Some(&generated[..10]));
+    let missing = checkout(Some("v2"));
+
+    let head = head?;
+    assert_ne!(head.commit, generated);
+    assert!(head.package_path.join("sources/extra.move").is_file());
+    for checkout in [tag?, branch?, commit?] {
+        assert_eq!(checkout.commit, generated);
+        assert!(!checkout.package_path.join("sources/extra.move").exists());
+    }
+    assert!(matches!(missing, Err(TokenGenErrors::GitError(_))));
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+// Test case to verify that the clone is removed once the checkout is dropped
+#[test]
+fn removes_clone_on_drop() -> Result<()> {
+    let dir = "git_checkout_cleanup";
+    let url = setup_package_repository(dir, ".", "Cleanup Token")?;
+
+    let checkout = GitCheckout::new(&GitSource::new(&url))?;
+    let clone_path = checkout.package_path.clone();
+    assert!(clone_path.join("Move.toml").is_file());
+    drop(checkout);
+
+    fs::remove_dir_all(dir)?;
+    assert!(!clone_path.exists());
+    Ok(())
+}
+
+// Test case to verify that package folders outside the repository are rejected before cloning
+#[test]
+fn rejects_subdir_outside_repository() {
+    for subdir in ["../token", "/etc"] {
+        let result = GitCheckout::new(&GitSource {
+            url: "file:///path/does/not/exist".to_string(),
+            reference: None,
+            subdir: Some(subdir.to_string()),
+        });
+        assert!(
+            matches!(result, Err(TokenGenErrors::InvalidPath(_))),
+            "Accepted {}",
+            subdir
+        );
+    }
+}
+
+// Test case to verify a tagged package offline while the default branch is modified
+#[tokio::test]
+async fn verifies_tag_offline() -> Result<()> {
+    let dir = "git_verify_tag";
+    let (url, _) = setup_tagged_repository(dir)?;
+
+    let head = GitSource {
+        subdir: Some("token".to_string()),
+        ..GitSource::new(&url)
+    };
+    let tag = GitSource {
+        reference: Some("v1".to_string()),
+        ..head.clone()
+    };
+
+    let head_result = verify_token_using_url(&head, None).await;
+    let tag_result = verify_token_using_url(&tag, None).await;
+    fs::remove_dir_all(dir)?;
+
+    assert!(
+        matches!(head_result, Err(TokenGenErrors::ContractModified(_))),
+        "Expected a modified contract, got {:?}",
+        head_result
+    );
+    assert!(
+        tag_result.is_ok(),
+        "Failed to verify tag: {:?}",
+        tag_result.err()
+    );
+    Ok(())
+}
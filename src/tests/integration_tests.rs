@@ -2,7 +2,7 @@ use std::{fs, path::Path};
 use tarpc::context;
 
 use crate::{
-    constants::{ADDRESS, DEFAULT_ENVIRONMENT, SUB_FOLDER},
+    constants::{DEFAULT_ENVIRONMENT, SUB_FOLDER},
     errors::TokenGenErrors,
     utils::{
         client::rpc_client::TokenGenClient, generation::ContractGenerator, helpers::sanitize_name,
@@ -10,7 +10,7 @@ use crate::{
     Result,
 };
 
-use super::common::test_utils::setup_test_client;
+use super::common::{setup_mock_client, MockServer};
 
 // Test case for the full token creation flow, including RPC client interaction and file generation
 #[tokio::test]
@@ -24,11 +24,11 @@ async fn test_full_token_creation_flow() -> Result<()> {
     let is_frozen = false; // Whether the token is frozen or not
     let environment = DEFAULT_ENVIRONMENT.to_string(); // Environment for token deployment
 
-    // Initialize the RPC client using the local address for testing
-    let client: TokenGenClient = setup_test_client(ADDRESS).await?;
+    // Initialize the RPC client against the in-process mock server
+    let client: TokenGenClient = setup_mock_client(MockServer::new());
 
     // Test the successful creation of the token contract using the provided data
-    let (token_content, move_toml, _test_content) = client
+    let response = client
         .create(
             context::current(),
             decimals,
@@ -37,18 +37,16 @@ async fn test_full_token_creation_flow() -> Result<()> {
             token_description.to_string(),
             is_frozen,
             environment,
+            None,
+            None,
         )
         .await
         .map_err(TokenGenErrors::RpcError)? // Map RPC error to a custom error type
         .map_err(|e| TokenGenErrors::FailedToCreateTokenContract(e.to_string()))?; // Map contract creation failure
 
+    // Write the generated Move.toml, token contract and test files
     let contract_generator = ContractGenerator::new(test_folder.to_string());
-    // Create the base folder to store generated files
-    contract_generator.create_base_folder()?;
-
-    // Generate the Move.toml file and token contract file for the token
-    contract_generator.create_move_toml(&move_toml)?;
-    contract_generator.create_contract_file(token_name, &token_content, SUB_FOLDER)?;
+    contract_generator.write_files(&response.files)?;
 
     // Verify the existence of the necessary files after creation
     let sources_path = format!("{}/{}", test_folder, SUB_FOLDER); // Path to sources folder
@@ -82,7 +80,7 @@ async fn test_full_token_creation_flow() -> Result<()> {
 #[tokio::test]
 async fn test_error_handling_integration() -> Result<()> {
     // Initialize the RPC client
-    let client: TokenGenClient = setup_test_client(ADDRESS).await?;
+    let client: TokenGenClient = setup_mock_client(MockServer::new());
     // Test invalid token creation by providing incorrect parameters
     let result = client
         .create(
@@ -93,6 +91,8 @@ async fn test_error_handling_integration() -> Result<()> {
             "Description".to_string(),
             false,
             "invalid_env".to_string(), // Invalid environment
+            None,
+            None,
         )
         .await?;
     // Assert that the result is an error due to invalid parameters
@@ -0,0 +1,255 @@
+use std::{fs, path::Path};
+
+use super::common::{setup_mock_client, MockServer, Script};
+use crate::{
+    commands::create::create_tokens_from_manifest,
+    constants::DEFAULT_ENVIRONMENT,
+    errors::TokenGenErrors,
+    handlers::error_json,
+    utils::{
+        client::responses::RpcResponseErrors,
+        manifest::{load_manifest, BatchStatus},
+        prompts::TokenInfo,
+    },
+    CreateTokenParams, Result,
+};
+
+// Helper function to build `create` parameters for a manifest
+fn manifest_params(manifest: &str, continue_on_error: bool) -> CreateTokenParams {
+    CreateTokenParams {
+        rpc: None,
+        name: None,
+        symbol: None,
+        decimals: None,
+        description: None,
+        icon_url: None,
+        max_supply: None,
+        is_frozen: None,
+        environment: None,
+        offline: false,
+        non_interactive: true,
+        force: false,
+        manifest: Some(manifest.to_string()),
+        continue_on_error,
+    }
+}
+
+// Helper function to parse a manifest with the default environment
+fn load(path: &str) -> Result<Vec<TokenInfo>> {
+    load_manifest(path, DEFAULT_ENVIRONMENT, false)
+}
+
+// Helper function to write a manifest, run a check against it and remove it again
+fn with_manifest<T>(file_name: &str, content: &str, check: impl FnOnce(&str) -> T) -> Result<T> {
+    fs::write(file_name, content)?;
+    let result = check(file_name);
+    fs::remove_file(file_name)?;
+    Ok(result)
+}
+
+// Test case to verify that TOML, JSON and YAML manifests are parsed with the same defaults
+#[test]
+fn manifest_formats_are_parsed() -> Result<()> {
+    let toml = r#"
+[[tokens]]
+name = "Toml Token"
+symbol = "TOML"
+decimals = 6
+
+[[tokens]]
+name = "Toml Frozen"
+symbol = "TFRZ"
+decimals = 9
+description = "Frozen metadata"
+is_frozen = true
+environment = "testnet"
+"#;
+    let json = r#"{"tokens": [{"name": "Json Token", "symbol": "JSON", "decimals": 6}]}"#;
+    let yaml = "tokens:\n  - name: Yaml Token\n    symbol: YAML\n    decimals: 6\n";
+
+    let tokens = with_manifest("manifest_formats.toml", toml, load)??;
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].name, "Toml Token");
+    assert_eq!(tokens[0].description, "");
+    assert!(!tokens[0].is_frozen);
+    assert_eq!(tokens[0].environment, DEFAULT_ENVIRONMENT);
+    assert!(tokens[1].is_frozen);
+    assert_eq!(tokens[1].environment, "testnet");
+
+    let tokens = with_manifest("manifest_formats.json", json, load)??;
+    assert_eq!(tokens[0].symbol, "JSON");
+
+    let tokens = with_manifest("manifest_formats.yml", yaml, load)??;
+    assert_eq!(tokens[0].decimals, 6);
+
+    // Entries without an environment use the given default
+    let tokens = with_manifest("manifest_environment.yml", yaml, |path| {
+        load_manifest(path, "mainnet", false)
+    })??;
+    assert_eq!(tokens[0].environment, "mainnet");
+    Ok(())
+}
+
+// Test case to verify that invalid manifests are rejected before anything is created
+#[test]
+fn invalid_manifests_are_rejected() -> Result<()> {
+    // Unsupported extension
+    let result = with_manifest("manifest_invalid.txt", "", load)?;
+    assert!(matches!(result, Err(TokenGenErrors::InvalidManifest(_))));
+
+    // Missing required field and unknown field
+    let result = with_manifest(
+        "manifest_invalid.json",
+        r#"{"tokens": [{"name": "No Decimals", "symbol": "NDC"}]}"#,
+        load,
+    )?;
+    assert!(
+        matches!(result, Err(TokenGenErrors::InvalidManifest(ref msg)) if msg.contains("decimals"))
+    );
+
+    let result = with_manifest(
+        "manifest_unknown.json",
+        r#"{"tokens": [{"name": "Typo", "symbol": "TYP", "decimals": 6, "frozen": true}]}"#,
+        load,
+    )?;
+    assert!(
+        matches!(result, Err(TokenGenErrors::InvalidManifest(ref msg)) if msg.contains("frozen"))
+    );
+
+    // Empty token list
+    let result = with_manifest("manifest_empty.json", r#"{"tokens": []}"#, load)?;
+    assert!(matches!(result, Err(TokenGenErrors::InvalidManifest(_))));
+
+    // Entries are validated with the prompt rules, and every problem is reported
+    let json = r#"{"tokens": [
+        {"name": "Valid Token", "symbol": "VAL", "decimals": 6},
+        {"name": "Bad Token", "symbol": "TOOLONG", "decimals": 0},
+        {"name": "valid token", "symbol": "DUP", "decimals": 6}
+    ]}"#;
+    match with_manifest("manifest_entries.json", json, load)? {
+        Err(TokenGenErrors::InvalidManifest(msg)) => assert_eq!(
+            msg,
+            "entry 2: Symbol must be at most 5 characters long; entry 2: Decimals must be between 1 and 99; entry 3: Generates the same folder 'validtoken' as entry 1"
+        ),
+        other => panic!("Expected invalid manifest error, but got {:?}", other),
+    }
+    Ok(())
+}
+
+// Test case to verify that entries whose folder already exists are rejected before anything is written
+#[tokio::test]
+async fn manifest_rejects_existing_folders() -> Result<()> {
+    let manifest = "manifest_existing.json";
+    fs::write(
+        manifest,
+        r#"{"tokens": [
+            {"name": "Fresh Entry", "symbol": "FRSH", "decimals": 6},
+            {"name": "Existing Entry", "symbol": "EXST", "decimals": 6}
+        ]}"#,
+    )?;
+    fs::create_dir_all("existingentry")?;
+
+    let result = create_tokens_from_manifest(None, &manifest_params(manifest, true)).await;
+    let fresh_created = Path::new("freshentry").exists();
+    let forced = load_manifest(manifest, DEFAULT_ENVIRONMENT, true);
+    fs::remove_dir_all("existingentry")?;
+    let _ = fs::remove_dir_all("freshentry");
+    fs::remove_file(manifest)?;
+
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::InvalidManifest(ref msg))
+            if msg == "entry 2: Folder 'existingentry' already exists, use --force to overwrite it"
+    ));
+    assert!(
+        !fresh_created,
+        "An entry was created from a rejected manifest"
+    );
+    assert!(
+        forced.is_ok(),
+        "--force still rejected the manifest: {:?}",
+        forced
+    );
+    Ok(())
+}
+
+// Test case to verify that every manifest entry is generated into its own folder
+#[tokio::test]
+async fn manifest_creates_every_token() -> Result<()> {
+    let manifest = "manifest_batch.toml";
+    fs::write(
+        manifest,
+        "[[tokens]]\nname = \"Batch One\"\nsymbol = \"BONE\"\ndecimals = 6\n\n[[tokens]]\nname = \"Batch Two\"\nsymbol = \"BTWO\"\ndecimals = 9\nis_frozen = true\n",
+    )?;
+
+    let result = create_tokens_from_manifest(None, &manifest_params(manifest, false)).await;
+
+    let created = ["batchone", "batchtwo"].map(|folder| {
+        let exists = Path::new(folder)
+            .join(format!("sources/{}.move", folder))
+            .exists()
+            && Path::new(folder).join("Move.toml").exists();
+        let _ = fs::remove_dir_all(folder);
+        exists
+    });
+    fs::remove_file(manifest)?;
+
+    assert!(result.is_ok(), "Batch creation failed: {:?}", result);
+    assert_eq!(created, [true, true]);
+    Ok(())
+}
+
+// Test case to verify stopping on the first failure and continuing past failures
+#[tokio::test]
+async fn manifest_failures_are_reported_per_entry() -> Result<()> {
+    let manifest = "manifest_failures.json";
+    fs::write(
+        manifest,
+        r#"{"tokens": [
+            {"name": "Failing One", "symbol": "FONE", "decimals": 6},
+            {"name": "Failing Two", "symbol": "FTWO", "decimals": 6}
+        ]}"#,
+    )?;
+    let server = || MockServer::new().create(Script::Fail(RpcResponseErrors::InvalidSymbol));
+
+    // Stop on the first error: the remaining entries are skipped
+    let client = setup_mock_client(server());
+    let stopped =
+        create_tokens_from_manifest(Some(client.into()), &manifest_params(manifest, false)).await;
+
+    // Continue on error: every entry is attempted
+    let client = setup_mock_client(server());
+    let continued =
+        create_tokens_from_manifest(Some(client.into()), &manifest_params(manifest, true)).await;
+    fs::remove_file(manifest)?;
+
+    match &stopped {
+        Err(TokenGenErrors::BatchCreationFailed(report)) => {
+            let statuses: Vec<_> = report.entries.iter().map(|entry| entry.status).collect();
+            assert_eq!(statuses, [BatchStatus::Failed, BatchStatus::Skipped]);
+            assert_eq!(
+                report.entries[0].error.as_deref(),
+                Some("Failed to create token contract: Invalid token symbol")
+            );
+        }
+        other => panic!("Expected batch failure, but got {:?}", other),
+    }
+
+    match &continued {
+        Err(error @ TokenGenErrors::BatchCreationFailed(report)) => {
+            assert_eq!(report.count(BatchStatus::Failed), 2);
+            assert!(error
+                .to_string()
+                .starts_with("Batch creation failed for 2 of 2 tokens"));
+
+            let value = error_json(error);
+            assert_eq!(value["code"], "BATCH_CREATION_FAILED");
+            assert_eq!(value["report"]["entries"][1]["status"], "failed");
+        }
+        other => panic!("Expected batch failure, but got {:?}", other),
+    }
+
+    // Failed entries leave no partial packages behind
+    assert!(!Path::new("failingone").exists() && !Path::new("failingtwo").exists());
+    Ok(())
+}
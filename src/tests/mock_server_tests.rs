@@ -0,0 +1,295 @@
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+use tarpc::{client::RpcError, context};
+
+use super::common::{setup_mock_client, setup_package_repository, MockServer, Script};
+use crate::{
+    commands::{
+        create::create_token,
+        verify::{verify_token_from_path, verify_token_using_url},
+    },
+    constants::{DEFAULT_ENVIRONMENT, VERSION},
+    errors::TokenGenErrors,
+    utils::{
+        client::responses::RpcResponseErrors,
+        deployment::{Deployment, DeploymentRecord},
+        generation::ContractGenerator,
+        git::GitSource,
+        prompts::TokenInfo,
+        templates::generate_contract,
+    },
+    CreateTokenParams, Result,
+};
+
+// Helper function to write a generated package into the given folder
+fn write_package(folder: &str, token: &TokenInfo) -> Result<()> {
+    let files = generate_contract(token)?.into_files(&token.name);
+    ContractGenerator::new(folder.to_string()).write_files(&files)?;
+    Ok(())
+}
+
+// Test case to verify the full `create` command against the mock server
+// All parameters are provided up front, so no prompt is shown.
+#[tokio::test]
+async fn create_token_with_mock_server() -> Result<()> {
+    let params = CreateTokenParams {
+        rpc: None,
+        name: Some("Mock Create Token".to_string()),
+        symbol: Some("MCT".to_string()),
+        decimals: Some(6),
+        description: Some("Created against the mock server".to_string()),
+        icon_url: None,
+        max_supply: None,
+        is_frozen: Some(false),
+        environment: Some(DEFAULT_ENVIRONMENT.to_string()),
+        offline: false,
+        non_interactive: true,
+        force: false,
+        manifest: None,
+        continue_on_error: false,
+    };
+    let client = setup_mock_client(MockServer::new());
+
+    let result = create_token(Some(client.into()), &params).await;
+
+    // The command writes the package into the current directory
+    let base_folder = "mockcreatetoken";
+    let created = Path::new(base_folder)
+        .join("sources/mockcreatetoken.move")
+        .exists()
+        && Path::new(base_folder)
+            .join("tests/mockcreatetoken.move")
+            .exists()
+        && Path::new(base_folder).join("Move.toml").exists();
+    let record = DeploymentRecord::load(base_folder);
+    if Path::new(base_folder).exists() {
+        fs::remove_dir_all(base_folder)?;
+    }
+
+    assert!(result.is_ok(), "Token creation failed: {:?}", result);
+    assert!(created, "Package files not created");
+    // The generation time is recorded for later deployments
+    let record = record?;
+    assert_eq!(record.generator_version.as_deref(), Some(VERSION));
+    assert!(record
+        .generated_at
+        .is_some_and(|generated_at| generated_at.ends_with('Z')));
+    assert!(record.deployments.is_empty());
+    Ok(())
+}
+
+// Test case to verify that a failed `create` response leaves no partial package behind
+#[tokio::test]
+async fn create_token_failure_is_reported() -> Result<()> {
+    let params = CreateTokenParams {
+        rpc: None,
+        name: Some("Mock Failed Token".to_string()),
+        symbol: Some("MFT".to_string()),
+        decimals: Some(6),
+        description: Some(String::new()),
+        icon_url: None,
+        max_supply: None,
+        is_frozen: Some(true),
+        environment: Some(DEFAULT_ENVIRONMENT.to_string()),
+        offline: false,
+        non_interactive: true,
+        force: false,
+        manifest: None,
+        continue_on_error: false,
+    };
+    let client =
+        setup_mock_client(MockServer::new().create(Script::Fail(RpcResponseErrors::InvalidSymbol)));
+
+    let result = create_token(Some(client.into()), &params).await;
+
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::FailedToCreateTokenContract(ref msg)) if msg == "Invalid token symbol"
+    ));
+    assert!(!Path::new("mockfailedtoken").exists());
+    Ok(())
+}
+
+// Test case to verify that an existing package folder is only written into with `--force`
+// Without it nothing is generated; a failure with it leaves the existing files in place.
+#[tokio::test]
+async fn create_token_keeps_existing_folder() -> Result<()> {
+    let base_folder = "mockexistingtoken";
+    fs::create_dir_all(base_folder)?;
+    fs::write(Path::new(base_folder).join("keep.txt"), "existing")?;
+    let mut record = DeploymentRecord {
+        generated_at: Some("2024-12-01T11:30:00Z".to_string()),
+        ..DeploymentRecord::default()
+    };
+    record.deployments.insert(
+        "devnet".to_string(),
+        Deployment {
+            package_id: "0x1".to_string(),
+            ..Deployment::default()
+        },
+    );
+    record.save(base_folder)?;
+    let params = CreateTokenParams {
+        rpc: None,
+        name: Some("Mock Existing Token".to_string()),
+        symbol: Some("MET".to_string()),
+        decimals: Some(6),
+        description: Some(String::new()),
+        icon_url: None,
+        max_supply: None,
+        is_frozen: Some(false),
+        environment: Some(DEFAULT_ENVIRONMENT.to_string()),
+        offline: false,
+        non_interactive: true,
+        force: false,
+        manifest: None,
+        continue_on_error: false,
+    };
+
+    let refused = create_token(Some(setup_mock_client(MockServer::new()).into()), &params).await;
+    let forced_params = CreateTokenParams {
+        force: true,
+        ..params
+    };
+    let failed = create_token(
+        Some(
+            setup_mock_client(
+                MockServer::new().create(Script::Fail(RpcResponseErrors::InvalidSymbol)),
+            )
+            .into(),
+        ),
+        &forced_params,
+    )
+    .await;
+    let kept = Path::new(base_folder).join("keep.txt").exists();
+    let forced = create_token(
+        Some(setup_mock_client(MockServer::new()).into()),
+        &forced_params,
+    )
+    .await;
+    let written = Path::new(base_folder).join("Move.toml").exists();
+    let regenerated = DeploymentRecord::load(base_folder);
+    fs::remove_dir_all(base_folder)?;
+
+    assert!(
+        matches!(refused, Err(TokenGenErrors::InvalidInput(ref msg)) if msg.contains("--force")),
+        "Expected the existing folder to be refused, but got {:?}",
+        refused
+    );
+    assert!(failed.is_err());
+    assert!(kept, "Existing folder was removed after a failure");
+    assert!(forced.is_ok(), "Forced creation failed: {:?}", forced);
+    assert!(written, "Package files not written with --force");
+    // Earlier deployments are kept, with the new generation time
+    let regenerated = regenerated?;
+    assert_eq!(regenerated.deployments, record.deployments);
+    assert_ne!(regenerated.generated_at, record.generated_at);
+    Ok(())
+}
+
+// Test case to verify that every RPC response error surfaces with its message, as a mismatch or a server failure
+#[tokio::test]
+async fn verify_path_maps_every_response_error() -> Result<()> {
+    let test_folder = "mock_response_errors_token";
+    write_package(
+        test_folder,
+        &TokenInfo {
+            decimals: 6,
+            symbol: "MRE".to_string(),
+            name: "Mock Errors".to_string(),
+            ..TokenInfo::default()
+        },
+    )?;
+
+    let errors = vec![
+        RpcResponseErrors::ProgramModified,
+        RpcResponseErrors::InvalidDecimals,
+        RpcResponseErrors::InvalidSymbol,
+        RpcResponseErrors::InvalidName,
+        RpcResponseErrors::InvalidDescription,
+        RpcResponseErrors::GeneralError("general".to_string()),
+        RpcResponseErrors::InvalidPath("path".to_string()),
+        RpcResponseErrors::InvalidUrl("url".to_string()),
+        RpcResponseErrors::GitError("git".to_string()),
+        RpcResponseErrors::FileIoError("io".to_string()),
+        RpcResponseErrors::VerifyResultError("mismatch".to_string()),
+    ];
+
+    let mut unexpected = Vec::new();
+    for error in errors {
+        let expected = error.to_string();
+        let client = setup_mock_client(MockServer::new().verify_package(Script::Fail(error)));
+
+        match verify_token_from_path(test_folder, Some(client.into())).await {
+            // Modified contracts are reported with a diff instead of the bare message
+            Err(TokenGenErrors::ContractModified(_))
+                if expected == "Contract has been modified" => {}
+            // Only a reported mismatch is a verification failure; other errors are server failures
+            Err(ref error @ TokenGenErrors::VerificationError(ref msg))
+                if *msg == expected && expected == "mismatch" && error.exit_code() == 4 => {}
+            Err(ref error @ TokenGenErrors::ServerError(ref msg))
+                if *msg == expected && error.exit_code() == 3 => {}
+            other => unexpected.push(format!("{}: {:?}", expected, other)),
+        }
+    }
+
+    fs::remove_dir_all(test_folder)?;
+    assert!(
+        unexpected.is_empty(),
+        "Unexpected results: {:?}",
+        unexpected
+    );
+    Ok(())
+}
+
+// Test case to verify that a slow server response fails once the request deadline passes
+#[tokio::test]
+async fn delayed_response_exceeds_deadline() -> Result<()> {
+    let client = setup_mock_client(
+        MockServer::new()
+            .verify_url(Script::Succeed)
+            .verify_package(Script::Succeed)
+            .delay(Duration::from_millis(500)),
+    );
+
+    let mut ctx = context::current();
+    ctx.deadline = Instant::now() + Duration::from_millis(50);
+
+    let result = client
+        .verify_url(ctx, "https://github.com/osec/slow-token".to_string())
+        .await;
+    assert!(matches!(result, Err(RpcError::DeadlineExceeded)));
+
+    // Within the deadline the delayed response still arrives
+    let url = setup_package_repository("slow_token_repository", ".", "Slow Token")?;
+    let result = verify_token_using_url(&GitSource::new(&url), Some(client.clone().into())).await;
+    fs::remove_dir_all("slow_token_repository")?;
+    assert!(result.is_ok());
+
+    Ok(())
+}
+
+// Test case to verify that a dropped connection surfaces as an RPC error
+#[tokio::test]
+async fn dropped_connection_is_rpc_error() -> Result<()> {
+    let test_folder = "dropped_connection_package";
+    write_package(
+        test_folder,
+        &TokenInfo {
+            name: "Dropped Token".to_string(),
+            symbol: "DROP".to_string(),
+            decimals: 6,
+            ..TokenInfo::default()
+        },
+    )?;
+    let client = setup_mock_client(MockServer::new().drop_connection());
+
+    let result = verify_token_from_path(test_folder, Some(client.into())).await;
+    fs::remove_dir_all(test_folder)?;
+    assert!(matches!(result, Err(TokenGenErrors::RpcError(_))));
+
+    Ok(())
+}
@@ -7,14 +7,31 @@
 //! - Error handling
 //!
 //! # Test Setup
-//! Tests require a running RPC server and clean filesystem state.
-//! Use `cargo test --test-threads 1` to run tests sequentially.
+//! RPC calls go to an in-process mock server (`common::MockServer`) with scriptable
+//! responses, so the suite runs offline. Tests that need a real socket start the
+//! reference server on a random local port.
 
+pub mod auth_tests;
 pub mod common;
+pub mod config_tests;
+pub mod connection_tests;
+pub mod deployment_verification_tests;
+pub mod diff_report_tests;
 pub mod error_handling_tests;
+pub mod git_verification_tests;
 pub mod integration_tests;
+pub mod manifest_tests;
+pub mod mock_server_tests;
+pub mod offline_verification_tests;
+pub mod onchain_verification_tests;
 pub mod other_tests;
+pub mod output_tests;
+pub mod publish_tests;
+pub mod recursive_verification_tests;
 pub mod rpc_connection_tests;
+pub mod server_tests;
 pub mod success_handler_tests;
+pub mod template_tests;
+pub mod tls_tests;
 pub mod token_command_tests;
 pub mod validation_tests;
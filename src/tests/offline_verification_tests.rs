@@ -0,0 +1,241 @@
+use std::{env, fs, path::Path};
+
+use crate::{
+    commands::verify::verify_token_from_path,
+    errors::TokenGenErrors,
+    utils::{
+        client::responses::{FileRole, RpcResponseErrors},
+        generation::ContractGenerator,
+        prompts::TokenInfo,
+        templates::generate_contract,
+        verify_helper::{
+            parse_token_header, verify_content_locally, verify_package_locally, verify_path,
+        },
+    },
+    Result,
+};
+
+// Helper function to read a fixture from `src/tests/tokens`
+fn read_fixture(file_name: &str) -> String {
+    let current_dir = env::current_dir().expect("Failed to get current directory");
+    fs::read_to_string(format!(
+        "{}/src/tests/tokens/{}",
+        current_dir.display(),
+        file_name
+    ))
+    .expect("Failed to read fixture file")
+}
+
+// Test case to verify that the header block of a generated contract is parsed into token details
+#[test]
+fn parse_header_of_generated_contract() {
+    let token = parse_token_header(&read_fixture("valid_token.move")).expect("Header not parsed");
+
+    assert_eq!(token.name, "My token");
+    assert_eq!(token.decimals, 8);
+    assert_eq!(token.symbol, "MT");
+    assert_eq!(token.description, "Tetsing");
+    assert!(token.is_frozen);
+
+    // Contracts without a header are not recognised
+    assert!(parse_token_header(&read_fixture("invalid_token.move")).is_none());
+
+    // An icon URL `create` would reject makes the header invalid
+    let with_icon = read_fixture("valid_token.move").replace(
+        "/// is_frozen:",
+        "/// icon_url: javascript:alert(1)\n/// is_frozen:",
+    );
+    assert!(parse_token_header(&with_icon).is_none());
+    let with_icon = with_icon.replace("javascript:alert(1)", "https://example.com/mt.png");
+    assert_eq!(
+        parse_token_header(&with_icon).and_then(|token| token.icon_url),
+        Some("https://example.com/mt.png".to_string())
+    );
+}
+
+// Test case to verify that the local verifier accepts the valid fixture and rejects the invalid one
+#[test]
+fn verify_fixtures_locally() {
+    let result = verify_content_locally(
+        &read_fixture("valid_token.move"),
+        &read_fixture("valid_toml.toml"),
+    );
+    assert!(result.is_ok(), "Expected valid fixture to verify");
+
+    let result = verify_content_locally(
+        &read_fixture("invalid_token.move"),
+        &read_fixture("invalid_toml.toml"),
+    );
+    assert!(matches!(result, Err(RpcResponseErrors::ProgramModified)));
+}
+
+// Test case to verify that tampering with the body, header or Move.toml is detected
+#[test]
+fn verify_tampered_content_locally() -> Result<()> {
+    let generated = generate_contract(&TokenInfo {
+        decimals: 6,
+        symbol: "TAMP".to_string(),
+        name: "Tamper Token".to_string(),
+        description: "Checks tampering".to_string(),
+        is_frozen: false,
+        environment: "testnet".to_string(),
+        icon_url: None,
+        max_supply: None,
+    })?;
+    assert!(verify_content_locally(&generated.token, &generated.toml).is_ok());
+
+    // Modified function body
+    let tampered = generated
+        .token
+        .replace("coin::mint(", "coin::mint_and_transfer(");
+    let result = verify_content_locally(&tampered, &generated.toml);
+    assert!(matches!(result, Err(RpcResponseErrors::ProgramModified)));
+
+    // Header claiming frozen metadata while the body shares it
+    let tampered = generated
+        .token
+        .replace("/// is_frozen: false", "/// is_frozen: true");
+    let result = verify_content_locally(&tampered, &generated.toml);
+    assert!(matches!(result, Err(RpcResponseErrors::ProgramModified)));
+
+    // Move.toml publishing under another address
+    let tampered = generated
+        .toml
+        .replace(r#"TamperToken = "0x0""#, r#"TamperToken = "0x1""#);
+    let result = verify_content_locally(&generated.token, &tampered);
+    assert!(matches!(result, Err(RpcResponseErrors::ProgramModified)));
+
+    Ok(())
+}
+
+// Test case to verify that header values that would inject Move code are rejected, not re-rendered
+// The injected text is placed in the header and the body alike, so re-rendering would reproduce it.
+#[test]
+fn rejects_code_injected_through_header() -> Result<()> {
+    let token = TokenInfo {
+        decimals: 6,
+        symbol: "INJ".to_string(),
+        name: "Injected Token".to_string(),
+        description: "Plain description".to_string(),
+        icon_url: Some("https://example.com/icon.png".to_string()),
+        ..TokenInfo::default()
+    };
+    let files = generate_contract(&token)?.into_files(&token.name);
+    assert!(verify_package_locally(&files).is_ok());
+
+    let payloads = [
+        (
+            "Plain description",
+            r#"x", b"", option::none(), ctx); let y = (b""#,
+        ),
+        (
+            "https://example.com/icon.png",
+            r#"https://example.com/"); abort 0; (b""#,
+        ),
+    ];
+    for (original, payload) in payloads {
+        let injected: Vec<_> = files
+            .iter()
+            .cloned()
+            .map(|mut file| {
+                file.contents = file.contents.replace(original, payload);
+                file
+            })
+            .collect();
+        let module = injected
+            .iter()
+            .find(|file| file.role == FileRole::Module)
+            .map(|file| file.contents.clone())
+            .unwrap_or_default();
+
+        assert!(parse_token_header(&module).is_none());
+        assert!(matches!(
+            verify_package_locally(&injected),
+            Err(RpcResponseErrors::ProgramModified)
+        ));
+    }
+
+    // Rendering validates the token, whoever supplies it
+    let result = generate_contract(&TokenInfo {
+        description: r#"x" ++ b"#.to_string(),
+        ..token
+    });
+    assert!(matches!(result, Err(TokenGenErrors::InvalidInput(_))));
+    Ok(())
+}
+
+// Test case to verify a generated package folder end to end without an RPC client
+#[tokio::test]
+async fn verify_path_offline() -> Result<()> {
+    let test_folder = "offline_verification_token";
+    let token_name = "Offline Token";
+
+    let generated = generate_contract(&TokenInfo {
+        decimals: 9,
+        symbol: "OFF".to_string(),
+        name: token_name.to_string(),
+        ..TokenInfo::default()
+    })?;
+
+    // Write the package the same way `create` does
+    let contract_generator = ContractGenerator::new(test_folder.to_string());
+    contract_generator.write_files(&generated.into_files(token_name))?;
+
+    let result = verify_token_from_path(test_folder, None).await;
+
+    // Clean up before asserting so a failure does not leave the folder behind
+    if Path::new(test_folder).exists() {
+        fs::remove_dir_all(test_folder)?;
+    }
+
+    assert!(result.is_ok(), "Offline verification failed: {:?}", result);
+    Ok(())
+}
+
+// Test case to verify that symbolic links in a package are rejected instead of read
+// A link could point at any file on the machine, which would then be sent to the RPC service.
+#[cfg(unix)]
+#[tokio::test]
+async fn verify_path_rejects_symlinks() -> Result<()> {
+    let test_folder = "offline_symlink_token";
+    let token_name = "Symlink Token";
+    let generated = generate_contract(&TokenInfo {
+        decimals: 9,
+        symbol: "LINK".to_string(),
+        name: token_name.to_string(),
+        ..TokenInfo::default()
+    })?;
+    ContractGenerator::new(test_folder.to_string())
+        .write_files(&generated.into_files(token_name))?;
+    fs::write(format!("{}.secret", test_folder), "secret")?;
+
+    // A linked file inside `sources/`
+    let linked_file = format!("{}/sources/leak.move", test_folder);
+    std::os::unix::fs::symlink(
+        fs::canonicalize(format!("{}.secret", test_folder))?,
+        &linked_file,
+    )?;
+    let file_result = verify_path(test_folder);
+    fs::remove_file(&linked_file)?;
+
+    // A linked `tests/` folder
+    let tests_folder = format!("{}/tests", test_folder);
+    fs::rename(&tests_folder, format!("{}.tests", test_folder))?;
+    std::os::unix::fs::symlink(
+        fs::canonicalize(format!("{}.tests", test_folder))?,
+        &tests_folder,
+    )?;
+    let folder_result = verify_token_from_path(test_folder, None).await;
+
+    fs::remove_dir_all(test_folder)?;
+    fs::remove_dir_all(format!("{}.tests", test_folder))?;
+    fs::remove_file(format!("{}.secret", test_folder))?;
+
+    assert!(
+        matches!(file_result, Err(TokenGenErrors::InvalidPath(ref message)) if message.contains("leak.move"))
+    );
+    assert!(
+        matches!(folder_result, Err(TokenGenErrors::InvalidPath(ref message)) if message.contains("symbolic link"))
+    );
+    Ok(())
+}
@@ -0,0 +1,415 @@
+use serde_json::json;
+use std::fs;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    task::JoinHandle,
+};
+
+use super::common::{coin_metadata, module_bytecode, FixedCompiler, FixtureFullnode};
+use crate::{
+    constants::{MAX_FULLNODE_RESPONSE_BYTES, SUB_FOLDER},
+    errors::TokenGenErrors,
+    utils::{
+        fullnode::{CoinMetadata, FullnodeClient},
+        helpers::normalize_sui_address,
+        onchain::{
+            unpublish, verify_onchain_package, without_bytecode_version, BytecodeCompiler,
+            CompiledPackage,
+        },
+    },
+    Result,
+};
+
+// Package ID of the fixture coin, and the same ID as it is written in its bytecode
+const PACKAGE_ID: &str = "0x00000000000000000000000000000000000000000000000000000000000c0123";
+const PACKAGE_ID_BYTES: [u8; 32] = {
+    let mut bytes = [0; 32];
+    bytes[29] = 0x0c;
+    bytes[30] = 0x01;
+    bytes[31] = 0x23;
+    bytes
+};
+
+// Compiler whose bytecode differs by the max supply recorded in the header of the coin module
+struct SupplyCompiler;
+
+impl BytecodeCompiler for SupplyCompiler {
+    fn build(&self, package_path: &str) -> Result<CompiledPackage> {
+        let source =
+            fs::read_to_string(format!("{}/{}/chaintoken.move", package_path, SUB_FOLDER))?;
+        let max_supply = source
+            .lines()
+            .find_map(|line| line.strip_prefix("/// max_supply: "))
+            .unwrap_or_default();
+        Ok(CompiledPackage {
+            modules: vec![[
+                module_bytecode("chaintoken", &[0; 32]),
+                max_supply.as_bytes().to_vec(),
+            ]
+            .concat()],
+            dependencies: vec![],
+        })
+    }
+}
+
+// Test case to verify that a published coin is verified and reported with its metadata
+#[tokio::test]
+async fn verifies_published_coin() -> Result<()> {
+    let fullnode = FixtureFullnode::new()
+        .with_coin(
+            PACKAGE_ID,
+            "chaintoken",
+            &module_bytecode("chaintoken", &PACKAGE_ID_BYTES),
+            &coin_metadata("0x5", "Chain Token", "CHAIN", 9),
+            true,
+        )
+        .connect()
+        .await?;
+    let compiler = FixedCompiler(vec![module_bytecode("chaintoken", &[0; 32])]);
+
+    // Short addresses are padded to the full package ID
+    let onchain = verify_onchain_package("0xC0123", "testnet", &fullnode, &compiler).await?;
+    assert_eq!(onchain.package_id, PACKAGE_ID);
+    assert_eq!(onchain.module, "chaintoken");
+    assert_eq!(
+        onchain.coin_type,
+        format!("{}::chaintoken::CHAINTOKEN", PACKAGE_ID)
+    );
+    assert_eq!(onchain.metadata_id.as_deref(), Some("0x5"));
+    assert_eq!(onchain.token.name, "Chain Token");
+    assert_eq!(onchain.token.symbol, "CHAIN");
+    assert_eq!(onchain.token.decimals, 9);
+    assert_eq!(onchain.token.environment, "testnet");
+    assert!(onchain.token.is_frozen);
+    Ok(())
+}
+
+// Test case to verify that a coin minted once in whole tokens is verified with its fixed supply
+#[tokio::test]
+async fn verifies_fixed_supply_coin() -> Result<()> {
+    let coin_type = format!("{}::chaintoken::CHAINTOKEN", PACKAGE_ID);
+    let published = [
+        module_bytecode("chaintoken", &PACKAGE_ID_BYTES),
+        b"1000".to_vec(),
+    ]
+    .concat();
+    let url = FixtureFullnode::new()
+        .with_coin(
+            PACKAGE_ID,
+            "chaintoken",
+            &published,
+            &coin_metadata("0x5", "Chain Token", "CHAIN", 9),
+            true,
+        )
+        .respond(
+            "suix_getTotalSupply",
+            &coin_type,
+            json!({ "value": "1000000000000" }),
+        )
+        .start()
+        .await?;
+
+    let onchain = verify_onchain_package(
+        PACKAGE_ID,
+        "testnet",
+        &FullnodeClient::new(&url)?,
+        &SupplyCompiler,
+    )
+    .await?;
+    assert_eq!(onchain.token.max_supply, Some(1000));
+
+    // An unlimited coin with minted whole tokens still verifies, without a fixed supply
+    let url = FixtureFullnode::new()
+        .with_coin(
+            PACKAGE_ID,
+            "chaintoken",
+            &module_bytecode("chaintoken", &PACKAGE_ID_BYTES),
+            &coin_metadata("0x5", "Chain Token", "CHAIN", 9),
+            true,
+        )
+        .respond(
+            "suix_getTotalSupply",
+            &coin_type,
+            json!({ "value": "5000000000" }),
+        )
+        .start()
+        .await?;
+
+    let onchain = verify_onchain_package(
+        PACKAGE_ID,
+        "testnet",
+        &FullnodeClient::new(&url)?,
+        &SupplyCompiler,
+    )
+    .await?;
+    assert_eq!(onchain.token.max_supply, None);
+    Ok(())
+}
+
+// Test case to verify that a module differing from the generated one fails verification
+#[tokio::test]
+async fn rejects_modified_bytecode() -> Result<()> {
+    let mut bytecode = module_bytecode("chaintoken", &PACKAGE_ID_BYTES);
+    bytecode.push(0);
+    let fullnode = FixtureFullnode::new()
+        .with_coin(
+            PACKAGE_ID,
+            "chaintoken",
+            &bytecode,
+            &coin_metadata("0x5", "Chain Token", "CHAIN", 9),
+            true,
+        )
+        .connect()
+        .await?;
+    let compiler = FixedCompiler(vec![module_bytecode("chaintoken", &[0; 32])]);
+
+    let result = verify_onchain_package(PACKAGE_ID, "testnet", &fullnode, &compiler).await;
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::VerificationError(ref msg))
+            if msg.contains("an unknown compiler version") && msg.contains("sui release")
+    ));
+    Ok(())
+}
+
+// Test case to verify that modules compiled for another bytecode version still match
+#[tokio::test]
+async fn ignores_bytecode_version_header() -> Result<()> {
+    let versioned = |version: u8, package_id: &[u8]| {
+        [
+            &[0xa1, 0x1c, 0xeb, 0x0b, version, 0, 0, 0][..],
+            &module_bytecode("chaintoken", package_id)[4..],
+        ]
+        .concat()
+    };
+    assert_eq!(
+        without_bytecode_version(&versioned(6, &[0; 32])),
+        without_bytecode_version(&versioned(7, &[0; 32]))
+    );
+    // Bytes that are not a Move module are left alone
+    assert_eq!(without_bytecode_version(&[1, 2, 3]), vec![1, 2, 3]);
+
+    let fullnode = FixtureFullnode::new()
+        .with_coin(
+            PACKAGE_ID,
+            "chaintoken",
+            &versioned(6, &PACKAGE_ID_BYTES),
+            &coin_metadata("0x5", "Chain Token", "CHAIN", 9),
+            true,
+        )
+        .connect()
+        .await?;
+    let compiler = FixedCompiler(vec![versioned(7, &[0; 32])]);
+    let onchain = verify_onchain_package(PACKAGE_ID, "testnet", &fullnode, &compiler).await?;
+    assert_eq!(onchain.module, "chaintoken");
+    Ok(())
+}
+
+// Test case to verify that metadata naming another module fails verification
+#[tokio::test]
+async fn rejects_metadata_of_other_token() -> Result<()> {
+    let fullnode = FixtureFullnode::new()
+        .with_coin(
+            PACKAGE_ID,
+            "chaintoken",
+            &module_bytecode("chaintoken", &PACKAGE_ID_BYTES),
+            &coin_metadata("0x5", "Other Token", "CHAIN", 9),
+            true,
+        )
+        .connect()
+        .await?;
+    let compiler = FixedCompiler(vec![module_bytecode("chaintoken", &[0; 32])]);
+
+    let result = verify_onchain_package(PACKAGE_ID, "testnet", &fullnode, &compiler).await;
+    assert!(matches!(result, Err(TokenGenErrors::VerificationError(_))));
+    Ok(())
+}
+
+// Test case to verify that metadata that would inject Move code into the generated package fails verification
+#[tokio::test]
+async fn rejects_metadata_injecting_code() -> Result<()> {
+    let injected = [
+        r#"x", b"", option::none(), ctx); abort 0; (b""#,
+        "line\nbreak",
+    ];
+    for description in injected {
+        let fullnode = FixtureFullnode::new()
+            .with_coin(
+                PACKAGE_ID,
+                "chaintoken",
+                &module_bytecode("chaintoken", &PACKAGE_ID_BYTES),
+                &CoinMetadata {
+                    description: description.to_string(),
+                    ..coin_metadata("0x5", "Chain Token", "CHAIN", 9)
+                },
+                true,
+            )
+            .connect()
+            .await?;
+        // The compiler would accept anything, so only the metadata check can fail
+        let compiler = FixedCompiler(vec![module_bytecode("chaintoken", &[0; 32])]);
+
+        let result = verify_onchain_package(PACKAGE_ID, "testnet", &fullnode, &compiler).await;
+        assert!(matches!(
+            result,
+            Err(TokenGenErrors::VerificationError(ref message))
+                if message.contains("CoinMetadata") && message.contains("Description")
+        ));
+    }
+    Ok(())
+}
+
+// Test case to verify that packages without coin metadata or with several modules fail verification
+#[tokio::test]
+async fn rejects_packages_that_are_not_generated_coins() -> Result<()> {
+    let url = FixtureFullnode::new()
+        .respond(
+            "sui_getObject",
+            PACKAGE_ID,
+            json!({ "data": { "bcs": { "dataType": "package", "moduleMap": { "chaintoken": "oRzrCw==" } } } }),
+        )
+        .start()
+        .await?;
+    let result = verify_onchain_package(
+        PACKAGE_ID,
+        "testnet",
+        &FullnodeClient::new(&url)?,
+        &FixedCompiler(vec![]),
+    )
+    .await;
+    assert!(
+        matches!(result, Err(TokenGenErrors::VerificationError(message)) if message.contains("CoinMetadata"))
+    );
+
+    let url = FixtureFullnode::new()
+        .respond(
+            "sui_getObject",
+            PACKAGE_ID,
+            json!({ "data": { "bcs": { "dataType": "package", "moduleMap": { "a": "oRzrCw==", "b": "oRzrCw==" } } } }),
+        )
+        .start()
+        .await?;
+    let result = verify_onchain_package(
+        PACKAGE_ID,
+        "testnet",
+        &FullnodeClient::new(&url)?,
+        &FixedCompiler(vec![]),
+    )
+    .await;
+    assert!(
+        matches!(result, Err(TokenGenErrors::VerificationError(message)) if message.contains("2 modules"))
+    );
+    Ok(())
+}
+
+// Test case to verify that an unreachable fullnode is reported as a fullnode error
+#[tokio::test]
+async fn unreachable_fullnode_is_fullnode_error() -> Result<()> {
+    let fullnode = FullnodeClient::new("http://127.0.0.1:1")?;
+    let result =
+        verify_onchain_package(PACKAGE_ID, "testnet", &fullnode, &FixedCompiler(vec![])).await;
+    assert!(matches!(
+        result,
+        Err(ref error @ TokenGenErrors::FullnodeError(_)) if error.exit_code() == 3
+    ));
+    Ok(())
+}
+
+// Helper function to answer a single fullnode request with a raw HTTP response
+// The returned task yields the request line the client sent.
+async fn serve_raw_response(response: Vec<u8>) -> Result<(String, JoinHandle<String>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    let request_line = tokio::spawn(async move {
+        let Ok((mut stream, _)) = listener.accept().await else {
+            return String::new();
+        };
+        let mut buffer = [0; 4096];
+        let read = stream.read(&mut buffer).await.unwrap_or_default();
+        let _ = stream.write_all(&response).await;
+        let _ = stream.shutdown().await;
+        String::from_utf8_lossy(&buffer[..read])
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    });
+    Ok((address.to_string(), request_line))
+}
+
+// Test case to verify that the query string is sent, redirects are reported and oversized responses are refused
+#[tokio::test]
+async fn fullnode_requests_are_bounded() -> Result<()> {
+    let (address, request_line) = serve_raw_response(
+        b"HTTP/1.1 301 Moved Permanently\r\nLocation: https://fullnode.example.com/\r\nContent-Length: 0\r\n\r\n".to_vec(),
+    )
+    .await?;
+    let fullnode = FullnodeClient::new(&format!("http://{}/rpc?token=secret", address))?;
+    let result = fullnode.get_total_supply("0x2::sui::SUI").await;
+    assert_eq!(
+        request_line.await.unwrap_or_default(),
+        "POST /rpc?token=secret HTTP/1.1"
+    );
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::FullnodeError(ref msg))
+            if msg.contains("redirects are not followed") && msg.contains("https://fullnode.example.com/")
+    ));
+
+    let mut oversized = b"HTTP/1.1 200 OK\r\n\r\n".to_vec();
+    oversized.resize(MAX_FULLNODE_RESPONSE_BYTES as usize + 64, b' ');
+    let (address, _) = serve_raw_response(oversized).await?;
+    let fullnode = FullnodeClient::new(&format!("http://{}", address))?;
+    let result = fullnode.get_total_supply("0x2::sui::SUI").await;
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::FullnodeError(ref msg)) if msg.contains("exceeds")
+    ));
+    Ok(())
+}
+
+// Test case to verify the fullnode URL of each environment and its override
+#[test]
+fn fullnode_urls() -> Result<()> {
+    let fullnode = FullnodeClient::for_environment("testnet", None)?;
+    assert_eq!(fullnode.url(), "https://fullnode.testnet.sui.io/");
+
+    let fullnode = FullnodeClient::for_environment("testnet", Some("http://127.0.0.1:9000"))?;
+    assert_eq!(fullnode.url(), "http://127.0.0.1:9000/");
+
+    assert!(FullnodeClient::for_environment("localnet", None).is_err());
+    assert!(FullnodeClient::new("ftp://fullnode.example.com").is_err());
+    Ok(())
+}
+
+// Test case to verify that Sui addresses are validated and padded to 32 bytes
+#[test]
+fn normalizes_sui_addresses() -> Result<()> {
+    assert_eq!(
+        normalize_sui_address("0x2")?,
+        "0x0000000000000000000000000000000000000000000000000000000000000002"
+    );
+    assert_eq!(normalize_sui_address("0xC0123")?, PACKAGE_ID);
+
+    for address in ["", "0x", "2", "0xg1", &format!("0x{}", "1".repeat(65))] {
+        assert!(
+            normalize_sui_address(address).is_err(),
+            "Accepted {}",
+            address
+        );
+    }
+    Ok(())
+}
+
+// Test case to verify that every occurrence of the package ID is replaced by the unpublished address
+#[test]
+fn unpublishes_bytecode() {
+    assert_eq!(
+        unpublish(
+            &module_bytecode("chaintoken", &PACKAGE_ID_BYTES),
+            PACKAGE_ID
+        ),
+        module_bytecode("chaintoken", &[0; 32])
+    );
+}
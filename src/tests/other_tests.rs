@@ -1,13 +1,13 @@
 use tarpc::context;
 
-use super::common::test_utils::setup_test_client;
-use crate::{constants::ADDRESS, Result};
+use super::common::{setup_mock_client, MockServer};
+use crate::Result;
 
 // Test case to verify token creation works across different environments (devnet, testnet, mainnet)
 // It attempts to create a token in each environment and asserts that the token creation is successful for all environments.
 #[tokio::test]
 async fn environment_specific_token_creation() -> Result<()> {
-    let client = setup_test_client(ADDRESS).await?;
+    let client = setup_mock_client(MockServer::new());
 
     // Loop through different environments and test token creation
     for env in ["devnet", "testnet", "mainnet"] {
@@ -20,6 +20,8 @@ async fn environment_specific_token_creation() -> Result<()> {
                 "Test Description".to_string(),
                 false,
                 env.to_string(), // Environment for deployment
+                None,
+                None,
             )
             .await;
 
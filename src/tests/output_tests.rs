@@ -0,0 +1,182 @@
+use crate::{
+    constants::{PROTOCOL_VERSION, VERSION},
+    errors::TokenGenErrors,
+    handlers::{error_json, success_json, SuccessType},
+    utils::{
+        client::responses::ServerCapabilities, deployment::Deployment,
+        diff_report::ModificationReport, onchain::OnChainToken, prompts::TokenInfo,
+        templates::generate_contract, verify_helper::VerifyPathStruct,
+    },
+    Result,
+};
+
+// Test case to verify the JSON object reported for token creation
+#[test]
+fn create_success_json() {
+    let token_info = TokenInfo {
+        decimals: 6,
+        symbol: "JSON".to_string(),
+        name: "Json Token".to_string(),
+        description: String::new(),
+        is_frozen: false,
+        environment: "testnet".to_string(),
+        icon_url: None,
+        max_supply: None,
+    };
+    let files = vec![
+        "jsontoken/Move.toml".to_string(),
+        "jsontoken/sources/jsontoken.move".to_string(),
+    ];
+
+    let value = success_json(&SuccessType::TokenCreated(
+        token_info,
+        "Contract has been generated at: jsontoken".to_string(),
+        files,
+    ));
+
+    assert_eq!(value["status"], "success");
+    assert_eq!(value["command"], "create");
+    assert_eq!(value["token"]["name"], "Json Token");
+    assert_eq!(value["token"]["decimals"], 6);
+    assert_eq!(value["token"]["environment"], "testnet");
+    assert_eq!(value["files"][1], "jsontoken/sources/jsontoken.move");
+}
+
+// Test case to verify the JSON object reported for each verification source
+#[test]
+fn verify_success_json() {
+    let value = success_json(&SuccessType::TokenVerified {
+        path: Some("./jsontoken".to_string()),
+        url: None,
+        file_name: Some("jsontoken.move".to_string()),
+        environment: Some("testnet".to_string()),
+        commit: None,
+        link: None,
+    });
+    assert_eq!(value["command"], "verify");
+    assert_eq!(value["source_type"], "path");
+    assert_eq!(value["source"], "./jsontoken");
+    assert_eq!(value["file_name"], "jsontoken.move");
+    assert_eq!(value["environment"], "testnet");
+    assert_eq!(value["result"], "verified");
+
+    let value = success_json(&SuccessType::TokenVerified {
+        path: None,
+        url: Some("https://github.com/example/jsontoken".to_string()),
+        file_name: Some("jsontoken.move".to_string()),
+        environment: Some("mainnet".to_string()),
+        commit: Some("abc123".to_string()),
+        link: None,
+    });
+    assert_eq!(value["source_type"], "url");
+    assert_eq!(value["environment"], "mainnet");
+    assert_eq!(value["commit"], "abc123");
+
+    let value = success_json(&SuccessType::AddressVerified(OnChainToken {
+        package_id: "0x1234".to_string(),
+        module: "jsontoken".to_string(),
+        coin_type: "0x1234::jsontoken::JSONTOKEN".to_string(),
+        metadata_id: Some("0x5".to_string()),
+        token: TokenInfo {
+            decimals: 6,
+            symbol: "JSON".to_string(),
+            name: "Json Token".to_string(),
+            is_frozen: true,
+            environment: "mainnet".to_string(),
+            ..TokenInfo::default()
+        },
+    }));
+    assert_eq!(value["source_type"], "address");
+    assert_eq!(value["source"], "0x1234");
+    assert_eq!(value["environment"], "mainnet");
+    assert_eq!(value["token"]["symbol"], "JSON");
+    assert_eq!(value["token"]["is_frozen"], true);
+    assert!(value["file_name"].is_null());
+}
+
+// Test case to verify the JSON object reported by `publish`
+#[test]
+fn publish_success_json() {
+    let value = success_json(&SuccessType::TokenPublished {
+        path: "./jsontoken".to_string(),
+        environment: "testnet".to_string(),
+        deployment: Deployment {
+            package_id: "0x1234".to_string(),
+            treasury_cap_id: Some("0x6".to_string()),
+            metadata_id: None,
+            digest: "JsonDigest".to_string(),
+            generator_version: Some(VERSION.to_string()),
+            generated_at: None,
+            published_at: None,
+        },
+    });
+    assert_eq!(value["command"], "publish");
+    assert_eq!(value["path"], "./jsontoken");
+    assert_eq!(value["environment"], "testnet");
+    assert_eq!(value["deployment"]["package_id"], "0x1234");
+    assert_eq!(value["deployment"]["treasury_cap_id"], "0x6");
+    assert!(value["deployment"]["metadata_id"].is_null());
+    assert_eq!(value["deployment"]["digest"], "JsonDigest");
+    assert_eq!(value["deployment"]["generator_version"], VERSION);
+}
+
+// Test case to verify the JSON object reported by `doctor`
+#[test]
+fn doctor_success_json() {
+    let value = success_json(&SuccessType::Doctor {
+        rpc: "tokengen.internal:5001".to_string(),
+        server: ServerCapabilities {
+            version: "0.1.0".to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            environments: vec!["devnet".to_string()],
+            requires_api_key: true,
+        },
+    });
+    assert_eq!(value["command"], "doctor");
+    assert_eq!(value["client"]["version"], VERSION);
+    assert_eq!(value["client"]["protocol_version"], PROTOCOL_VERSION);
+    assert_eq!(value["rpc"], "tokengen.internal:5001");
+    assert_eq!(value["server"]["version"], "0.1.0");
+    assert_eq!(value["server"]["environments"][0], "devnet");
+    assert_eq!(value["server"]["requires_api_key"], true);
+}
+
+// Test case to verify that errors carry a stable code and the diff report for modified contracts
+#[test]
+fn error_json_codes() -> Result<()> {
+    let value = error_json(&TokenGenErrors::InvalidRpcUrl);
+    assert_eq!(value["status"], "error");
+    assert_eq!(value["code"], "INVALID_RPC_URL");
+    assert_eq!(value["category"], "usage");
+    assert_eq!(value["exit_code"], 2);
+    assert_eq!(value["message"], "Invalid RPC url");
+
+    let value = error_json(&TokenGenErrors::VerificationError("mismatch".to_string()));
+    assert_eq!(value["code"], "VERIFICATION_FAILED");
+    assert_eq!(value["message"], "Verification failed: mismatch");
+
+    let mut files = generate_contract(&TokenInfo {
+        decimals: 6,
+        symbol: "JSON".to_string(),
+        name: "Json Token".to_string(),
+        ..TokenInfo::default()
+    })?
+    .into_files("Json Token");
+    files[1].contents = files[1].contents.replace("b\"JSON\"", "b\"NOSJ\"");
+    files.pop();
+    let report = ModificationReport::new(&VerifyPathStruct::from_files(files)?);
+
+    let value = error_json(&TokenGenErrors::ContractModified(Box::new(report)));
+    assert_eq!(value["code"], "CONTRACT_MODIFIED");
+    assert_eq!(
+        value["message"],
+        "Verification failed: Contract has been modified"
+    );
+    assert_eq!(value["report"]["claimed"]["symbol"], "JSON");
+    assert_eq!(value["report"]["modified"][0], "sources/jsontoken.move");
+    assert_eq!(value["report"]["missing"][0], "tests/jsontoken.move");
+    assert!(value["report"]["diff"]
+        .as_str()
+        .is_some_and(|diff| diff.contains("b\"NOSJ\"")));
+    Ok(())
+}
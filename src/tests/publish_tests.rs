@@ -0,0 +1,381 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde_json::{json, Value};
+use std::{fs, path::Path};
+
+use super::common::{publish_transaction, FixedCompiler, FixtureFullnode};
+use crate::{
+    commands::publish::publish_token,
+    constants::DEPLOYMENTS_FILE,
+    errors::TokenGenErrors,
+    utils::{
+        deployment::{Deployment, DeploymentRecord},
+        fullnode::FullnodeClient,
+        generation::ContractGenerator,
+        keystore::{load_keypair, SuiKeypair},
+        onchain::{BytecodeCompiler, CompiledPackage},
+        prompts::TokenInfo,
+        templates::generate_contract,
+        transaction::check_publish_transaction,
+    },
+    Result,
+};
+
+const PACKAGE_ID: &str = "0x00000000000000000000000000000000000000000000000000000000000c0ffe";
+const TREASURY_CAP_ID: &str = "0x0000000000000000000000000000000000000000000000000000000000000ca9";
+const METADATA_ID: &str = "0x0000000000000000000000000000000000000000000000000000000000000da7";
+const TX_BYTES: &[u8] = b"publish transaction";
+
+// Helper function to write a generated package for the given environment into a folder
+fn write_package(folder: &str, environment: &str) -> Result<()> {
+    let token = TokenInfo {
+        name: "Publish Token".to_string(),
+        symbol: "PUB".to_string(),
+        decimals: 9,
+        environment: environment.to_string(),
+        ..TokenInfo::default()
+    };
+    let files = generate_contract(&token)?.into_files(&token.name);
+    ContractGenerator::new(folder.to_string()).write_files(&files)?;
+    Ok(())
+}
+
+// Helper function to write a keystore with Ed25519 keys made of the given seeds
+fn write_keystore(path: &str, keys: &[(u8, [u8; 32])]) -> Result<()> {
+    let keys: Vec<String> = keys
+        .iter()
+        .map(|(flag, seed)| STANDARD.encode([&[*flag][..], seed].concat()))
+        .collect();
+    fs::write(path, serde_json::to_string(&keys).unwrap_or_default())?;
+    Ok(())
+}
+
+// Helper function to build the response to a publish transaction with the given status
+fn publish_response(status: Value) -> Value {
+    json!({
+        "digest": "PublishDigest",
+        "effects": { "status": status },
+        "objectChanges": [
+            { "type": "mutated", "objectType": "0x2::coin::Coin<0x2::sui::SUI>", "objectId": "0x9" },
+            { "type": "published", "packageId": PACKAGE_ID, "modules": ["publishtoken"] },
+            {
+                "type": "created",
+                "objectType": format!("0x2::coin::TreasuryCap<{}::publishtoken::PUBLISHTOKEN>", PACKAGE_ID),
+                "objectId": TREASURY_CAP_ID,
+            },
+            {
+                "type": "created",
+                "objectType": format!("0x2::coin::CoinMetadata<{}::publishtoken::PUBLISHTOKEN>", PACKAGE_ID),
+                "objectId": METADATA_ID,
+            },
+        ],
+    })
+}
+
+// Test case to verify that a published package is recorded next to earlier deployments
+#[tokio::test]
+async fn publishes_package_and_records_deployment() -> Result<()> {
+    let folder = "publish_records_package";
+    write_package(folder, "devnet")?;
+
+    // A deployment to another environment is kept
+    let testnet = Deployment {
+        package_id: "0x1".to_string(),
+        digest: "TestnetDigest".to_string(),
+        ..Deployment::default()
+    };
+    // The package was generated by an older version than the one publishing it
+    let mut record = DeploymentRecord {
+        generator_version: Some("0.1.0".to_string()),
+        generated_at: Some("2024-12-01T11:30:00Z".to_string()),
+        ..DeploymentRecord::default()
+    };
+    record
+        .deployments
+        .insert("testnet".to_string(), testnet.clone());
+    record.save(folder)?;
+
+    let signer = SuiKeypair::from_seed(&[7; 32])?;
+    let compiler = FixedCompiler(vec![vec![0xa1, 0x1c, 0xeb, 0x0b]]);
+    let fullnode = FixtureFullnode::new()
+        .with_publish(
+            &signer.address(),
+            &publish_transaction(&signer.address(), &compiler.build(folder)?, 1000),
+            publish_response(json!({ "status": "success" })),
+        )
+        .connect()
+        .await?;
+    let result = publish_token(folder, "devnet", &fullnode, &compiler, &signer, 1000).await;
+    let record = DeploymentRecord::load(folder);
+    fs::remove_dir_all(folder)?;
+
+    assert!(result.is_ok(), "Publishing failed: {:?}", result);
+    let record = record?;
+    assert_eq!(record.deployments.get("testnet"), Some(&testnet));
+    let devnet = record
+        .deployments
+        .get("devnet")
+        .cloned()
+        .unwrap_or_default();
+    assert_eq!(devnet.package_id, PACKAGE_ID);
+    assert_eq!(devnet.treasury_cap_id.as_deref(), Some(TREASURY_CAP_ID));
+    assert_eq!(devnet.metadata_id.as_deref(), Some(METADATA_ID));
+    assert_eq!(devnet.digest, "PublishDigest");
+    assert_eq!(devnet.generator_version.as_deref(), Some("0.1.0"));
+    assert_eq!(devnet.generated_at.as_deref(), Some("2024-12-01T11:30:00Z"));
+    assert!(devnet
+        .published_at
+        .is_some_and(|published_at| published_at.ends_with('Z')));
+    Ok(())
+}
+
+// Test case to verify that a failed transaction is reported and records nothing
+#[tokio::test]
+async fn failed_transaction_is_publish_error() -> Result<()> {
+    let folder = "publish_failed_package";
+    write_package(folder, "devnet")?;
+
+    let signer = SuiKeypair::from_seed(&[8; 32])?;
+    let compiler = FixedCompiler(vec![]);
+    let fullnode = FixtureFullnode::new()
+        .with_publish(
+            &signer.address(),
+            &publish_transaction(&signer.address(), &compiler.build(folder)?, 1),
+            publish_response(json!({ "status": "failure", "error": "InsufficientGas" })),
+        )
+        .connect()
+        .await?;
+    let result = publish_token(folder, "devnet", &fullnode, &compiler, &signer, 1).await;
+    let recorded = Path::new(folder).join(DEPLOYMENTS_FILE).exists();
+    fs::remove_dir_all(folder)?;
+
+    assert!(matches!(
+        result,
+        Err(ref error @ TokenGenErrors::PublishError(ref message))
+            if message.contains("InsufficientGas") && error.exit_code() == 1
+    ));
+    assert!(!recorded);
+    Ok(())
+}
+
+// Test case to verify that a transaction built by the fullnode is not signed unless it only publishes the package
+#[tokio::test]
+async fn refuses_unexpected_publish_transaction() -> Result<()> {
+    let folder = "publish_unexpected_transaction_package";
+    write_package(folder, "devnet")?;
+
+    let signer = SuiKeypair::from_seed(&[10; 32])?;
+    let compiler = FixedCompiler(vec![vec![0xa1, 0x1c, 0xeb, 0x0b]]);
+    let package = compiler.build(folder)?;
+    let fullnode = FixtureFullnode::new()
+        .with_publish(
+            &signer.address(),
+            &publish_transaction(&signer.address(), &package, 5000),
+            publish_response(json!({ "status": "success" })),
+        )
+        .connect()
+        .await?;
+    let result = publish_token(folder, "devnet", &fullnode, &compiler, &signer, 1000).await;
+    let recorded = Path::new(folder).join(DEPLOYMENTS_FILE).exists();
+    fs::remove_dir_all(folder)?;
+
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::PublishError(ref message))
+            if message.contains("gas budget is 5000 instead of 1000")
+    ));
+    assert!(!recorded);
+
+    let sender = signer.address();
+    let expected = publish_transaction(&sender, &package, 1000);
+    assert!(check_publish_transaction(&expected, &sender, &package, 1000).is_ok());
+
+    let other = SuiKeypair::from_seed(&[11; 32])?.address();
+    // The sender and gas owner are followed by the gas payment, price, budget and expiration
+    let mut other_sender = expected.clone();
+    let sender_at = other_sender.len() - 155;
+    other_sender[sender_at..sender_at + 32].copy_from_slice(&[0xee; 32]);
+    let mut gas_owner = expected.clone();
+    let owner_at = gas_owner.len() - 49;
+    gas_owner[owner_at..owner_at + 32].copy_from_slice(&[0xee; 32]);
+    let mut extra_command = expected.clone();
+    extra_command[37] = 3;
+    let mut trailing = expected.clone();
+    trailing.push(0);
+    let other_modules = FixedCompiler(vec![vec![0xa1, 0x1c, 0xeb, 0x0c]]).build(folder)?;
+
+    for (tx, reason) in [
+        (
+            publish_transaction(&other, &package, 1000),
+            "UpgradeCap to another address",
+        ),
+        (other_sender, "sent by another address"),
+        (gas_owner, "gas is paid by another address"),
+        (extra_command, "exactly two commands"),
+        (trailing, "trailing data"),
+        (
+            publish_transaction(&sender, &other_modules, 1000),
+            "other modules",
+        ),
+        (b"publish transaction".to_vec(), "unexpected"),
+    ] {
+        assert!(
+            matches!(
+                check_publish_transaction(&tx, &sender, &package, 1000),
+                Err(TokenGenErrors::PublishError(ref message)) if message.contains(reason)
+            ),
+            "Transaction accepted or refused for another reason than {}",
+            reason
+        );
+    }
+    Ok(())
+}
+
+// Compiles like `FixedCompiler`, then puts a folder where the deployment record is written
+struct RecordBlockingCompiler(FixedCompiler);
+
+impl BytecodeCompiler for RecordBlockingCompiler {
+    fn build(&self, package_path: &str) -> Result<CompiledPackage> {
+        fs::create_dir_all(Path::new(package_path).join(DEPLOYMENTS_FILE))?;
+        self.0.build(package_path)
+    }
+}
+
+// Test case to verify that an invalid deployment record is reported before anything is published
+#[tokio::test]
+async fn invalid_record_is_rejected_before_publishing() -> Result<()> {
+    let folder = "publish_invalid_record_package";
+    write_package(folder, "devnet")?;
+    fs::write(Path::new(folder).join(DEPLOYMENTS_FILE), "[deployments")?;
+
+    // The fullnode has no responses, so any publish attempt fails with a fullnode error
+    let signer = SuiKeypair::from_seed(&[12; 32])?;
+    let fullnode = FixtureFullnode::new().connect().await?;
+    let result = publish_token(
+        folder,
+        "devnet",
+        &fullnode,
+        &FixedCompiler(vec![]),
+        &signer,
+        1000,
+    )
+    .await;
+    fs::remove_dir_all(folder)?;
+
+    assert!(
+        matches!(result, Err(TokenGenErrors::InvalidInput(message)) if message.contains(DEPLOYMENTS_FILE))
+    );
+    Ok(())
+}
+
+// Test case to verify that a deployment that cannot be recorded is reported with its object IDs
+#[tokio::test]
+async fn unrecorded_deployment_names_its_objects() -> Result<()> {
+    let folder = "publish_unrecorded_package";
+    write_package(folder, "devnet")?;
+
+    let signer = SuiKeypair::from_seed(&[13; 32])?;
+    let compiler = RecordBlockingCompiler(FixedCompiler(vec![vec![0xa1, 0x1c, 0xeb, 0x0b]]));
+    let fullnode = FixtureFullnode::new()
+        .with_publish(
+            &signer.address(),
+            &publish_transaction(&signer.address(), &compiler.0.build(folder)?, 1000),
+            publish_response(json!({ "status": "success" })),
+        )
+        .connect()
+        .await?;
+    let result = publish_token(folder, "devnet", &fullnode, &compiler, &signer, 1000).await;
+    fs::remove_dir_all(folder)?;
+
+    assert!(matches!(
+        result,
+        Err(ref error @ TokenGenErrors::FileIoError(_))
+            if error.exit_code() == 5
+                && [PACKAGE_ID, TREASURY_CAP_ID, METADATA_ID]
+                    .iter()
+                    .all(|id| error.to_string().contains(id))
+    ));
+    Ok(())
+}
+
+// Test case to verify that a package is not published to another environment than it was generated for
+#[tokio::test]
+async fn rejects_package_of_other_environment() -> Result<()> {
+    let folder = "publish_other_environment_package";
+    write_package(folder, "testnet")?;
+
+    let signer = SuiKeypair::from_seed(&[9; 32])?;
+    let fullnode = FullnodeClient::new("http://127.0.0.1:1")?;
+    let result = publish_token(
+        folder,
+        "mainnet",
+        &fullnode,
+        &FixedCompiler(vec![]),
+        &signer,
+        1,
+    )
+    .await;
+    fs::remove_dir_all(folder)?;
+
+    assert!(
+        matches!(result, Err(TokenGenErrors::InvalidInput(message)) if message.contains("testnet"))
+    );
+    Ok(())
+}
+
+// Test case to verify that transactions are signed over the intent digest with the key's public key
+#[test]
+fn signs_transaction_intent() -> Result<()> {
+    let signer = SuiKeypair::from_seed(&[1; 32])?;
+    let signature = STANDARD
+        .decode(signer.sign_transaction(TX_BYTES))
+        .unwrap_or_default();
+
+    // Flag, 64-byte signature and 32-byte public key
+    assert_eq!(signature.len(), 97);
+    assert_eq!(signature[0], 0);
+    let (signature, public_key) = signature[1..].split_at(64);
+
+    let digest = Blake2b::<U32>::new()
+        .chain_update([0, 0, 0])
+        .chain_update(TX_BYTES)
+        .finalize();
+    assert!(UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(&digest, signature)
+        .is_ok());
+
+    let address = Blake2b::<U32>::new()
+        .chain_update([0])
+        .chain_update(public_key)
+        .finalize();
+    assert_eq!(signer.address(), format!("0x{:x}", address));
+    Ok(())
+}
+
+// Test case to verify that keys are selected from a keystore by sender
+#[test]
+fn loads_keys_from_keystore() -> Result<()> {
+    let path = "publish_test.keystore";
+    let second = SuiKeypair::from_seed(&[2; 32])?.address();
+    write_keystore(path, &[(1, [5; 32]), (0, [1; 32]), (0, [2; 32])])?;
+
+    let by_sender = load_keypair(path, Some(&second)).map(|key| key.address());
+    let missing = load_keypair(path, Some("0x1234"));
+    // Without a sender, the first key is used, and it is not an Ed25519 key
+    let first = load_keypair(path, None);
+    fs::remove_file(path)?;
+
+    assert_eq!(by_sender?, second);
+    assert!(
+        matches!(missing, Err(TokenGenErrors::InvalidKeystore(message)) if message.contains("No Ed25519 key"))
+    );
+    assert!(
+        matches!(first, Err(ref error @ TokenGenErrors::InvalidKeystore(_)) if error.exit_code() == 2)
+    );
+    assert!(matches!(
+        load_keypair("missing.keystore", None),
+        Err(TokenGenErrors::InvalidKeystore(_))
+    ));
+    Ok(())
+}
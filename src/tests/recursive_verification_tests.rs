@@ -0,0 +1,164 @@
+use std::{fs, path::Path};
+
+use super::common::{setup_mock_client, MockServer, Script};
+use crate::{
+    commands::verify::verify_packages_recursively,
+    errors::TokenGenErrors,
+    utils::{
+        client::responses::RpcResponseErrors,
+        discovery::{discover_packages, VerifyStatus},
+        generation::ContractGenerator,
+        prompts::TokenInfo,
+        templates::generate_contract,
+    },
+    Result,
+};
+
+// Helper function to write a generated package for the named token into the given folder
+fn write_package(folder: &str, name: &str) -> Result<()> {
+    let token = TokenInfo {
+        name: name.to_string(),
+        symbol: "REC".to_string(),
+        decimals: 6,
+        ..TokenInfo::default()
+    };
+    let files = generate_contract(&token)?.into_files(&token.name);
+    ContractGenerator::new(folder.to_string()).write_files(&files)?;
+    Ok(())
+}
+
+// Helper function to write a tree with a verified, a modified and a foreign package
+fn write_tree(root: &str) -> Result<()> {
+    write_package(&format!("{}/coins/alpha", root), "Alpha Coin")?;
+    write_package(&format!("{}/coins/nested/beta", root), "Beta Coin")?;
+    fs::write(
+        format!("{}/coins/nested/beta/sources/extra.move", root),
+        "module BetaCoin::extra {}\n",
+    )?;
+
+    let foreign = format!("{}/legacy/gamma", root);
+    fs::create_dir_all(format!("{}/sources", foreign))?;
+    fs::write(
+        format!("{}/Move.toml", foreign),
+        "[package]\nname = \"gamma\"\n",
+    )?;
+    fs::write(
+        format!("{}/sources/gamma.move", foreign),
+        "module gamma::gamma {}\n",
+    )?;
+    Ok(())
+}
+
+// Test case to verify that packages are found below a folder, skipping hidden folders
+// Folders inside a package are not searched, so its build output is never reported.
+#[test]
+fn discovers_nested_packages() -> Result<()> {
+    let root = "discovery_tree";
+    write_tree(root)?;
+    write_package(&format!("{}/.git/cached", root), "Cached Coin")?;
+    write_package(&format!("{}/coins/alpha/build/inner", root), "Inner Coin")?;
+
+    let packages = discover_packages(Path::new(root));
+    fs::remove_dir_all(root)?;
+
+    let packages: Vec<String> = packages?
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    assert_eq!(
+        packages,
+        vec![
+            "discovery_tree/coins/alpha".to_string(),
+            "discovery_tree/coins/nested/beta".to_string(),
+            "discovery_tree/legacy/gamma".to_string(),
+        ]
+    );
+    Ok(())
+}
+
+// Test case to verify that symbolic links are not followed, so a link cycle cannot recurse forever
+#[cfg(unix)]
+#[test]
+fn discovery_skips_symlink_cycles() -> Result<()> {
+    let root = "discovery_symlinks";
+    write_package(&format!("{}/coins/alpha", root), "Alpha Coin")?;
+    std::os::unix::fs::symlink("..", format!("{}/coins/loop", root))?;
+
+    let packages = discover_packages(Path::new(root));
+    fs::remove_dir_all(root)?;
+
+    let packages: Vec<String> = packages?
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    assert_eq!(packages, vec!["discovery_symlinks/coins/alpha".to_string()]);
+    Ok(())
+}
+
+// Test case to verify that every package is verified and classified in the summary
+#[tokio::test]
+async fn recursive_verification_reports_each_package() -> Result<()> {
+    let root = "recursive_verification_tree";
+    write_tree(root)?;
+
+    let offline = verify_packages_recursively(root, None).await;
+    let client = setup_mock_client(MockServer::new());
+    let online = verify_packages_recursively(root, Some(client.into())).await;
+    fs::remove_dir_all(root)?;
+
+    for result in [offline, online] {
+        let report = match result {
+            Err(TokenGenErrors::PackagesNotVerified(report)) => report,
+            other => panic!("Expected a verification report, got {:?}", other),
+        };
+        let statuses: Vec<_> = report.entries.iter().map(|entry| entry.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                VerifyStatus::Verified,
+                VerifyStatus::Modified,
+                VerifyStatus::NotGenerated
+            ]
+        );
+        assert_eq!(
+            report.entries[1].report.as_ref().map(|r| r.extra.clone()),
+            Some(vec!["sources/extra.move".to_string()])
+        );
+
+        let table = report.to_string();
+        assert!(table.contains("alpha (alphacoin.move)"));
+        assert!(table.contains("(0 missing, 1 extra, 0 modified)"));
+        assert!(table.ends_with("1 verified, 1 modified, 1 not generated, 0 failed"));
+    }
+    Ok(())
+}
+
+// Test case to verify that a tree of unmodified packages verifies, and RPC failures are reported per package
+#[tokio::test]
+async fn recursive_verification_succeeds_and_reports_failures() -> Result<()> {
+    let root = "recursive_success_tree";
+    write_package(&format!("{}/one", root), "One Coin")?;
+    write_package(&format!("{}/two", root), "Two Coin")?;
+
+    let verified = verify_packages_recursively(root, None).await;
+    let client = setup_mock_client(MockServer::new().verify_package(Script::Fail(
+        RpcResponseErrors::GeneralError("service unavailable".to_string()),
+    )));
+    let failed = verify_packages_recursively(root, Some(client.into())).await;
+    let empty = verify_packages_recursively(&format!("{}/one/sources", root), None).await;
+    fs::remove_dir_all(root)?;
+
+    assert!(verified.is_ok());
+    match failed {
+        Err(error @ TokenGenErrors::PackagesNotVerified(_)) => {
+            assert_eq!(error.exit_code(), 4);
+            assert!(error
+                .to_string()
+                .starts_with("Verification failed for 2 of 2 packages"));
+            assert!(error.to_string().contains("service unavailable"));
+        }
+        other => panic!("Expected a verification report, got {:?}", other),
+    }
+    assert!(matches!(empty, Err(TokenGenErrors::InvalidPath(_))));
+    Ok(())
+}
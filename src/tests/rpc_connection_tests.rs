@@ -1,7 +1,20 @@
+use futures::StreamExt;
+use serde_json::Value;
+use std::time::Duration;
 use tarpc::context;
+use tokio::{
+    io::{duplex, AsyncWriteExt},
+    time::timeout,
+};
 
-use super::common::test_utils::setup_test_client;
-use crate::{constants::ADDRESS, errors::TokenGenErrors, Result};
+use super::common::{setup_mock_client, setup_test_client, MockServer};
+use crate::{
+    constants::MAX_RPC_FRAME_BYTES,
+    errors::TokenGenErrors,
+    server::{spawn_server, AccessPolicy},
+    utils::{client::rpc_client::json_transport, helpers::validate_rpc_url},
+    Result,
+};
 
 // Test case to simulate a failed client connection due to an invalid address.
 // This tests the scenario where the address provided for the client setup is incorrect (e.g., wrong port or unavailable address).
@@ -15,7 +28,7 @@ async fn rpc_client_connection_failure() -> Result<()> {
 
     // Assert that the result is an error, and it matches the specific error type
     assert!(result.is_err()); // Ensure that the result is an error
-    assert!(matches!(result, Err(TokenGenErrors::FailedToConnectRpc))); // Ensure that the error is of type InvalidInput
+    assert!(matches!(result, Err(TokenGenErrors::FailedToConnectRpc(_)))); // Ensure that the error is of type InvalidInput
 
     Ok(())
 }
@@ -24,7 +37,7 @@ async fn rpc_client_connection_failure() -> Result<()> {
 // It simulates a failed RPC request (e.g., when trying to verify content that doesn't exist).
 #[tokio::test]
 async fn setup_test_client_error_handling() -> Result<()> {
-    let client = setup_test_client(ADDRESS).await?;
+    let client = setup_mock_client(MockServer::new());
 
     // Test error handling during content verification with invalid input
     let result = client
@@ -46,7 +59,7 @@ async fn setup_test_client_error_handling() -> Result<()> {
 // It tests the scenario where errors occur during RPC calls (like verifying content or URL verification) and ensures that the errors are propagated properly.
 #[tokio::test]
 async fn test_error_propagation() -> Result<()> {
-    let client = setup_test_client(ADDRESS).await?;
+    let client = setup_mock_client(MockServer::new());
 
     // Test RPC error propagation during content verification with invalid input
     let result = client
@@ -66,3 +79,85 @@ async fn test_error_propagation() -> Result<()> {
 
     Ok(())
 }
+
+// Test case to verify that RPC addresses are normalized to `host:port`
+// Hostnames, IPv6 literals and URLs are accepted; addresses without a host or port are rejected.
+#[test]
+fn rpc_url_normalization() {
+    let valid = [
+        ("127.0.0.1:5001", "127.0.0.1:5001"),
+        ("localhost:5001", "localhost:5001"),
+        ("tokengen.internal:5001", "tokengen.internal:5001"),
+        ("[::1]:5001", "[::1]:5001"),
+        ("http://localhost:5001", "localhost:5001"),
+        ("http://[2001:db8::1]:5001/", "[2001:db8::1]:5001"),
+        ("http://tokengen.internal", "tokengen.internal:80"),
+        ("https://tokengen.internal", "tls://tokengen.internal:443"),
+        ("https://localhost:5443/", "tls://localhost:5443"),
+        ("tls://[::1]:5001", "tls://[::1]:5001"),
+    ];
+    for (url, expected) in valid {
+        assert_eq!(
+            validate_rpc_url(url).ok().as_deref(),
+            Some(expected),
+            "{}",
+            url
+        );
+    }
+
+    let invalid = [
+        "localhost",
+        "::1:5001",
+        ":5001",
+        "not a url:5001",
+        "tcp://:5001",
+    ];
+    for url in invalid {
+        assert!(
+            matches!(validate_rpc_url(url), Err(TokenGenErrors::InvalidRpcUrl)),
+            "{}",
+            url
+        );
+    }
+}
+
+// Test case to verify that the client resolves hostnames and tries each resolved address
+// `localhost` may resolve to `::1` before `127.0.0.1`, while the server only listens on IPv4.
+#[tokio::test]
+async fn rpc_client_connects_by_hostname() -> Result<()> {
+    let (address, handle) = spawn_server("127.0.0.1:0", AccessPolicy::default()).await?;
+
+    let rpc_url = validate_rpc_url(&format!("http://localhost:{}", address.port()))?;
+    let client = setup_test_client(&rpc_url).await?;
+    let result = client
+        .verify_address(context::current(), "0x1".to_string(), "devnet".to_string())
+        .await;
+    handle.abort();
+
+    // The reference server answers, even though it does not support address verification
+    assert!(matches!(result, Ok(Err(_))));
+    Ok(())
+}
+
+// Test case to verify that an unresolvable hostname fails to connect
+#[tokio::test]
+async fn rpc_client_unresolvable_hostname() {
+    let result = setup_test_client("tokengen.invalid:5001").await;
+    assert!(matches!(result, Err(TokenGenErrors::FailedToConnectRpc(_))));
+}
+
+// Test case to verify that the transport refuses a frame longer than the limit instead of buffering it
+#[tokio::test]
+async fn transport_rejects_oversized_frames() -> Result<()> {
+    let (local, mut peer) = duplex(64);
+    let mut transport = json_transport::<_, Value, Value>(local);
+
+    // Frames start with their length as a big-endian u32
+    let length = u32::try_from(MAX_RPC_FRAME_BYTES + 1).unwrap_or(u32::MAX);
+    peer.write_all(&length.to_be_bytes()).await?;
+    peer.write_all(b"{").await?;
+
+    let frame = timeout(Duration::from_secs(5), transport.next()).await;
+    assert!(matches!(frame, Ok(Some(Err(_)))), "Frame was buffered");
+    Ok(())
+}
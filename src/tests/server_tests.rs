@@ -0,0 +1,232 @@
+use std::time::Duration;
+use tarpc::context;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time::timeout,
+};
+
+use super::common::test_utils::setup_test_client;
+use crate::{
+    constants::{DEFAULT_ENVIRONMENT, MAX_RPC_FRAME_BYTES},
+    errors::TokenGenErrors,
+    server::{spawn_server, AccessPolicy},
+    utils::client::{
+        responses::{FileRole, RpcResponseErrors},
+        rpc_client::TokenGenClient,
+    },
+    Result,
+};
+
+// Helper function to start the reference server on a random local port and connect a client to it
+async fn start_local_server() -> Result<TokenGenClient> {
+    let (address, _server) = spawn_server("127.0.0.1:0", AccessPolicy::default()).await?;
+    setup_test_client(&address.to_string()).await
+}
+
+// Test case to verify that contracts created by the reference server pass its own verification
+#[tokio::test]
+async fn server_create_and_verify_round_trip() -> Result<()> {
+    let client = start_local_server().await?;
+
+    let response = client
+        .create(
+            context::current(),
+            6,
+            "Server Token".to_string(),
+            "SRV".to_string(),
+            "Served locally".to_string(),
+            true,
+            DEFAULT_ENVIRONMENT.to_string(),
+            None,
+            None,
+        )
+        .await
+        .map_err(TokenGenErrors::RpcError)?
+        .map_err(|e| TokenGenErrors::FailedToCreateTokenContract(e.to_string()))?;
+
+    let contents = |role| {
+        response
+            .file(role)
+            .map(|file| (file.path.clone(), file.contents.clone()))
+            .ok_or_else(|| TokenGenErrors::FailedToCreateTokenContract(format!("no {:?}", role)))
+    };
+    let (token_path, token_content) = contents(FileRole::Module)?;
+    let (toml_path, move_toml) = contents(FileRole::Manifest)?;
+    let (test_path, test_content) = contents(FileRole::Test)?;
+
+    assert_eq!(response.files.len(), 3);
+    assert_eq!(token_path, "sources/servertoken.move");
+    assert_eq!(toml_path, "Move.toml");
+    assert_eq!(test_path, "tests/servertoken.move");
+    assert!(token_content.contains("module ServerToken::servertoken"));
+    assert!(move_toml.contains(r#"name = "servertoken""#));
+    assert!(test_content.contains("module ServerToken::servertoken_tests"));
+
+    // The generated contract verifies, a modified one does not
+    let result = client
+        .verify_content(context::current(), token_content.clone(), move_toml.clone())
+        .await?;
+    assert!(result.is_ok());
+
+    let tampered = token_content.replace("b\"SRV\"", "b\"FAKE\"");
+    let result = client
+        .verify_content(context::current(), tampered, move_toml)
+        .await?;
+    assert!(matches!(
+        result,
+        Err(RpcResponseErrors::VerifyResultError(_))
+    ));
+
+    Ok(())
+}
+
+// Test case to verify that the reference server rejects invalid token parameters
+#[tokio::test]
+async fn server_rejects_invalid_parameters() -> Result<()> {
+    let client = start_local_server().await?;
+
+    let result = client
+        .create(
+            context::current(),
+            255, // Invalid decimals
+            "TestToken".to_string(),
+            "TEST".to_string(),
+            "Description".to_string(),
+            false,
+            DEFAULT_ENVIRONMENT.to_string(),
+            None,
+            None,
+        )
+        .await?;
+    assert!(matches!(result, Err(RpcResponseErrors::InvalidDecimals)));
+
+    let result = client
+        .create(
+            context::current(),
+            6,
+            "".to_string(), // Empty token name
+            "TEST".to_string(),
+            "Description".to_string(),
+            false,
+            DEFAULT_ENVIRONMENT.to_string(),
+            None,
+            None,
+        )
+        .await?;
+    assert!(matches!(result, Err(RpcResponseErrors::InvalidName)));
+
+    let result = client
+        .create(
+            context::current(),
+            6,
+            "TestToken".to_string(),
+            "TOOLONG".to_string(), // Symbol longer than 5 characters
+            "Description".to_string(),
+            false,
+            DEFAULT_ENVIRONMENT.to_string(),
+            None,
+            None,
+        )
+        .await?;
+    assert!(matches!(result, Err(RpcResponseErrors::InvalidSymbol)));
+
+    let result = client
+        .create(
+            context::current(),
+            6,
+            "TestToken".to_string(),
+            "TEST".to_string(),
+            "Description".to_string(),
+            false,
+            "localnet".to_string(), // Unsupported environment
+            None,
+            None,
+        )
+        .await?;
+    assert!(matches!(
+        result,
+        Err(RpcResponseErrors::InvalidEnvironment(_))
+    ));
+
+    let result = client
+        .create(
+            context::current(),
+            6,
+            "TestToken".to_string(),
+            "TEST".to_string(),
+            "Description".to_string(),
+            false,
+            DEFAULT_ENVIRONMENT.to_string(),
+            Some("ftp://example.com/icon.png".to_string()), // Invalid icon URL
+            None,
+        )
+        .await?;
+    assert!(matches!(result, Err(RpcResponseErrors::InvalidUrl(_))));
+
+    let result = client
+        .create(
+            context::current(),
+            9,
+            "TestToken".to_string(),
+            "TEST".to_string(),
+            "Description".to_string(),
+            false,
+            DEFAULT_ENVIRONMENT.to_string(),
+            None,
+            Some(u64::MAX), // Max supply exceeding the largest coin balance
+        )
+        .await?;
+    assert!(matches!(
+        result,
+        Err(RpcResponseErrors::InvalidMaxSupply(_))
+    ));
+
+    // Invalid URLs are rejected before cloning
+    let result = client
+        .verify_url(context::current(), "https://invalid-url".to_string())
+        .await?;
+    assert!(matches!(result, Err(RpcResponseErrors::InvalidUrl(_))));
+
+    // Local repositories and hosts outside the allowlist are never cloned
+    for url in [
+        "FILE:///etc/coins",
+        "file:/etc/coins",
+        "http://169.254.169.254/latest/meta-data",
+        "https://github.com@internal.example/osec/token",
+        "git@internal.example:osec/token.git",
+    ] {
+        let result = client
+            .verify_url(context::current(), url.to_string())
+            .await?;
+        assert!(
+            matches!(result, Err(RpcResponseErrors::InvalidUrl(_))),
+            "Accepted {}: {:?}",
+            url,
+            result
+        );
+    }
+
+    Ok(())
+}
+
+// Test case to verify that a peer announcing an oversized frame is disconnected instead of buffered
+#[tokio::test]
+async fn server_rejects_oversized_frames() -> Result<()> {
+    let (address, _server) = spawn_server("127.0.0.1:0", AccessPolicy::default()).await?;
+    let mut stream = TcpStream::connect(address).await?;
+
+    // Frames start with their length as a big-endian u32
+    let length = u32::try_from(MAX_RPC_FRAME_BYTES + 1).unwrap_or(u32::MAX);
+    stream.write_all(&length.to_be_bytes()).await?;
+    stream.write_all(b"{").await?;
+
+    let mut buffer = [0; 1];
+    let read = timeout(Duration::from_secs(5), stream.read(&mut buffer)).await;
+    assert!(
+        matches!(read, Ok(Ok(0)) | Ok(Err(_))),
+        "The connection was kept open: {:?}",
+        read
+    );
+    Ok(())
+}
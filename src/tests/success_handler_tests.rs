@@ -15,11 +15,16 @@ fn test_token_creation_success_message() {
         description: "A test token for database storage".to_string(), // Token description
         is_frozen: false, // Indicates whether the token is frozen (not transferable)
         environment: "mainnet".to_string(), // The environment for the token (e.g., mainnet, testnet)
+        icon_url: None,
+        max_supply: None,
     };
 
     // Create a success message for token creation using the TokenInfo object
-    let success_type =
-        SuccessType::TokenCreated(token_info, "Contract has been generated!".to_string());
+    let success_type = SuccessType::TokenCreated(
+        token_info,
+        "Contract has been generated!".to_string(),
+        vec![],
+    );
 
     // Call handle_success function to display the success message for token creation
     handle_success(success_type);
@@ -35,9 +40,10 @@ fn test_token_verification_success_message_path() {
     let success_type = SuccessType::TokenVerified {
         path: Some("./test_token".to_string()), // Token verification path
         url: None,                              // URL is not provided
-        address: None,                          // Address is not provided
-        environment: None,                      // Environment is not provided
         file_name: None,                        // Message is not provided
+        environment: Some("devnet".to_string()), // Environment of the package
+        commit: None,                           // Commit is not provided
+        link: None,                             // Link is not provided
     };
 
     // Call handle_success function to display the success message for token verification with path
@@ -51,9 +57,10 @@ fn test_token_verification_success_message_url() {
     let success_type = SuccessType::TokenVerified {
         path: None,                                         // Path is not provided
         url: Some("https://example.com/token".to_string()), // Token verification URL
-        address: None,                                      // Address is not provided
-        environment: None,                                  // Environment is not provided
         file_name: None,                                    // Message is not provided
+        environment: None,                                  // Environment is not known
+        commit: None,                                       // Commit is not provided
+        link: None,                                         // Link is not provided
     };
 
     // Call handle_success function to display the success message for token verification with URL
@@ -71,11 +78,16 @@ fn test_token_info_parameter_capture() {
         description: "Testing parameter capture for database storage".to_string(), // Token description
         is_frozen: true, // Indicates that the token is frozen
         environment: DEFAULT_ENVIRONMENT.to_string(), // The environment for the token (e.g., devnet)
+        icon_url: None,
+        max_supply: None,
     };
 
     // Create a success message for token creation using the TokenInfo object
-    let success_type =
-        SuccessType::TokenCreated(token_info, "Contract has been generated!".to_string());
+    let success_type = SuccessType::TokenCreated(
+        token_info,
+        "Contract has been generated!".to_string(),
+        vec![],
+    );
 
     // Call handle_success function to display the success message for token creation
     handle_success(success_type);
@@ -0,0 +1,148 @@
+use std::{env, fs};
+
+use crate::{
+    constants::DEFAULT_ENVIRONMENT,
+    errors::TokenGenErrors,
+    utils::{
+        prompts::TokenInfo,
+        templates::generate_contract,
+        verify_helper::{parse_token_header, verify_content_locally},
+    },
+    Result,
+};
+
+// Token parameters matching the fixtures in `src/tests/tokens`, which were produced by the RPC service.
+fn fixture_token() -> TokenInfo {
+    TokenInfo {
+        decimals: 8,
+        symbol: "MT".to_string(),
+        name: "My token".to_string(),
+        description: "Tetsing".to_string(),
+        is_frozen: true,
+        environment: DEFAULT_ENVIRONMENT.to_string(),
+        icon_url: None,
+        max_supply: None,
+    }
+}
+
+// Test case to verify that offline generation is byte-identical to the RPC `create` output
+#[test]
+fn offline_generation_matches_rpc_output() -> Result<()> {
+    let current_dir = env::current_dir().expect("Failed to get current directory");
+    let token_path = format!(
+        "{}/src/tests/tokens/valid_token.move",
+        current_dir.display()
+    );
+    let toml_path = format!("{}/src/tests/tokens/valid_toml.toml", current_dir.display());
+
+    let expected_token = fs::read_to_string(token_path).expect("Failed to read valid token file");
+    let expected_toml = fs::read_to_string(toml_path).expect("Failed to read valid toml file");
+
+    // Render the package from the embedded templates
+    let generated = generate_contract(&fixture_token())?;
+
+    assert_eq!(generated.token, expected_token);
+    assert_eq!(generated.toml, expected_toml);
+    Ok(())
+}
+
+// Test case to verify that the identifiers in the test module and metadata handling follow the token settings
+#[test]
+fn offline_generation_uses_token_settings() -> Result<()> {
+    let token = TokenInfo {
+        is_frozen: false,
+        environment: "testnet".to_string(),
+        ..fixture_token()
+    };
+
+    let generated = generate_contract(&token)?;
+
+    // Unfrozen metadata is shared instead of frozen
+    assert!(generated
+        .token
+        .contains("transfer::public_share_object(metadata);"));
+    assert!(!generated.token.contains("public_freeze_object"));
+
+    // The environment selects the framework revision
+    assert!(generated.toml.contains(r#"rev = "framework/testnet""#));
+
+    // The test module targets the generated coin module
+    assert!(generated.test.contains("module Mytoken::mytoken_tests"));
+    assert!(generated
+        .test
+        .contains("use Mytoken::mytoken::{Self, MYTOKEN};"));
+    Ok(())
+}
+
+// Test case to verify that an icon URL is recorded in the header and set in the coin metadata
+#[test]
+fn offline_generation_sets_icon_url() -> Result<()> {
+    let token = TokenInfo {
+        icon_url: Some("https://example.com/icon.png".to_string()),
+        ..fixture_token()
+    };
+
+    let generated = generate_contract(&token)?;
+
+    assert!(generated
+        .token
+        .contains("/// icon_url: https://example.com/icon.png"));
+    assert!(generated.token.contains(
+        r#"option::some(sui::url::new_unsafe_from_bytes(b"https://example.com/icon.png"))"#
+    ));
+
+    // The header parses back to the same token, and the generated package verifies locally
+    assert_eq!(
+        parse_token_header(&generated.token).and_then(|parsed| parsed.icon_url),
+        token.icon_url
+    );
+    assert!(verify_content_locally(&generated.token, &generated.toml).is_ok());
+
+    // Without an icon, the metadata has none
+    let generated = generate_contract(&fixture_token())?;
+    assert!(!generated.token.contains("icon_url"));
+    assert!(generated.token.contains("option::none()"));
+    Ok(())
+}
+
+// Test case to verify that a max supply is minted once in base units and minting is disabled
+#[test]
+fn offline_generation_fixes_max_supply() -> Result<()> {
+    let token = TokenInfo {
+        max_supply: Some(21_000_000),
+        ..fixture_token()
+    };
+
+    let generated = generate_contract(&token)?;
+
+    assert!(generated.token.contains("/// max_supply: 21000000\n"));
+    assert!(generated
+        .token
+        .contains("coin::mint_and_transfer(&mut treasury, 2100000000000000, ctx.sender(), ctx);"));
+    assert!(generated
+        .token
+        .contains("transfer::public_freeze_object(treasury);"));
+    assert!(!generated.token.contains("public fun mint("));
+    assert!(generated.token.contains("    use sui::coin;\n"));
+    assert!(generated
+        .test
+        .contains("assert!(coin.value() == 2100000000000000, 0);"));
+
+    // The header parses back to the same supply, and the generated package verifies locally
+    assert_eq!(
+        parse_token_header(&generated.token).and_then(|parsed| parsed.max_supply),
+        token.max_supply
+    );
+    assert!(verify_content_locally(&generated.token, &generated.toml).is_ok());
+
+    // Supplies that do not fit in a coin balance are rejected
+    let token = TokenInfo {
+        max_supply: Some(u64::MAX),
+        ..fixture_token()
+    };
+    assert!(matches!(
+        generate_contract(&token),
+        Err(TokenGenErrors::InvalidInput(_))
+    ));
+    Ok(())
+}
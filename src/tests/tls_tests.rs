@@ -0,0 +1,212 @@
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, ExtendedKeyUsagePurpose, IsCa, KeyPair,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tarpc::context;
+
+use crate::{
+    constants::TLS_SCHEME,
+    errors::TokenGenErrors,
+    server::{spawn_tls_server, AccessPolicy},
+    utils::{
+        client::rpc_client::{initiate_tls_client, TokenGenClient},
+        helpers::validate_rpc_url,
+        tls::{server_acceptor, TlsOptions},
+    },
+    Result,
+};
+
+// A certificate authority that signs the test certificates
+struct TestCa {
+    cert: Certificate,
+    key: KeyPair,
+}
+
+impl TestCa {
+    fn new() -> Self {
+        let key = KeyPair::generate().expect("Failed to generate CA key");
+        let mut params = CertificateParams::new(Vec::<String>::new()).expect("Invalid CA params");
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let cert = params.self_signed(&key).expect("Failed to sign CA");
+        Self { cert, key }
+    }
+
+    // Issues a certificate for the given names and usage, returning the certificate and key PEMs
+    fn issue(&self, names: &[&str], usage: ExtendedKeyUsagePurpose) -> (String, String) {
+        let key = KeyPair::generate().expect("Failed to generate key");
+        let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+        let mut params = CertificateParams::new(names).expect("Invalid certificate params");
+        params.extended_key_usages = vec![usage];
+        let cert = params
+            .signed_by(&key, &self.cert, &self.key)
+            .expect("Failed to sign certificate");
+        (cert.pem(), key.serialize_pem())
+    }
+}
+
+// Helper function to write PEM files into a fresh directory for a test
+fn write_pems(test: &str, files: &[(&str, &str)]) -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("sui-token-gen-{}-{}", test, std::process::id()));
+    fs::create_dir_all(&dir)?;
+    for (name, content) in files {
+        fs::write(dir.join(name), content)?;
+    }
+    Ok(dir)
+}
+
+// Helper function to return a PEM path as a string
+fn pem(dir: &Path, name: &str) -> String {
+    dir.join(name).display().to_string()
+}
+
+// Helper function to start a TLS server on a random local port, returning its `tls://` address
+async fn start_tls_server(dir: &Path, client_ca: Option<&str>) -> Result<String> {
+    let acceptor = server_acceptor(&pem(dir, "server.pem"), &pem(dir, "server.key"), client_ca)?;
+    let (address, _handle) =
+        spawn_tls_server("127.0.0.1:0", acceptor, AccessPolicy::default()).await?;
+    validate_rpc_url(&format!("{}localhost:{}", TLS_SCHEME, address.port()))
+}
+
+// Helper function to connect over TLS and make a request, as the CLI does for `tls://` addresses
+async fn request(rpc_url: &str, tls: &TlsOptions) -> Result<()> {
+    let address = rpc_url.strip_prefix(TLS_SCHEME).unwrap_or(rpc_url);
+    let client: TokenGenClient = initiate_tls_client(address, tls.connector()?)
+        .await
+        .map_err(TokenGenErrors::FailedToConnectRpc)?;
+
+    // The reference server does not support address verification, so any response is an error
+    let response = client
+        .verify_address(context::current(), "0x1".to_string(), "devnet".to_string())
+        .await?;
+    assert!(response.is_err());
+    Ok(())
+}
+
+// Test case to verify a request over TLS with a custom CA bundle
+#[tokio::test]
+async fn tls_request_with_custom_ca() -> Result<()> {
+    let ca = TestCa::new();
+    let (server_cert, server_key) = ca.issue(&["localhost"], ExtendedKeyUsagePurpose::ServerAuth);
+    let dir = write_pems(
+        "tls-custom-ca",
+        &[
+            ("ca.pem", &ca.cert.pem()),
+            ("server.pem", &server_cert),
+            ("server.key", &server_key),
+        ],
+    )?;
+
+    let rpc_url = start_tls_server(&dir, None).await?;
+    let result = request(
+        &rpc_url,
+        &TlsOptions {
+            ca_cert: Some(pem(&dir, "ca.pem")),
+            ..TlsOptions::default()
+        },
+    )
+    .await;
+    fs::remove_dir_all(&dir)?;
+
+    assert!(rpc_url.starts_with("tls://localhost:"));
+    assert!(result.is_ok(), "TLS request failed: {:?}", result);
+    Ok(())
+}
+
+// Test case to verify that a server certificate from an untrusted CA is rejected
+#[tokio::test]
+async fn tls_rejects_untrusted_server() -> Result<()> {
+    let ca = TestCa::new();
+    let other_ca = TestCa::new();
+    let (server_cert, server_key) = ca.issue(&["localhost"], ExtendedKeyUsagePurpose::ServerAuth);
+    let dir = write_pems(
+        "tls-untrusted",
+        &[
+            ("other-ca.pem", &other_ca.cert.pem()),
+            ("server.pem", &server_cert),
+            ("server.key", &server_key),
+        ],
+    )?;
+
+    let rpc_url = start_tls_server(&dir, None).await?;
+    let result = request(
+        &rpc_url,
+        &TlsOptions {
+            ca_cert: Some(pem(&dir, "other-ca.pem")),
+            ..TlsOptions::default()
+        },
+    )
+    .await;
+    fs::remove_dir_all(&dir)?;
+
+    assert!(matches!(result, Err(TokenGenErrors::FailedToConnectRpc(_))));
+    Ok(())
+}
+
+// Test case to verify client certificate authentication
+#[tokio::test]
+async fn tls_client_certificate() -> Result<()> {
+    let ca = TestCa::new();
+    let (server_cert, server_key) = ca.issue(&["localhost"], ExtendedKeyUsagePurpose::ServerAuth);
+    let (client_cert, client_key) = ca.issue(&["client"], ExtendedKeyUsagePurpose::ClientAuth);
+    let dir = write_pems(
+        "tls-client-cert",
+        &[
+            ("ca.pem", &ca.cert.pem()),
+            ("server.pem", &server_cert),
+            ("server.key", &server_key),
+            ("client.pem", &client_cert),
+            ("client.key", &client_key),
+        ],
+    )?;
+
+    let rpc_url = start_tls_server(&dir, Some(&pem(&dir, "ca.pem"))).await?;
+    let with_cert = request(
+        &rpc_url,
+        &TlsOptions {
+            ca_cert: Some(pem(&dir, "ca.pem")),
+            client_cert: Some(pem(&dir, "client.pem")),
+            client_key: Some(pem(&dir, "client.key")),
+        },
+    )
+    .await;
+    let without_cert = request(
+        &rpc_url,
+        &TlsOptions {
+            ca_cert: Some(pem(&dir, "ca.pem")),
+            ..TlsOptions::default()
+        },
+    )
+    .await;
+    fs::remove_dir_all(&dir)?;
+
+    assert!(with_cert.is_ok(), "TLS request failed: {:?}", with_cert);
+
+    // Depending on the TLS version, the server rejects the client during the handshake or on the first request
+    assert!(without_cert.is_err());
+    Ok(())
+}
+
+// Test case to verify that invalid TLS settings are reported before connecting
+#[test]
+fn invalid_tls_config() {
+    let missing = TlsOptions {
+        ca_cert: Some("/path/does/not/exist.pem".to_string()),
+        ..TlsOptions::default()
+    };
+    assert!(matches!(
+        missing.connector(),
+        Err(TokenGenErrors::InvalidTlsConfig(ref msg)) if msg.starts_with("/path/does/not/exist.pem: ")
+    ));
+
+    let cert_without_key = TlsOptions {
+        client_cert: Some("client.pem".to_string()),
+        ..TlsOptions::default()
+    };
+    assert!(matches!(
+        cert_without_key.connector(),
+        Err(TokenGenErrors::InvalidTlsConfig(_))
+    ));
+}
@@ -3,28 +3,36 @@ use tarpc::context;
 
 use crate::{
     commands::verify::{verify_token_address, verify_token_using_url},
-    constants::{ADDRESS, DEFAULT_ENVIRONMENT, SUB_FOLDER},
+    constants::{DEFAULT_ENVIRONMENT, SUB_FOLDER},
     errors::TokenGenErrors,
-    tests::common::setup_test_client,
+    tests::common::{
+        setup_mock_client, setup_package_repository, FixedCompiler, FixtureFullnode, MockServer,
+        Script,
+    },
     utils::{
-        client::{responses::RpcResponseErrors, rpc_client::TokenGenClient},
+        client::{
+            responses::{FileRole, GeneratedFile, RpcResponseErrors},
+            rpc_client::TokenGenClient,
+        },
+        fullnode::{CoinMetadata, FullnodeClient},
         generation::ContractGenerator,
+        git::GitSource,
         helpers::sanitize_name,
     },
     Result,
 };
 
 // Test function to initiate the RPC client for testing purposes
-// It simply calls the setup_test_client function with the predefined ADDRESS constant.
-async fn test_initiate_client() -> Result<TokenGenClient> {
-    setup_test_client(ADDRESS).await
+// It connects to an in-process mock server scripted by the calling test.
+fn test_initiate_client(server: MockServer) -> TokenGenClient {
+    setup_mock_client(server)
 }
 
 #[tokio::test]
 async fn create_command() -> Result<()> {
     // Test user inputs for creating a token contract
     let decimals: u8 = 6;
-    let symbol: String = "SAMPLE".to_string();
+    let symbol: String = "SMPL".to_string(); // The server enforces the 5-character symbol limit
     let name: &str = "sampletoken";
     let description: String = "This is a sample token for testing.".to_string();
     let is_frozen: bool = false;
@@ -34,7 +42,7 @@ async fn create_command() -> Result<()> {
     let base_folder = sanitize_name(name);
 
     // Initialize the RPC client
-    let client: TokenGenClient = test_initiate_client().await?;
+    let client: TokenGenClient = test_initiate_client(MockServer::new());
 
     // If the test base folder already exists, delete it
     if Path::new(&base_folder).exists() {
@@ -42,7 +50,7 @@ async fn create_command() -> Result<()> {
     }
 
     // Create token contract by calling the `create` method on the RPC client
-    let (token_content, move_toml, _test_token_content) = client
+    let response = client
         .create(
             context::current(),
             decimals,
@@ -51,6 +59,8 @@ async fn create_command() -> Result<()> {
             description.to_owned(),
             is_frozen,
             environment,
+            None,
+            None,
         )
         .await
         .map_err(TokenGenErrors::RpcError)?
@@ -59,14 +69,8 @@ async fn create_command() -> Result<()> {
     // Create the base folder for the token contract
     let contract_generator = ContractGenerator::new(base_folder.to_string());
 
-    // Create the base folder for the token contract
-    contract_generator.create_base_folder()?;
-
-    // Generate the Move.toml file for the contract
-    contract_generator.create_move_toml(&move_toml)?;
-
-    // Generate the actual contract file for the token
-    contract_generator.create_contract_file(name, &token_content, SUB_FOLDER)?;
+    // Write the Move.toml file, the contract and its tests
+    contract_generator.write_files(&response.files)?;
 
     // Validate folder and file creation
     let sources_folder = format!("{}/{}", base_folder, SUB_FOLDER);
@@ -120,6 +124,71 @@ async fn create_command() -> Result<()> {
     Ok(())
 }
 
+// Test case to verify that the generator writes whatever files it receives
+// The layout comes from the response, so extra modules, docs and scripts are written too.
+#[test]
+fn generator_writes_received_layout() -> Result<()> {
+    let base_folder = "generator_layout_token";
+    let file = |path: &str, role| GeneratedFile {
+        path: path.to_string(),
+        contents: format!("// {}", path),
+        role,
+    };
+    let files = vec![
+        file("Move.toml", FileRole::Manifest),
+        file("sources/token.move", FileRole::Module),
+        file("sources/vesting.move", FileRole::Module),
+        file("README.md", FileRole::Docs),
+        file("scripts/deploy/publish.sh", FileRole::Script),
+    ];
+
+    let written = ContractGenerator::new(base_folder.to_string()).write_files(&files);
+    let contents = fs::read_to_string(format!("{}/scripts/deploy/publish.sh", base_folder));
+    fs::remove_dir_all(base_folder)?;
+
+    assert_eq!(written?.len(), files.len());
+    assert_eq!(contents?, "// scripts/deploy/publish.sh");
+    Ok(())
+}
+
+// Test case to verify that files outside the package folder are rejected before anything is written
+#[test]
+fn generator_rejects_unsafe_paths() {
+    let base_folder = "generator_unsafe_token";
+    for path in [
+        "../escape.move",
+        "/tmp/escape.move",
+        "sources/../../escape.move",
+        "",
+    ] {
+        let files = vec![
+            GeneratedFile {
+                path: "Move.toml".to_string(),
+                contents: String::new(),
+                role: FileRole::Manifest,
+            },
+            GeneratedFile {
+                path: path.to_string(),
+                contents: String::new(),
+                role: FileRole::Module,
+            },
+        ];
+        let result = ContractGenerator::new(base_folder.to_string()).write_files(&files);
+        assert!(
+            matches!(result, Err(TokenGenErrors::FailedToCreateTokenContract(_))),
+            "{}",
+            path
+        );
+        assert!(!Path::new(base_folder).exists(), "{}", path);
+    }
+
+    let result = ContractGenerator::new(base_folder.to_string()).write_files(&[]);
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::FailedToCreateTokenContract(_))
+    ));
+}
+
 // Test case for verifying a valid token file
 #[tokio::test]
 async fn verify_command_valid_file() -> Result<()> {
@@ -133,7 +202,7 @@ async fn verify_command_valid_file() -> Result<()> {
     let toml_path = format!("{}/src/tests/tokens/valid_toml.toml", current_dir.display());
 
     // Initialize the RPC client
-    let client: TokenGenClient = test_initiate_client().await?;
+    let client: TokenGenClient = test_initiate_client(MockServer::new());
 
     // Read content from the existing valid token file
     let valid_content =
@@ -171,7 +240,7 @@ async fn verify_command_invalid_file() -> Result<()> {
     );
 
     // Initialize the RPC client
-    let client: TokenGenClient = test_initiate_client().await?;
+    let client: TokenGenClient = test_initiate_client(MockServer::new());
 
     // Read content from the existing invalid token file
     let invalid_content =
@@ -193,64 +262,95 @@ async fn verify_command_invalid_file() -> Result<()> {
     Ok(())
 }
 
-// Test case for verifying a valid GitHub URL
+// Test case for verifying a repository holding an unmodified generated package
 #[tokio::test]
-async fn verify_command_valid_github() -> Result<()> {
-    // Testing repo URL
-    let valid_url = "https://github.com/meumar-osec/test-sui-token";
+async fn verify_command_valid_repository() -> Result<()> {
+    let url = setup_package_repository("verify_valid_repository", ".", "Git Token")?;
 
     // Initialize the RPC client
-    let client: TokenGenClient = test_initiate_client().await?;
+    let client: TokenGenClient = test_initiate_client(MockServer::new());
 
-    // Call verify_token with the valid GitHub URL
-    let response = verify_token_using_url(valid_url, client).await;
-    assert!(response.is_ok(), "Failed to verify URL");
+    // Call verify_token with the local repository URL
+    let response = verify_token_using_url(&GitSource::new(&url), Some(client.into())).await;
+    fs::remove_dir_all("verify_valid_repository")?;
+    assert!(
+        response.is_ok(),
+        "Failed to verify URL: {:?}",
+        response.err()
+    );
     Ok(())
 }
 
-// Test case for verifying an invalid GitHub URL
+// Test case for verifying a repository the RPC service reports as modified
 #[tokio::test]
-async fn verify_command_invalid_github() -> Result<()> {
-    let invalid_url = "https://github.com/meumar-osec/sui-token1";
+async fn verify_command_modified_repository() -> Result<()> {
+    let url = setup_package_repository("verify_modified_repository", ".", "Git Token")?;
 
     // Initialize the RPC client
-    let client: TokenGenClient = test_initiate_client().await?;
+    let client: TokenGenClient = test_initiate_client(
+        MockServer::new().verify_package(Script::Fail(RpcResponseErrors::ProgramModified)),
+    );
 
-    // Call verify_token with the invalid GitHub URL
-    let response = verify_token_using_url(invalid_url, client).await;
-    assert!(response.is_err(), "Failed to verify URL");
+    // Call verify_token with the local repository URL
+    let response = verify_token_using_url(&GitSource::new(&url), Some(client.into())).await;
+    fs::remove_dir_all("verify_modified_repository")?;
+    assert!(
+        matches!(response, Err(TokenGenErrors::ContractModified(_))),
+        "Expected a modified contract, got {:?}",
+        response
+    );
     Ok(())
 }
 
-// Test case for verifying a valid GitLab URL
+// Test case for verifying a package nested in a folder of the repository
 #[tokio::test]
-async fn verify_command_valid_gitlab() -> Result<()> {
-    // Testing repo URL
-    let valid_url = "https://gitlab.com/osec/test-sui-token";
+async fn verify_command_repository_subdir() -> Result<()> {
+    let url = setup_package_repository("verify_subdir_repository", "coins/git", "Git Token")?;
 
     // Initialize the RPC client
-    let client: TokenGenClient = test_initiate_client().await?;
+    let client: TokenGenClient = test_initiate_client(MockServer::new());
 
-    // Call verify_token with the valid GitLab URL
-    let response = verify_token_using_url(valid_url, client).await;
-    assert!(response.is_ok(), "Failed to verify URL");
+    // The repository root is not a package
+    let response = verify_token_using_url(&GitSource::new(&url), Some(client.clone().into())).await;
+    assert!(
+        response.is_err(),
+        "Verified a repository without a root package"
+    );
+
+    let source = GitSource {
+        subdir: Some("coins/git".to_string()),
+        ..GitSource::new(&url)
+    };
+    let response = verify_token_using_url(&source, Some(client.into())).await;
+    fs::remove_dir_all("verify_subdir_repository")?;
+    assert!(
+        response.is_ok(),
+        "Failed to verify URL: {:?}",
+        response.err()
+    );
     Ok(())
 }
 
 // Test case to verify correct handling of invalid token addresses
 #[tokio::test]
 async fn verify_token_address_invalid_cases() -> Result<()> {
-    let client = setup_test_client(ADDRESS).await?;
+    let fullnode = FullnodeClient::new(&FixtureFullnode::new().start().await?)?;
+    let compiler = FixedCompiler(vec![]);
 
     // Test with an empty address
     let empty_address = "";
-    let result = verify_token_address(empty_address, "testnet", client.to_owned()).await;
-    assert!(result.is_err()); // Expecting an error due to empty address
+    let result = verify_token_address(empty_address, "testnet", &fullnode, &compiler).await;
+    assert!(matches!(result, Err(TokenGenErrors::InvalidInput(_)))); // Expecting an error due to empty address
 
     // Test with an invalid address format
     let invalid_address = "invalid_token_address";
-    let result = verify_token_address(invalid_address, "testnet", client.to_owned()).await;
-    assert!(result.is_err()); // Expecting an error due to incorrect address format
+    let result = verify_token_address(invalid_address, "testnet", &fullnode, &compiler).await;
+    assert!(matches!(result, Err(TokenGenErrors::InvalidInput(_)))); // Expecting an error due to incorrect address format
+
+    // Test with an address that holds no package
+    let missing_address = "0x1234";
+    let result = verify_token_address(missing_address, "testnet", &fullnode, &compiler).await;
+    assert!(matches!(result, Err(TokenGenErrors::VerificationError(_))));
 
     Ok(())
 }
@@ -258,14 +358,47 @@ async fn verify_token_address_invalid_cases() -> Result<()> {
 // Test case to verify valid token addresses
 #[tokio::test]
 async fn verify_token_address_successful_case() -> Result<()> {
-    let client = setup_test_client(ADDRESS).await?;
-
-    // Assume this is a valid token address (mocked in test setup)
     let valid_address = "0xd808a18c3b508f6d80f7bd21fbc0faa20d5f69fab237cf073df29cfff199a440";
-    let result = verify_token_address(valid_address, "devnet", client.to_owned()).await;
+    let metadata = CoinMetadata {
+        id: Some("0x5".to_string()),
+        decimals: 6,
+        name: "Address Token".to_string(),
+        symbol: "ADDR".to_string(),
+        description: String::new(),
+        icon_url: None,
+    };
+
+    // The published module only differs from the compiled one by its package ID
+    let compiled = [b"module".as_slice(), &[0; 32]].concat();
+    let published = [b"module".as_slice(), &normalize_package_id(valid_address)].concat();
+    let url = FixtureFullnode::new()
+        .with_coin(valid_address, "addresstoken", &published, &metadata, false)
+        .start()
+        .await?;
+
+    let fullnode = FullnodeClient::new(&url)?;
+    let result = verify_token_address(
+        valid_address,
+        "devnet",
+        &fullnode,
+        &FixedCompiler(vec![compiled]),
+    )
+    .await;
 
     // Expecting the verification to pass for a valid token address
-    assert!(result.is_ok());
+    assert!(
+        result.is_ok(),
+        "Failed to verify address: {:?}",
+        result.err()
+    );
 
     Ok(())
 }
+
+// Helper function to decode the 32 bytes of a full package ID
+fn normalize_package_id(package_id: &str) -> Vec<u8> {
+    (2..package_id.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&package_id[i..i + 2], 16).expect("Invalid package ID"))
+        .collect()
+}
@@ -1,27 +1,40 @@
-use super::common::test_utils::setup_test_client;
-use crate::{commands::verify::verify_token_using_url, constants::ADDRESS, Result};
+use super::common::{setup_mock_client, MockServer};
+use crate::{
+    commands::verify::verify_token_using_url,
+    constants::DEFAULT_ENVIRONMENT,
+    errors::TokenGenErrors,
+    utils::{
+        git::GitSource,
+        prompts::get_user_prompt,
+        validation::{
+            validate_decimals, validate_description, validate_environment, validate_icon_url,
+            validate_max_supply, validate_name, validate_symbol,
+        },
+    },
+    CreateTokenParams, Result,
+};
 use tarpc::context;
 
 // Test case to verify URL validation errors when verifying token via URL
-// It checks that only valid GitHub URLs are accepted, and tests non-GitHub, malformed, and non-existent URLs.
+// It checks that unsupported schemes and malformed URLs are rejected, and that missing repositories fail to clone.
 #[tokio::test]
 async fn test_url_validation_errors() -> Result<()> {
-    let client = setup_test_client(ADDRESS).await?;
+    let client = setup_mock_client(MockServer::new());
 
-    // Test non-GitHub URL
-    let non_github_url = "https://gitlab.com/some/repo";
-    let result = verify_token_using_url(non_github_url, client.to_owned()).await;
-    assert!(result.is_err());
+    // Test unsupported scheme
+    let ftp_url = GitSource::new("ftp://example.com/some/repo");
+    let result = verify_token_using_url(&ftp_url, Some(client.clone().into())).await;
+    assert!(matches!(result, Err(TokenGenErrors::InvalidGitUrl)));
 
     // Test malformed URL
-    let malformed_url = "not-a-url";
-    let result = verify_token_using_url(malformed_url, client.to_owned()).await;
-    assert!(result.is_err());
+    let malformed_url = GitSource::new("not-a-url");
+    let result = verify_token_using_url(&malformed_url, Some(client.clone().into())).await;
+    assert!(matches!(result, Err(TokenGenErrors::InvalidGitUrl)));
 
-    // Test non-existent GitHub URL
-    let invalid_url = "https://github.com/invalid/repo";
-    let result = verify_token_using_url(invalid_url, client).await;
-    assert!(result.is_err());
+    // Test non-existent repository
+    let invalid_url = GitSource::new("file:///path/does/not/exist");
+    let result = verify_token_using_url(&invalid_url, Some(client.into())).await;
+    assert!(matches!(result, Err(TokenGenErrors::GitError(_))));
 
     Ok(())
 }
@@ -30,7 +43,7 @@ async fn test_url_validation_errors() -> Result<()> {
 // It tests scenarios where a non-existent path or a path that is not a directory is provided for verification.
 #[tokio::test]
 async fn test_path_validation_errors() -> Result<()> {
-    let client = setup_test_client(ADDRESS).await?;
+    let client = setup_mock_client(MockServer::new());
 
     // Test non-existent path
     let non_existent_path = "/path/does/not/exist";
@@ -52,3 +65,163 @@ async fn test_path_validation_errors() -> Result<()> {
 
     Ok(())
 }
+
+// Helper function to build `create` parameters for non-interactive mode
+fn non_interactive_params() -> CreateTokenParams {
+    CreateTokenParams {
+        rpc: None,
+        name: None,
+        symbol: None,
+        decimals: None,
+        description: None,
+        icon_url: None,
+        max_supply: None,
+        is_frozen: None,
+        environment: None,
+        offline: false,
+        non_interactive: true,
+        force: false,
+        manifest: None,
+        continue_on_error: false,
+    }
+}
+
+// Test case to verify that non-interactive mode reports every missing required value at once
+#[test]
+fn test_non_interactive_missing_values() {
+    let result = get_user_prompt(&non_interactive_params());
+    match result {
+        Err(TokenGenErrors::InvalidInput(msg)) => assert_eq!(
+            msg,
+            "Missing required values for non-interactive mode: --name, --symbol, --decimals"
+        ),
+        other => panic!("Expected missing values error, but got {:?}", other),
+    }
+
+    // Only the values still missing are reported
+    let result = get_user_prompt(&CreateTokenParams {
+        name: Some("Batch Token".to_string()),
+        ..non_interactive_params()
+    });
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::InvalidInput(ref msg)) if msg.ends_with(": --symbol, --decimals")
+    ));
+}
+
+// Test case to verify that optional values fall back to their defaults in non-interactive mode
+#[test]
+fn test_non_interactive_defaults() -> Result<()> {
+    let token = get_user_prompt(&CreateTokenParams {
+        name: Some("Batch Token".to_string()),
+        symbol: Some("BAT".to_string()),
+        decimals: Some(9),
+        ..non_interactive_params()
+    })?;
+
+    assert_eq!(token.name, "Batch Token");
+    assert_eq!(token.symbol, "BAT");
+    assert_eq!(token.decimals, 9);
+    assert_eq!(token.description, "");
+    assert!(!token.is_frozen);
+    assert_eq!(token.environment, DEFAULT_ENVIRONMENT);
+    assert_eq!(token.max_supply, None);
+
+    // A max supply is checked against the decimals
+    let result = get_user_prompt(&CreateTokenParams {
+        name: Some("Batch Token".to_string()),
+        symbol: Some("BAT".to_string()),
+        decimals: Some(18),
+        max_supply: Some(1_000_000),
+        ..non_interactive_params()
+    });
+    assert!(matches!(
+        result,
+        Err(TokenGenErrors::InvalidInput(ref msg)) if msg.contains("exceeds the largest coin balance")
+    ));
+    Ok(())
+}
+
+// Test case to verify the shared validation rules and their messages
+#[test]
+fn test_shared_validation_rules() {
+    assert!(validate_name("My Token 2").is_ok());
+    assert_eq!(validate_name(""), Err("Name is required".to_string()));
+    assert_eq!(
+        validate_name("My-Token"),
+        Err("Name can only contain alphabets, numbers, and spaces".to_string())
+    );
+
+    assert!(validate_symbol("MTK5").is_ok());
+    assert_eq!(validate_symbol(""), Err("Symbol is required".to_string()));
+    assert_eq!(
+        validate_symbol("MT$"),
+        Err("Symbol can only contain alphabets and numbers".to_string())
+    );
+    assert_eq!(
+        validate_symbol("SAMPLE"),
+        Err("Symbol must be at most 5 characters long".to_string())
+    );
+
+    assert!(validate_decimals(1).is_ok());
+    assert!(validate_decimals(99).is_ok());
+    assert_eq!(
+        validate_decimals(0),
+        Err("Decimals must be between 1 and 99".to_string())
+    );
+    assert!(validate_decimals(100).is_err());
+
+    assert!(validate_description("").is_ok());
+    assert!(validate_description("A token, with (some) symbols!").is_ok());
+    assert!(validate_description("No \"quotes\"").is_err());
+
+    // Only spaces are allowed, so a line break or tab cannot escape the generated `///` header
+    assert!(validate_name("My\nToken").is_err());
+    assert!(validate_name("My\tToken").is_err());
+    assert!(validate_description("First line\nmodule evil {}").is_err());
+    assert!(validate_description("Tab\tseparated").is_err());
+
+    assert!(validate_icon_url("https://example.com/icon.png?size=64").is_ok());
+    assert_eq!(
+        validate_icon_url("ftp://example.com/icon.png"),
+        Err("Icon URL must be an http or https URL".to_string())
+    );
+    assert!(validate_icon_url("icon.png").is_err());
+    assert!(validate_icon_url("https://example.com/\"icon\".png").is_err());
+    assert!(validate_icon_url("https://example.com/my icon.png").is_err());
+
+    assert!(validate_max_supply(1_000_000, 9).is_ok());
+    assert!(validate_max_supply(u64::MAX, 0).is_ok());
+    assert_eq!(
+        validate_max_supply(0, 9),
+        Err("Max supply must be at least 1".to_string())
+    );
+    assert!(validate_max_supply(20_000_000_000, 9).is_err());
+    assert!(validate_max_supply(1, 20).is_err());
+
+    assert!(validate_environment("testnet").is_ok());
+    assert_eq!(
+        validate_environment("localnet"),
+        Err("Environment must be one of: mainnet, devnet, testnet".to_string())
+    );
+}
+
+// Test case to verify that invalid flags are rejected with the prompt messages, all at once
+#[test]
+fn test_invalid_flags_rejected() {
+    let result = get_user_prompt(&CreateTokenParams {
+        name: Some("Flag Token".to_string()),
+        symbol: Some("TOOLONG".to_string()),
+        decimals: Some(120),
+        environment: Some("localnet".to_string()),
+        ..non_interactive_params()
+    });
+
+    match result {
+        Err(TokenGenErrors::InvalidInput(msg)) => assert_eq!(
+            msg,
+            "Symbol must be at most 5 characters long; Decimals must be between 1 and 99; Environment must be one of: mainnet, devnet, testnet"
+        ),
+        other => panic!("Expected invalid input error, but got {:?}", other),
+    }
+}
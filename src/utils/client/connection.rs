@@ -0,0 +1,452 @@
+use futures::Future;
+use std::{
+    io,
+    net::IpAddr,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+use tarpc::{client::RpcError, context};
+use tokio_rustls::TlsConnector;
+
+use super::{
+    responses::{CreateTokenResponse, GeneratedFile, RpcResponseErrors, ServerCapabilities},
+    rpc_client::{initiate_client, initiate_tls_client, TokenGenClient},
+};
+use crate::{
+    constants::{
+        DEFAULT_RPC_RETRIES, DEFAULT_RPC_TIMEOUT_SECS, MAX_RPC_TIMEOUT_SECS, PROTOCOL_VERSION,
+        RPC_MAX_BACKOFF_MS, RPC_RETRY_BACKOFF_MS, TLS_SCHEME, VERSION,
+    },
+    errors::TokenGenErrors,
+    utils::{helpers::validate_rpc_url, prompts::TokenInfo, tls::TlsOptions},
+    Result,
+};
+
+/// Deadline and retry policy applied to every call of an `RpcClient`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallOptions {
+    /// Deadline of each attempt, and of opening each connection.
+    pub timeout: Duration,
+
+    /// Number of times an idempotent call is retried after a transient failure.
+    pub retries: u32,
+
+    /// Delay before the first retry, doubled for every further retry.
+    pub backoff: Duration,
+}
+
+impl Default for CallOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(DEFAULT_RPC_TIMEOUT_SECS),
+            retries: DEFAULT_RPC_RETRIES,
+            backoff: Duration::from_millis(RPC_RETRY_BACKOFF_MS),
+        }
+    }
+}
+
+/**
+ * Connection to the RPC service used by the commands.
+ *
+ * Every call gets the configured deadline. Verification calls are idempotent, so they are retried
+ * with exponential backoff when they time out or the transport fails; a dropped transport is
+ * replaced by a new connection first. `create` is never retried.
+ *
+ * Clones share the connection, so a reconnect by one clone is seen by all of them.
+ */
+#[derive(Clone)]
+pub struct RpcClient {
+    // Current connection, replaced on every reconnect.
+    connection: Arc<Mutex<Connection>>,
+    // Serializes reconnects, so concurrent calls that fail together open a single connection.
+    reconnecting: Arc<tokio::sync::Mutex<()>>,
+    endpoint: Option<Arc<Endpoint>>,
+    options: CallOptions,
+}
+
+/// Wraps an established client with the default options. It cannot be reconnected.
+impl From<TokenGenClient> for RpcClient {
+    fn from(client: TokenGenClient) -> Self {
+        Self::new(client, CallOptions::default())
+    }
+}
+
+impl RpcClient {
+    /**
+     * Connects to the RPC service.
+     *
+     * `tls://` addresses are connected to over TLS with the given certificates; any other address
+     * uses the plaintext transport. Every connection starts with the `capabilities` handshake, and
+     * is authenticated with the API key, if any, before use. An API key is only sent in plaintext
+     * to a loopback address.
+     *
+     * # Parameters
+     * - `rpc_url`: The address of the RPC service, as accepted by `--rpc`.
+     * - `tls`: Certificates for `tls://` addresses.
+     * - `api_key`: API key for services that restrict access.
+     * - `options`: Deadline and retry policy of the calls.
+     *
+     * # Returns
+     * - `Ok(RpcClient)`: The connected client.
+     * - `Err(TokenGenErrors::FailedToConnectRpc)`: If the connection fails, with the I/O error as its source.
+     * - `Err(TokenGenErrors::IncompatibleServer)`: If the server speaks another protocol version.
+     * - `Err(TokenGenErrors::InvalidTlsConfig)`: If an API key would be sent in plaintext to a remote host.
+     * - `Err(TokenGenErrors)`: If the address or TLS settings are invalid, or the API key is rejected.
+     */
+    pub async fn connect(
+        rpc_url: &str,
+        tls: &TlsOptions,
+        api_key: Option<String>,
+        options: CallOptions,
+    ) -> Result<Self> {
+        let rpc_url = validate_rpc_url(rpc_url)?;
+        let (address, tls) = match rpc_url.strip_prefix(TLS_SCHEME) {
+            Some(address) => (address.to_string(), Some(tls.connector()?)),
+            None => (rpc_url, None),
+        };
+
+        // The key is a bearer credential, so it must not cross the network unencrypted.
+        if api_key.is_some() && tls.is_none() && !is_loopback(&address) {
+            return Err(TokenGenErrors::InvalidTlsConfig(format!(
+                "refusing to send the API key in plaintext to {}, use a {}{} address",
+                address, TLS_SCHEME, address
+            )));
+        }
+
+        let endpoint = Endpoint {
+            address,
+            tls,
+            api_key,
+            timeout: options.timeout,
+        };
+        let (client, capabilities) = endpoint.connect().await?;
+
+        let rpc_client = Self {
+            endpoint: Some(Arc::new(endpoint)),
+            ..Self::new(client, options)
+        };
+        rpc_client.lock().capabilities = Some(capabilities);
+        Ok(rpc_client)
+    }
+
+    /// Wraps an established client, which cannot be reconnected if its transport drops.
+    pub fn new(client: TokenGenClient, options: CallOptions) -> Self {
+        Self {
+            connection: Arc::new(Mutex::new(Connection {
+                generation: 0,
+                client,
+                capabilities: None,
+            })),
+            reconnecting: Arc::default(),
+            endpoint: None,
+            options,
+        }
+    }
+
+    /// Returns the current client, for calls that are not wrapped by `RpcClient`.
+    pub fn client(&self) -> TokenGenClient {
+        self.lock().client.clone()
+    }
+
+    /// Returns what the server reported in the handshake, unless the client was created with `new`.
+    pub fn capabilities(&self) -> Option<ServerCapabilities> {
+        self.lock().capabilities.clone()
+    }
+
+    /// Asks the server for its version and protocol, and checks that it is compatible.
+    pub async fn fetch_capabilities(&self) -> Result<ServerCapabilities> {
+        let capabilities = self
+            .call(true, |client, context| async move {
+                client.capabilities(context).await
+            })
+            .await?;
+        check_compatibility(&capabilities)?;
+        self.lock().capabilities = Some(capabilities.clone());
+        Ok(capabilities)
+    }
+
+    /// Requests the contract sources for a token. Not retried, as the request is not idempotent.
+    pub async fn create(
+        &self,
+        token: &TokenInfo,
+    ) -> Result<std::result::Result<CreateTokenResponse, RpcResponseErrors>> {
+        self.call(false, |client, context| {
+            let token = token.clone();
+            async move {
+                client
+                    .create(
+                        context,
+                        token.decimals,
+                        token.name,
+                        token.symbol,
+                        token.description,
+                        token.is_frozen,
+                        token.environment,
+                        token.icon_url,
+                        token.max_supply,
+                    )
+                    .await
+            }
+        })
+        .await
+    }
+
+    /// Verifies the package in a Git repository.
+    pub async fn verify_url(
+        &self,
+        url: &str,
+    ) -> Result<std::result::Result<String, RpcResponseErrors>> {
+        self.call(true, |client, context| {
+            let url = url.to_string();
+            async move { client.verify_url(context, url).await }
+        })
+        .await
+    }
+
+    /// Verifies contract sources and their `Move.toml`.
+    pub async fn verify_content(
+        &self,
+        content: &str,
+        toml: &str,
+    ) -> Result<std::result::Result<(), RpcResponseErrors>> {
+        self.call(true, |client, context| {
+            let (content, toml) = (content.to_string(), toml.to_string());
+            async move { client.verify_content(context, content, toml).await }
+        })
+        .await
+    }
+
+    /// Verifies every file of a package.
+    pub async fn verify_package(
+        &self,
+        files: &[GeneratedFile],
+    ) -> Result<std::result::Result<(), RpcResponseErrors>> {
+        self.call(true, |client, context| {
+            let files = files.to_vec();
+            async move { client.verify_package(context, files).await }
+        })
+        .await
+    }
+
+    /// Verifies a deployed token by its address.
+    pub async fn verify_address(
+        &self,
+        address: &str,
+        environment: &str,
+    ) -> Result<std::result::Result<(), RpcResponseErrors>> {
+        self.call(true, |client, context| {
+            let (address, environment) = (address.to_string(), environment.to_string());
+            async move { client.verify_address(context, address, environment).await }
+        })
+        .await
+    }
+
+    // Makes a call, retrying idempotent calls after transient failures.
+    async fn call<T, F, Fut>(&self, idempotent: bool, request: F) -> Result<T>
+    where
+        F: Fn(TokenGenClient, context::Context) -> Fut,
+        Fut: Future<Output = std::result::Result<T, RpcError>>,
+    {
+        let mut retries = if idempotent { self.options.retries } else { 0 };
+        let mut backoff = self.options.backoff;
+
+        loop {
+            let (generation, client) = self.current();
+            let error = match request(client, deadline(self.options.timeout)).await {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+
+            if retries == 0 || !is_transient(&error) {
+                return Err(TokenGenErrors::RpcError(error));
+            }
+            retries -= 1;
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(Duration::from_millis(RPC_MAX_BACKOFF_MS));
+
+            // A dropped transport fails every later request, so open a new connection first.
+            if !matches!(error, RpcError::DeadlineExceeded) {
+                match self.reconnect(generation).await {
+                    Ok(()) => {}
+                    // The service may come back before the next retry.
+                    Err(TokenGenErrors::FailedToConnectRpc(_)) if retries > 0 => {}
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+    }
+
+    // Returns the current client and its generation.
+    fn current(&self) -> (u64, TokenGenClient) {
+        let connection = self.lock();
+        (connection.generation, connection.client.clone())
+    }
+
+    // Locks the current connection.
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // Replaces the client of the given generation, unless another call already did.
+    async fn reconnect(&self, generation: u64) -> Result<()> {
+        let Some(endpoint) = &self.endpoint else {
+            return Ok(());
+        };
+
+        let _reconnecting = self.reconnecting.lock().await;
+        if self.current().0 != generation {
+            return Ok(());
+        }
+
+        let (client, capabilities) = endpoint.connect().await?;
+        *self.lock() = Connection {
+            generation: generation + 1,
+            client,
+            capabilities: Some(capabilities),
+        };
+        Ok(())
+    }
+}
+
+// A connection, with a generation that is bumped on every reconnect.
+struct Connection {
+    generation: u64,
+    client: TokenGenClient,
+    capabilities: Option<ServerCapabilities>,
+}
+
+// Everything needed to open another connection to the same service.
+//
+// The API key is sent once per connection with `authenticate`, rather than with every request:
+// tarpc's `context::Context` only carries the deadline and trace context, and has no room for
+// custom fields. The server remembers the key for the rest of the connection, see `Session`.
+struct Endpoint {
+    address: String,
+    tls: Option<TlsConnector>,
+    api_key: Option<String>,
+    timeout: Duration,
+}
+
+impl Endpoint {
+    // Opens a connection, checks the server's protocol version and authenticates the connection.
+    async fn connect(&self) -> Result<(TokenGenClient, ServerCapabilities)> {
+        let connect = async {
+            match &self.tls {
+                Some(connector) => initiate_tls_client(&self.address, connector.clone()).await,
+                None => initiate_client(&self.address).await,
+            }
+        };
+        let client = tokio::time::timeout(self.timeout, connect)
+            .await
+            .unwrap_or_else(|_| {
+                Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("Timed out connecting to {}", self.address),
+                ))
+            })
+            .map_err(TokenGenErrors::FailedToConnectRpc)?;
+
+        let capabilities = client
+            .capabilities(deadline(self.timeout))
+            .await
+            .map_err(handshake_error)?;
+        check_compatibility(&capabilities)?;
+
+        match &self.api_key {
+            Some(api_key) => client
+                .authenticate(deadline(self.timeout), api_key.clone())
+                .await?
+                .map_err(|e| TokenGenErrors::from_response(e, |_| TokenGenErrors::Unauthorized))?,
+            None if capabilities.requires_api_key => return Err(TokenGenErrors::Unauthorized),
+            None => {}
+        }
+        Ok((client, capabilities))
+    }
+}
+
+// Returns whether a `host:port` address is on this machine, where plaintext never leaves the host.
+fn is_loopback(address: &str) -> bool {
+    let host = address.rsplit_once(':').map_or(address, |(host, _)| host);
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    match host.parse::<IpAddr>() {
+        Ok(ip) => ip.is_loopback(),
+        Err(_) => host.eq_ignore_ascii_case("localhost"),
+    }
+}
+
+// Classifies a failed `capabilities` handshake.
+//
+// A server that predates the handshake reads the request, cannot decode it and closes the
+// connection cleanly; one that decodes it but does not serve it rejects it with an error. Anything
+// else, e.g. a reset connection or a missed deadline, is a connection failure that may be retried.
+fn handshake_error(error: RpcError) -> TokenGenErrors {
+    match error {
+        RpcError::Shutdown => TokenGenErrors::IncompatibleServer(format!(
+            "it does not report its protocol version, so it predates this client ({})",
+            VERSION
+        )),
+        RpcError::Server(ref rejection)
+            if matches!(
+                rejection.kind,
+                io::ErrorKind::InvalidInput
+                    | io::ErrorKind::InvalidData
+                    | io::ErrorKind::Unsupported
+            ) =>
+        {
+            TokenGenErrors::IncompatibleServer(format!(
+                "it rejected the protocol version request: {}",
+                rejection.detail
+            ))
+        }
+        RpcError::DeadlineExceeded => TokenGenErrors::FailedToConnectRpc(io::Error::new(
+            io::ErrorKind::TimedOut,
+            "Timed out waiting for the protocol version",
+        )),
+        error => TokenGenErrors::FailedToConnectRpc(io::Error::new(
+            io::ErrorKind::ConnectionAborted,
+            error,
+        )),
+    }
+}
+
+/**
+ * Checks that a server speaks the protocol version of this client.
+ *
+ * # Returns
+ * - `Ok(())`: If the protocol versions match.
+ * - `Err(TokenGenErrors::IncompatibleServer)`: Otherwise, naming both versions.
+ */
+pub fn check_compatibility(server: &ServerCapabilities) -> Result<()> {
+    if server.protocol_version == PROTOCOL_VERSION {
+        return Ok(());
+    }
+
+    let upgrade = if server.protocol_version > PROTOCOL_VERSION {
+        "this client"
+    } else {
+        "the server"
+    };
+    Err(TokenGenErrors::IncompatibleServer(format!(
+        "server {} speaks protocol {}, client {} speaks protocol {}. Upgrade {}.",
+        server.version, server.protocol_version, VERSION, PROTOCOL_VERSION, upgrade
+    )))
+}
+
+// Returns a request context that expires after the timeout.
+fn deadline(timeout: Duration) -> context::Context {
+    let mut context = context::current();
+    let now = Instant::now();
+    // Timeouts from config files are not bounded, so very large ones saturate instead of overflowing.
+    context.deadline = now
+        .checked_add(timeout)
+        .unwrap_or_else(|| now + Duration::from_secs(MAX_RPC_TIMEOUT_SECS));
+    context
+}
+
+// Returns whether a failed call may succeed when retried.
+fn is_transient(error: &RpcError) -> bool {
+    matches!(
+        error,
+        RpcError::Shutdown | RpcError::Send(_) | RpcError::Channel(_) | RpcError::DeadlineExceeded
+    )
+}
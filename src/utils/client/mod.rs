@@ -1,11 +1,14 @@
 //! Client-side utilities for RPC communication
 //!
 //! This module contains client-side utilities for interacting with the RPC server.
-//! It includes the `rpc_client` for RPC communication and the `responses` enum
-//! for handling responses from the RPC server.
+//! It includes the `rpc_client` for RPC communication, the reconnecting `connection` used by
+//! the commands, and the `responses` enum for handling responses from the RPC server.
 
 /// Client-side utilities for RPC communication
 pub mod rpc_client;
 
+/// Connections with deadlines, retries and reconnection for RPC calls
+pub mod connection;
+
 /// Response types and handling for client operations
 pub mod responses;
@@ -8,7 +8,7 @@ use thiserror::Error;
  * be transmitted or stored easily. Each variant provides a specific error message
  * for clearer error handling on the client side.
  */
-#[derive(Error, Debug, Deserialize, Serialize)]
+#[derive(Error, Debug, Clone, Deserialize, Serialize)]
 pub enum RpcResponseErrors {
     /// Error returned when the contract has been modified from its original state
     #[error("Contract has been modified")]
@@ -30,6 +30,14 @@ pub enum RpcResponseErrors {
     #[error("Invalid token description")]
     InvalidDescription,
 
+    /// Error returned when the blockchain environment is not supported
+    #[error("Invalid environment: {0}")]
+    InvalidEnvironment(String),
+
+    /// Error returned when the token max supply is zero or exceeds the largest coin balance
+    #[error("Invalid token max supply: {0}")]
+    InvalidMaxSupply(String),
+
     /// General error with a detailed message
     #[error("{0}")]
     GeneralError(String),
@@ -53,4 +61,78 @@ pub enum RpcResponseErrors {
     /// Error returned when verification fails
     #[error("{0}")]
     VerifyResultError(String),
+
+    /// Error returned when the connection has not authenticated with an accepted API key
+    #[error("Unauthorized: a valid API key is required")]
+    Unauthorized,
+
+    /// Error returned when the API key exceeded its request quota, with the seconds until it resets
+    #[error("Rate limit exceeded, retry in {0} seconds")]
+    RateLimited(u64),
+}
+
+/// Version and features reported by an RPC server in the connection handshake.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ServerCapabilities {
+    /// Version of the server build.
+    pub version: String,
+
+    /// Version of the RPC protocol spoken by the server; see `constants::PROTOCOL_VERSION`.
+    pub protocol_version: u32,
+
+    /// Blockchain environments the server generates and verifies contracts for.
+    pub environments: Vec<String>,
+
+    /// Whether connections must authenticate with an API key.
+    pub requires_api_key: bool,
+}
+
+/// Response of `create`: the files of the generated package.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct CreateTokenResponse {
+    /// Files to write, relative to the package folder.
+    pub files: Vec<GeneratedFile>,
+}
+
+impl CreateTokenResponse {
+    /// Returns the first file with the given role, if any.
+    pub fn file(&self, role: FileRole) -> Option<&GeneratedFile> {
+        self.files.iter().find(|file| file.role == role)
+    }
+}
+
+/// A file of a generated package.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GeneratedFile {
+    /// Path relative to the package folder, with `/` separators, e.g. `sources/mytoken.move`.
+    pub path: String,
+
+    /// Contents of the file.
+    pub contents: String,
+
+    /// What the file is used for.
+    pub role: FileRole,
+}
+
+/// Purpose of a generated file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileRole {
+    /// The package `Move.toml`.
+    Manifest,
+
+    /// A Move module under `sources/`.
+    Module,
+
+    /// A Move test module under `tests/`.
+    Test,
+
+    /// Documentation, such as a README.
+    Docs,
+
+    /// A helper script, such as a deployment script.
+    Script,
+
+    /// Any other file.
+    Other,
 }
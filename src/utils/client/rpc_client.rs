@@ -1,12 +1,53 @@
 use anyhow::Result;
-use std::{io::Error, net::SocketAddr};
-use tarpc::{client, service, tokio_serde::formats::Json};
+use futures::Future;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{Error, ErrorKind},
+    net::SocketAddr,
+};
+use tarpc::{
+    client,
+    serde_transport::{self, Transport},
+    service,
+    tokio_serde::formats::Json,
+    tokio_util::codec::length_delimited::LengthDelimitedCodec,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::{lookup_host, TcpStream},
+};
+use tokio_rustls::{rustls::pki_types::ServerName, TlsConnector};
 
-use super::responses::RpcResponseErrors;
+use super::responses::{CreateTokenResponse, GeneratedFile, RpcResponseErrors, ServerCapabilities};
+use crate::constants::MAX_RPC_FRAME_BYTES;
 
 /// `TokenGen` trait defines the RPC interface for token generation operations.
 #[service]
 pub trait TokenGen {
+    /// `capabilities` is an asynchronous method that reports the server's version and features.
+    ///
+    /// Clients call it first on every connection, before authenticating, and refuse servers that
+    /// speak another protocol version.
+    ///
+    /// # Returns
+    /// - The server's version, protocol version, supported environments and access requirements.
+    async fn capabilities() -> ServerCapabilities;
+
+    /// `authenticate` is an asynchronous method that authenticates the connection with an API key.
+    ///
+    /// Requests carry no credentials of their own, so the key is sent once per connection and
+    /// applies to every later request on it. Servers without access control accept any key.
+    ///
+    /// # Arguments
+    /// - `api_key`: The API key issued by the operator of the RPC service.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the key is accepted.
+    ///
+    /// # Errors
+    /// - Returns `RpcResponseErrors::Unauthorized` if the key is not accepted.
+    async fn authenticate(api_key: String) -> Result<(), RpcResponseErrors>;
+
     /// `create` is an asynchronous method that creates a token with the specified parameters.
     ///
     /// # Arguments
@@ -16,12 +57,12 @@ pub trait TokenGen {
     /// - `description`: A description for the token.
     /// - `is_frozen`: Whether the token is frozen or not.
     /// - `environment`: The environment in which the token is deployed (e.g., `mainnet`, `devnet`, `testnet`).
+    /// - `icon_url`: The optional URL of the token icon.
+    /// - `max_supply`: The optional fixed total supply, in whole tokens.
     ///
     /// # Returns
-    /// - A tuple containing:
-    ///   - The created token's contract address.
-    ///   - The transaction ID.
-    ///   - The metadata associated with the token.
+    /// - The files of the generated package, each with its path relative to the package folder,
+    ///   its contents and its role.
     ///
     /// # Errors
     /// - Returns `RpcResponseErrors` in case of RPC failures or invalid input parameters.
@@ -33,7 +74,9 @@ pub trait TokenGen {
         description: String,
         is_frozen: bool,
         environment: String,
-    ) -> Result<(String, String, String), RpcResponseErrors>;
+        icon_url: Option<String>,
+        max_supply: Option<u64>,
+    ) -> Result<CreateTokenResponse, RpcResponseErrors>;
 
     /// `verify_url` is an asynchronous method that verifies the validity of a provided URL.
     ///
@@ -59,6 +102,19 @@ pub trait TokenGen {
     /// - Returns `RpcResponseErrors` if the content is invalid or verification fails.
     async fn verify_content(content: String, toml: String) -> Result<(), RpcResponseErrors>;
 
+    /// `verify_package` is an asynchronous method that verifies every file of a package.
+    ///
+    /// # Arguments
+    /// - `files`: `Move.toml` and the files in `sources/` and `tests/`, with paths relative to the
+    ///   package folder.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the package has exactly the files generated for its header.
+    ///
+    /// # Errors
+    /// - Returns `RpcResponseErrors::ProgramModified` if any file is missing, extra or modified.
+    async fn verify_package(files: Vec<GeneratedFile>) -> Result<(), RpcResponseErrors>;
+
     /// `verify_address` is an asynchronous method that validates a given blockchain address in a specific environment.
     ///
     /// # Arguments
@@ -74,26 +130,96 @@ pub trait TokenGen {
 }
 
 /// Initializes the RPC client by connecting to the provided server address.
+///
+/// The address is a `host:port` pair whose host may be a hostname, an IPv4 address or a bracketed
+/// IPv6 address. Hostnames are resolved and each resolved address is tried in turn until one
+/// accepts the connection.
 pub async fn initiate_client(address: &str) -> Result<TokenGenClient, Error> {
-    // Parse the address string to `SocketAddr` to ensure it is in a valid format.
-    let server_addr: SocketAddr = address.parse().map_err(|_| {
-        std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid address format")
-    })?;
+    connect_any(address, |server_addr| async move {
+        Ok(new_client(TcpStream::connect(server_addr).await?))
+    })
+    .await
+}
 
-    // Establish a TCP connection to the server, using JSON for serialization.
-    let mut transport = tarpc::serde_transport::tcp::connect(server_addr, Json::default);
+/// Initializes the RPC client over TLS, verifying the server certificate for the address's host.
+///
+/// Resolution works as for `initiate_client`; the TLS handshake is performed on each connection.
+pub async fn initiate_tls_client(
+    address: &str,
+    connector: TlsConnector,
+) -> Result<TokenGenClient, Error> {
+    // The server certificate must be valid for the host the client asked for, not a resolved IP.
+    let host = address
+        .rsplit_once(':')
+        .map_or(address, |(host, _)| host)
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let server_name = ServerName::try_from(host.to_string())
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
 
-    // Configure the transport to handle large messages by increasing the maximum frame length.
-    transport.config_mut().max_frame_length(usize::MAX);
+    connect_any(address, |server_addr| {
+        let connector = connector.clone();
+        let server_name = server_name.clone();
+        async move {
+            let stream = TcpStream::connect(server_addr).await?;
+            Ok(new_client(connector.connect(server_name, stream).await?))
+        }
+    })
+    .await
+}
+
+/// Resolves the address and connects to each resolved address in turn, returning the first client.
+async fn connect_any<F, Fut>(address: &str, connect: F) -> Result<TokenGenClient, Error>
+where
+    F: Fn(SocketAddr) -> Fut,
+    Fut: Future<Output = Result<TokenGenClient, Error>>,
+{
+    // Resolve the address, which may name a host rather than a single IP address.
+    let server_addrs: Vec<SocketAddr> = lookup_host(address).await?.collect();
+
+    // Try each resolved address, keeping the last error if none accepts the connection.
+    let mut last_error = Error::new(
+        ErrorKind::NotFound,
+        format!("No addresses found for {}", address),
+    );
+    for server_addr in server_addrs {
+        match connect(server_addr).await {
+            Ok(client) => return Ok(client),
+            Err(e) => last_error = e,
+        }
+    }
 
+    Err(last_error)
+}
+
+/// Creates the RPC client on an established connection.
+fn new_client<S>(stream: S) -> TokenGenClient
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
     // Configure the client settings to support a larger number of in-flight requests and a bigger request buffer.
     let mut client_config = client::Config::default();
     client_config.max_in_flight_requests = 1024; // Allows up to 1024 concurrent requests
     client_config.pending_request_buffer = 1024; // Defines a buffer for pending requests
 
     // Create the RPC client using the transport and client configuration.
-    let client: TokenGenClient = TokenGenClient::new(client_config, transport.await?).spawn();
+    TokenGenClient::new(client_config, json_transport(stream)).spawn()
+}
 
-    // Return the initialized client
-    Ok(client)
+/// Frames a connection with the JSON transport shared by the client and the reference server.
+///
+/// Frames are length-delimited like `tarpc::serde_transport::tcp`, and limited to
+/// `MAX_RPC_FRAME_BYTES` so that a peer cannot make the other side buffer without bound.
+pub fn json_transport<S, Item, SinkItem>(
+    stream: S,
+) -> Transport<S, Item, SinkItem, Json<Item, SinkItem>>
+where
+    S: AsyncRead + AsyncWrite,
+    Item: for<'de> Deserialize<'de>,
+    SinkItem: Serialize,
+{
+    let framed = LengthDelimitedCodec::builder()
+        .max_frame_length(MAX_RPC_FRAME_BYTES)
+        .new_framed(stream);
+    serde_transport::new(framed, Json::default())
 }
@@ -0,0 +1,323 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use toml::{Table, Value};
+
+use crate::{
+    constants::{
+        CONFIG_ENV_PREFIX, MAX_RPC_RETRIES, MAX_RPC_TIMEOUT_SECS, PROJECT_CONFIG_FILE,
+        PROJECT_CONFIG_KEYS, USER_CONFIG_DIR, USER_CONFIG_FILE,
+    },
+    errors::TokenGenErrors,
+    handlers::OutputFormat,
+    Result,
+};
+
+/// Preferences that can be set in a config file, a profile or the environment.
+///
+/// Every field is optional; command-line flags take precedence over all of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    /// RPC service address, as accepted by `--rpc`.
+    pub rpc: Option<String>,
+
+    /// Default blockchain environment, as accepted by `--environment`.
+    pub environment: Option<String>,
+
+    /// Default output format, as accepted by `--output`.
+    pub output: Option<OutputFormat>,
+
+    /// CA bundle trusted for `tls://` RPC addresses, as accepted by `--ca-cert`.
+    pub ca_cert: Option<String>,
+
+    /// Client certificate for `tls://` RPC addresses, as accepted by `--client-cert`.
+    pub client_cert: Option<String>,
+
+    /// Private key of the client certificate, as accepted by `--client-key`.
+    pub client_key: Option<String>,
+
+    /// API key sent to the RPC service, as accepted by `--api-key`.
+    pub api_key: Option<String>,
+
+    /// Deadline of RPC calls in seconds, as accepted by `--timeout`.
+    pub timeout: Option<u64>,
+
+    /// Number of retries of verification calls, as accepted by `--retries`.
+    pub retries: Option<u32>,
+
+    /// Sui fullnode JSON-RPC URL, as accepted by `verify --fullnode` and `publish --fullnode`.
+    pub fullnode: Option<String>,
+
+    /// Sui keystore signing `publish` transactions, as accepted by `publish --keystore`.
+    pub keystore: Option<String>,
+}
+
+impl Settings {
+    /**
+     * Loads the settings for the current process.
+     *
+     * The user config file (`$SUI_TOKEN_GEN_CONFIG`, or `sui-token-gen/config.toml` in the XDG
+     * config directory) and the project-local `.sui-token-gen.toml` in the current directory are
+     * read if they exist, followed by the `SUI_TOKEN_GEN_*` environment variables. The project
+     * config may only set `environment` and `output`.
+     *
+     * # Parameters
+     * - `profile`: Profile selected with `--profile`; falls back to `$SUI_TOKEN_GEN_PROFILE`.
+     *
+     * # Returns
+     * - `Ok(Settings)`: The merged settings.
+     * - `Err(TokenGenErrors::InvalidConfig)`: If a config file or variable is invalid, the
+     *   project config sets a restricted key, or the profile is not defined in any config file.
+     */
+    pub fn load(profile: Option<&str>) -> Result<Self> {
+        let files: Vec<PathBuf> = user_config_path().into_iter().collect();
+
+        Self::resolve(
+            &files,
+            Some(Path::new(PROJECT_CONFIG_FILE)),
+            profile,
+            |name| env::var(name).ok(),
+        )
+    }
+
+    /**
+     * Merges the given config files and environment variables into settings.
+     *
+     * Later sources override earlier ones: the top-level values of each file in order, then the
+     * selected profile of each file in order, then the environment variables. The project file
+     * comes after the other files, and may only set the keys in `PROJECT_CONFIG_KEYS`.
+     *
+     * # Parameters
+     * - `files`: Config files in increasing order of precedence; missing files are skipped.
+     * - `project`: Project-local config file, skipped if missing.
+     * - `profile`: Profile selected with `--profile`; falls back to `SUI_TOKEN_GEN_PROFILE`.
+     * - `lookup`: Returns the value of an environment variable.
+     *
+     * # Returns
+     * - `Ok(Settings)`: The merged settings.
+     * - `Err(TokenGenErrors::InvalidConfig)`: If a source is invalid, the project file sets a
+     *   restricted key, or the profile is unknown.
+     */
+    pub fn resolve(
+        files: &[PathBuf],
+        project: Option<&Path>,
+        profile: Option<&str>,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self> {
+        let profile = profile
+            .map(str::to_string)
+            .or_else(|| lookup(&env_var("PROFILE")));
+
+        let configs: Vec<ConfigFile> = files
+            .iter()
+            .map(|path| (path.as_path(), false))
+            .chain(project.map(|path| (path, true)))
+            .filter(|(path, _)| path.is_file())
+            .map(|(path, project)| ConfigFile::read(path, project))
+            .collect::<Result<_>>()?;
+
+        let mut settings = Settings::default();
+        for config in &configs {
+            settings.merge(config.defaults.clone());
+        }
+
+        if let Some(profile) = &profile {
+            let mut found = false;
+            for config in &configs {
+                if let Some(values) = config.profiles.get(profile) {
+                    settings.merge(values.clone());
+                    found = true;
+                }
+            }
+
+            if !found {
+                return Err(TokenGenErrors::InvalidConfig(format!(
+                    "Profile '{}' is not defined in any config file",
+                    profile
+                )));
+            }
+        }
+
+        settings.merge(Settings::from_env(&lookup)?);
+        Ok(settings)
+    }
+
+    // Reads the `SUI_TOKEN_GEN_*` variables that mirror the settings.
+    fn from_env(lookup: &impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let output = lookup(&env_var("OUTPUT"))
+            .map(|value| {
+                OutputFormat::from_str(&value, true).map_err(|_| {
+                    TokenGenErrors::InvalidConfig(format!(
+                        "Invalid value '{}' for {}, expected 'text' or 'json'",
+                        value,
+                        env_var("OUTPUT")
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let settings = Settings {
+            rpc: lookup(&env_var("RPC")),
+            environment: lookup(&env_var("ENVIRONMENT")),
+            output,
+            ca_cert: lookup(&env_var("CA_CERT")),
+            client_cert: lookup(&env_var("CLIENT_CERT")),
+            client_key: lookup(&env_var("CLIENT_KEY")),
+            api_key: lookup(&env_var("API_KEY")),
+            timeout: parse_env(lookup, "TIMEOUT")?,
+            retries: parse_env(lookup, "RETRIES")?,
+            fullnode: lookup(&env_var("FULLNODE")),
+            keystore: lookup(&env_var("KEYSTORE")),
+        };
+        settings
+            .check_limits(|key| env_var(&key.to_uppercase()))
+            .map_err(TokenGenErrors::InvalidConfig)?;
+        Ok(settings)
+    }
+
+    // Applies the bounds of `--timeout` and `--retries`, naming the offending key with `name`.
+    fn check_limits(&self, name: impl Fn(&str) -> String) -> std::result::Result<(), String> {
+        if let Some(timeout) = self.timeout {
+            if !(1..=MAX_RPC_TIMEOUT_SECS).contains(&timeout) {
+                return Err(format!(
+                    "Invalid value '{}' for {}, expected between 1 and {} seconds",
+                    timeout,
+                    name("timeout"),
+                    MAX_RPC_TIMEOUT_SECS
+                ));
+            }
+        }
+        if let Some(retries) = self.retries {
+            if retries > MAX_RPC_RETRIES {
+                return Err(format!(
+                    "Invalid value '{}' for {}, expected at most {}",
+                    retries,
+                    name("retries"),
+                    MAX_RPC_RETRIES
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // Overrides the values that are set in `other`.
+    fn merge(&mut self, other: Settings) {
+        self.rpc = other.rpc.or(self.rpc.take());
+        self.environment = other.environment.or(self.environment.take());
+        self.output = other.output.or(self.output.take());
+        self.ca_cert = other.ca_cert.or(self.ca_cert.take());
+        self.client_cert = other.client_cert.or(self.client_cert.take());
+        self.client_key = other.client_key.or(self.client_key.take());
+        self.api_key = other.api_key.or(self.api_key.take());
+        self.timeout = other.timeout.or(self.timeout.take());
+        self.retries = other.retries.or(self.retries.take());
+        self.fullnode = other.fullnode.or(self.fullnode.take());
+        self.keystore = other.keystore.or(self.keystore.take());
+    }
+}
+
+/// Contents of a config file: top-level settings plus named profiles under `[profiles.<name>]`.
+#[derive(Debug, Clone, Default)]
+struct ConfigFile {
+    defaults: Settings,
+    profiles: HashMap<String, Settings>,
+}
+
+impl ConfigFile {
+    // Reads and parses a config file, naming the file in errors. A project file may only set the
+    // keys in `PROJECT_CONFIG_KEYS`, at the top level and in every profile.
+    fn read(path: &Path, project: bool) -> Result<Self> {
+        let invalid = |message: String| {
+            TokenGenErrors::InvalidConfig(format!("{}: {}", path.display(), message))
+        };
+
+        let content = fs::read_to_string(path)?;
+        let mut table: Table = toml::from_str(&content).map_err(|e| invalid(e.message().into()))?;
+
+        if project {
+            let profile_keys = table
+                .get("profiles")
+                .and_then(Value::as_table)
+                .into_iter()
+                .flat_map(|profiles| profiles.values())
+                .filter_map(Value::as_table)
+                .flat_map(|profile| profile.keys());
+            let restricted = table
+                .keys()
+                .filter(|key| *key != "profiles")
+                .chain(profile_keys)
+                .find(|key| !PROJECT_CONFIG_KEYS.contains(&key.as_str()));
+
+            if let Some(key) = restricted {
+                return Err(invalid(format!(
+                    "'{}' cannot be set in the project config, use the user config, a {}* variable or a flag",
+                    key, CONFIG_ENV_PREFIX
+                )));
+            }
+        }
+
+        // Profiles are read separately; the remaining top-level keys must all be settings.
+        let profiles: HashMap<String, Settings> = table
+            .remove("profiles")
+            .map(|profiles| profiles.try_into())
+            .transpose()
+            .map_err(|e| invalid(format!("in [profiles]: {}", e.message())))?
+            .unwrap_or_default();
+        let defaults: Settings = Value::Table(table)
+            .try_into()
+            .map_err(|e: toml::de::Error| invalid(e.message().into()))?;
+
+        defaults.check_limits(str::to_string).map_err(invalid)?;
+        for (name, profile) in &profiles {
+            profile
+                .check_limits(|key| format!("{} in [profiles.{}]", key, name))
+                .map_err(invalid)?;
+        }
+
+        Ok(ConfigFile { defaults, profiles })
+    }
+}
+
+/// Returns the user config file path: `$SUI_TOKEN_GEN_CONFIG` if set, otherwise
+/// `$XDG_CONFIG_HOME/sui-token-gen/config.toml`, falling back to `~/.config`.
+pub fn user_config_path() -> Option<PathBuf> {
+    if let Ok(path) = env::var(env_var("CONFIG")) {
+        return Some(PathBuf::from(path));
+    }
+
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join(USER_CONFIG_DIR).join(USER_CONFIG_FILE))
+}
+
+// Reads a numeric `SUI_TOKEN_GEN_*` variable.
+fn parse_env<T: FromStr>(
+    lookup: &impl Fn(&str) -> Option<String>,
+    name: &str,
+) -> Result<Option<T>> {
+    lookup(&env_var(name))
+        .map(|value| {
+            value.parse().map_err(|_| {
+                TokenGenErrors::InvalidConfig(format!(
+                    "Invalid value '{}' for {}, expected a number",
+                    value,
+                    env_var(name)
+                ))
+            })
+        })
+        .transpose()
+}
+
+// Builds the name of a `SUI_TOKEN_GEN_*` environment variable.
+fn env_var(name: &str) -> String {
+    format!("{}{}", CONFIG_ENV_PREFIX, name)
+}
@@ -2,6 +2,23 @@
 // The format is [IP address]:port
 pub const ADDRESS: &str = "5.161.90.244:5001";
 
+// Version of this build, reported by `--version`, `doctor` and the reference server.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Version of the RPC protocol. Bumped whenever a request or response of `TokenGen` changes, so
+// that clients and servers speaking different versions refuse each other instead of misbehaving.
+pub const PROTOCOL_VERSION: u32 = 6;
+
+// Number of packages verified at the same time by `verify --recursive`.
+pub const MAX_CONCURRENT_VERIFICATIONS: usize = 8;
+
+// Largest RPC frame accepted, in bytes. A generated package fits well within it, and a peer
+// announcing a larger frame is disconnected instead of being buffered.
+pub const MAX_RPC_FRAME_BYTES: usize = 16 * 1024 * 1024;
+
+// Scheme of RPC addresses that are connected to over TLS, e.g. `tls://tokengen.internal:5001`.
+pub const TLS_SCHEME: &str = "tls://";
+
 // The error message used when the user cancels an ongoing operation.
 // This message is displayed to indicate that the action was aborted by the user.
 pub const CANCEL_ERROR_MESSAGE: &str = "Operation was canceled by the user";
@@ -20,3 +37,63 @@ pub const TEST_FOLDER: &str = "tests";
 
 // Constant for default environment
 pub const DEFAULT_ENVIRONMENT: &str = "devnet";
+
+// Blockchain environments a token can be generated for.
+pub const SUPPORTED_ENVIRONMENTS: [&str; 3] = ["mainnet", "devnet", "testnet"];
+
+// JSON-RPC endpoints of the public Sui fullnode of each environment, used by `verify --address`.
+pub const FULLNODE_URLS: [(&str, &str); 3] = [
+    ("mainnet", "https://fullnode.mainnet.sui.io:443"),
+    ("devnet", "https://fullnode.devnet.sui.io:443"),
+    ("testnet", "https://fullnode.testnet.sui.io:443"),
+];
+
+// Largest fullnode response read, in bytes. A compiled package fits well within it.
+pub const MAX_FULLNODE_RESPONSE_BYTES: u64 = 16 * 1024 * 1024;
+
+// Sui CLI used to compile generated packages to bytecode, looked up in `PATH`.
+pub const SUI_BINARY: &str = "sui";
+
+// Keystore of the Sui CLI, relative to the home folder, used by `publish` to sign transactions.
+pub const SUI_KEYSTORE_PATH: &str = ".sui/sui_config/sui.keystore";
+
+// Default gas budget of a `publish` transaction, in MIST (0.1 SUI).
+pub const DEFAULT_GAS_BUDGET: u64 = 100_000_000;
+
+// Record of the published deployments of a package, written to the package folder by `publish`.
+pub const DEPLOYMENTS_FILE: &str = "deployments.toml";
+
+// Hosts the reference server clones repositories from for `verify_url`. Any other host is
+// refused, so a request cannot make the server reach internal or arbitrary addresses.
+pub const ALLOWED_GIT_HOSTS: [&str; 4] =
+    ["github.com", "gitlab.com", "bitbucket.org", "codeberg.org"];
+
+// Name of the project-local config file, read from the current directory.
+pub const PROJECT_CONFIG_FILE: &str = ".sui-token-gen.toml";
+
+// Settings the project-local config may set. Anything that selects a server, a credential or a
+// signing key only comes from the user config, the environment or flags.
+pub const PROJECT_CONFIG_KEYS: [&str; 2] = ["environment", "output"];
+
+// Folder and file name of the user config file inside the XDG config directory.
+pub const USER_CONFIG_DIR: &str = "sui-token-gen";
+pub const USER_CONFIG_FILE: &str = "config.toml";
+
+// Prefix of the environment variables that override config values, e.g. `SUI_TOKEN_GEN_RPC`.
+pub const CONFIG_ENV_PREFIX: &str = "SUI_TOKEN_GEN_";
+
+// Default deadline of a single RPC call, matching tarpc's default context.
+pub const DEFAULT_RPC_TIMEOUT_SECS: u64 = 10;
+
+// Longest accepted RPC deadline, one day.
+pub const MAX_RPC_TIMEOUT_SECS: u64 = 86_400;
+
+// Default number of times an idempotent RPC call is retried after a transient failure.
+pub const DEFAULT_RPC_RETRIES: u32 = 2;
+
+// Most retries accepted for an RPC call; with the backoff cap a failing call still ends.
+pub const MAX_RPC_RETRIES: u32 = 10;
+
+// Delay before the first retry; it doubles with every further retry, up to the maximum.
+pub const RPC_RETRY_BACKOFF_MS: u64 = 250;
+pub const RPC_MAX_BACKOFF_MS: u64 = 4000;
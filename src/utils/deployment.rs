@@ -0,0 +1,210 @@
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{
+    constants::{DEPLOYMENTS_FILE, VERSION},
+    errors::TokenGenErrors,
+    utils::{
+        fullnode::FullnodeClient, keystore::SuiKeypair, onchain::BytecodeCompiler,
+        transaction::check_publish_transaction,
+    },
+    Result,
+};
+
+/// Objects created by publishing a generated coin package.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deployment {
+    /// ID of the published package.
+    pub package_id: String,
+    /// ID of the `TreasuryCap` of the coin, owned by the publisher.
+    pub treasury_cap_id: Option<String>,
+    /// ID of the `CoinMetadata` of the coin.
+    pub metadata_id: Option<String>,
+    /// Digest of the publish transaction.
+    pub digest: String,
+    /// Version of this tool that generated the package, if it was recorded by `create`.
+    pub generator_version: Option<String>,
+    /// When `create` generated the package, in RFC 3339 format (UTC), if it was recorded.
+    pub generated_at: Option<String>,
+    /// When the package was published, in RFC 3339 format (UTC).
+    pub published_at: Option<String>,
+}
+
+/**
+ * Deployments of a package, by environment, stored in `deployments.toml` in the package folder.
+ *
+ * `create` writes the record with the version of this tool and the time that generated the
+ * package, which each deployment then copies. Each environment is a table, so publishing again to
+ * an environment replaces its deployment:
+ *
+ * ```toml
+ * generator_version = "0.2.0"
+ * generated_at = "2024-12-01T11:30:00Z"
+ *
+ * [testnet]
+ * package_id = "0x..."
+ * treasury_cap_id = "0x..."
+ * metadata_id = "0x..."
+ * digest = "..."
+ * generator_version = "0.2.0"
+ * generated_at = "2024-12-01T11:30:00Z"
+ * published_at = "2024-12-01T12:00:00Z"
+ * ```
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeploymentRecord {
+    /// Version of this tool that generated the package.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator_version: Option<String>,
+    /// When `create` generated the package, in RFC 3339 format (UTC).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_at: Option<String>,
+    #[serde(flatten)]
+    pub deployments: BTreeMap<String, Deployment>,
+}
+
+impl DeploymentRecord {
+    /**
+     * Reads the deployment record of a package.
+     *
+     * # Parameters
+     * - `package_path`: The package folder.
+     *
+     * # Returns
+     * - `Ok(DeploymentRecord)`: The recorded deployments, or none if the package has no record.
+     * - `Err(TokenGenErrors::InvalidInput)`: If the record cannot be parsed.
+     */
+    pub fn load(package_path: &str) -> Result<Self> {
+        let path = Path::new(package_path).join(DEPLOYMENTS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        toml::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+            TokenGenErrors::InvalidInput(format!("Invalid {}: {}", path.display(), e.message()))
+        })
+    }
+
+    /**
+     * Writes the deployment record into a package folder, replacing any previous record.
+     *
+     * # Parameters
+     * - `package_path`: The package folder.
+     */
+    pub fn save(&self, package_path: &str) -> Result<()> {
+        let content = toml::to_string(self)
+            .map_err(|e| TokenGenErrors::InvalidInput(format!("Invalid deployment: {}", e)))?;
+        fs::write(Path::new(package_path).join(DEPLOYMENTS_FILE), content)?;
+        Ok(())
+    }
+
+    /**
+     * Records this version of the tool and the current time as the generator of the package and
+     * writes the record, keeping its deployments.
+     *
+     * # Parameters
+     * - `package_path`: The package folder, just written by `create`.
+     */
+    pub fn record_generation(&mut self, package_path: &str) -> Result<()> {
+        self.generator_version = Some(VERSION.to_string());
+        self.generated_at = Some(timestamp());
+        self.save(package_path)
+    }
+}
+
+/**
+ * Publishes the package in a folder and returns the objects it created.
+ *
+ * This function performs the following steps:
+ * 1. Compiles the package in its folder.
+ * 2. Asks the fullnode to build the publish transaction, paid for by the signer.
+ * 3. Signs the transaction and executes it, waiting until the fullnode has applied it.
+ * 4. Reads the package ID and the IDs of the `TreasuryCap` and `CoinMetadata` of the coin from
+ *    the object changes of the transaction.
+ *
+ * # Parameters
+ * - `package_path`: The package folder.
+ * - `fullnode`: The fullnode of the environment to publish to.
+ * - `compiler`: Compiles the package.
+ * - `signer`: The key of the publisher, which pays for gas and receives the `TreasuryCap`.
+ * - `gas_budget`: The gas budget, in MIST.
+ *
+ * # Returns
+ * - `Ok(Deployment)`: The published package and coin objects.
+ * - `Err(TokenGenErrors::PublishError)`: If the package cannot be compiled or the transaction failed.
+ * - `Err(TokenGenErrors::FullnodeError)`: If a fullnode request fails.
+ */
+pub async fn publish_package(
+    package_path: &str,
+    fullnode: &FullnodeClient,
+    compiler: &impl BytecodeCompiler,
+    signer: &SuiKeypair,
+    gas_budget: u64,
+) -> Result<Deployment> {
+    let package = compiler.build(package_path).map_err(|e| match e {
+        TokenGenErrors::CompilerError(message) => TokenGenErrors::PublishError(message),
+        e => e,
+    })?;
+
+    let tx_bytes = fullnode
+        .unsafe_publish(&signer.address(), &package, gas_budget)
+        .await?;
+    check_publish_transaction(&tx_bytes, &signer.address(), &package, gas_budget)?;
+    let response = fullnode
+        .execute_transaction(&tx_bytes, &signer.sign_transaction(&tx_bytes))
+        .await?;
+
+    let digest = response["digest"].as_str().unwrap_or_default().to_string();
+    let status = &response["effects"]["status"];
+    if status["status"] != "success" {
+        return Err(TokenGenErrors::PublishError(format!(
+            "Transaction {} failed: {}",
+            digest,
+            status["error"].as_str().unwrap_or("unknown error")
+        )));
+    }
+
+    let changes = response["objectChanges"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let package_id = changes
+        .iter()
+        .find(|change| change["type"] == "published")
+        .and_then(|change| change["packageId"].as_str())
+        .ok_or_else(|| {
+            TokenGenErrors::PublishError(format!("Transaction {} published no package", digest))
+        })?
+        .to_string();
+
+    Ok(Deployment {
+        treasury_cap_id: created_object(&changes, "0x2::coin::TreasuryCap<"),
+        metadata_id: created_object(&changes, "0x2::coin::CoinMetadata<"),
+        package_id,
+        digest,
+        generator_version: None,
+        generated_at: None,
+        published_at: Some(timestamp()),
+    })
+}
+
+// Returns the current time in RFC 3339 format (UTC), to the second.
+fn timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+// Returns the ID of the object created with a type starting with the given prefix.
+fn created_object(changes: &[Value], type_prefix: &str) -> Option<String> {
+    changes
+        .iter()
+        .filter(|change| change["type"] == "created")
+        .find(|change| {
+            change["objectType"]
+                .as_str()
+                .is_some_and(|object_type| object_type.starts_with(type_prefix))
+        })
+        .and_then(|change| change["objectId"].as_str())
+        .map(str::to_string)
+}
@@ -0,0 +1,155 @@
+use colored::*;
+use serde::Serialize;
+use similar::TextDiff;
+use std::{
+    fmt,
+    io::{self, IsTerminal},
+};
+
+use crate::utils::{
+    prompts::TokenInfo,
+    verify_helper::{compare_package, VerifyPathStruct},
+};
+
+// Number of unchanged lines shown around each change.
+const CONTEXT_LINES: usize = 3;
+
+/// Details of how a package differs from the source this tool would generate for it.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModificationReport {
+    /// Token parameters claimed by the contract's header block, if it has one.
+    pub claimed: Option<TokenInfo>,
+
+    /// Generated files that are not in the package.
+    pub missing: Vec<String>,
+
+    /// Package files in `sources/` or `tests/` that this tool does not generate.
+    pub extra: Vec<String>,
+
+    /// Package files whose contents differ from the generated ones.
+    pub modified: Vec<String>,
+
+    /// Unified diff from the generated sources to the package sources of the modified files.
+    pub diff: String,
+}
+
+impl ModificationReport {
+    /**
+     * Builds a report by re-rendering the package from the parameters claimed in its header.
+     *
+     * The environment is taken from the framework revision in `Move.toml`, falling back to the
+     * default environment when it is missing. Without a header no expected package can be
+     * rendered, so the report only states that the header is missing.
+     *
+     * # Parameters
+     * - `package`: The files read from the package.
+     *
+     * # Returns
+     * - A `ModificationReport` with the claimed parameters, the missing, extra and modified files,
+     *   and the diff of the modified files against the generated sources.
+     */
+    pub fn new(package: &VerifyPathStruct) -> Self {
+        let comparison = compare_package(&package.files);
+
+        let diff = comparison
+            .modified
+            .iter()
+            .filter_map(|path| {
+                let expected = comparison.expected.iter().find(|file| &file.path == path)?;
+                let actual = package.files.iter().find(|file| &file.path == path)?;
+                Some(unified_diff(path, &expected.contents, &actual.contents))
+            })
+            .collect();
+
+        Self {
+            claimed: comparison.claimed,
+            missing: comparison.missing,
+            extra: comparison.extra,
+            modified: comparison.modified,
+            diff,
+        }
+    }
+
+    /**
+     * Renders the report as text.
+     *
+     * # Parameters
+     * - `colorize`: Whether to colour removed, added and hunk header lines.
+     *
+     * # Returns
+     * - The claimed header fields followed by the unified diff.
+     */
+    pub fn render(&self, colorize: bool) -> String {
+        let mut report = String::new();
+
+        match &self.claimed {
+            Some(token) => report.push_str(&format!(
+                "Contract claims:\n  Name: {}\n  Symbol: {}\n  Decimals: {}\n  Icon URL: {}\n  Max supply: {}\n  Frozen: {}\n",
+                token.name,
+                token.symbol,
+                token.decimals,
+                token.icon_url.as_deref().unwrap_or("None"),
+                token
+                    .max_supply
+                    .map_or("Unlimited".to_string(), |supply| supply.to_string()),
+                if token.is_frozen { "Yes" } else { "No" },
+            )),
+            None => report.push_str("Contract has no generator header block.\n"),
+        }
+
+        for (title, paths) in [
+            ("Missing files", &self.missing),
+            ("Extra files", &self.extra),
+            ("Modified files", &self.modified),
+        ] {
+            if !paths.is_empty() {
+                report.push_str(&format!("{}:\n", title));
+                for path in paths {
+                    report.push_str(&format!("  {}\n", path));
+                }
+            }
+        }
+
+        if !self.diff.is_empty() {
+            report.push_str("Differences from the generated source:\n");
+            for line in self.diff.lines() {
+                let line = if !colorize {
+                    line.normal()
+                } else if line.starts_with("---") || line.starts_with("+++") {
+                    line.bold()
+                } else if line.starts_with("@@") {
+                    line.cyan()
+                } else if line.starts_with('-') {
+                    line.red()
+                } else if line.starts_with('+') {
+                    line.green()
+                } else {
+                    line.normal()
+                };
+                report.push_str(&format!("{}\n", line));
+            }
+        }
+
+        report
+    }
+}
+
+impl fmt::Display for ModificationReport {
+    /// Colours the report only when it is written to a terminal.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(io::stderr().is_terminal()).trim_end())
+    }
+}
+
+/// Produces a unified diff of a single file, or an empty string when it is unchanged.
+fn unified_diff(path: &str, expected: &str, actual: &str) -> String {
+    if expected == actual {
+        return String::new();
+    }
+
+    TextDiff::from_lines(expected, actual)
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .header(&format!("generated/{}", path), path)
+        .to_string()
+}
@@ -0,0 +1,156 @@
+use serde::Serialize;
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{constants::SUB_FOLDER, utils::diff_report::ModificationReport, Result};
+
+/**
+ * Finds every Move package below a folder.
+ *
+ * A package is a folder with a `Move.toml` file and a `sources` folder. Packages are not searched
+ * for nested packages, and hidden folders such as `.git` are skipped. Symbolic links are not
+ * followed, so a link cycle cannot make the search loop.
+ *
+ * # Parameters
+ * - `root`: The folder to search, which may itself be a package.
+ *
+ * # Returns
+ * - `Ok(Vec<PathBuf>)`: The package folders, sorted by path.
+ * - `Err(TokenGenErrors::FileIoError)`: If a folder cannot be read.
+ */
+pub fn discover_packages(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut packages = Vec::new();
+    collect_packages(root, &mut packages)?;
+    packages.sort();
+    Ok(packages)
+}
+
+// Adds the folder if it is a package, or searches its subfolders otherwise.
+fn collect_packages(dir: &Path, packages: &mut Vec<PathBuf>) -> Result<()> {
+    if dir.join("Move.toml").is_file() && dir.join(SUB_FOLDER).is_dir() {
+        packages.push(dir.to_path_buf());
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let is_hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        // The entry's own file type, unlike `Path::is_dir`, does not follow symbolic links.
+        if entry.file_type()?.is_dir() && !is_hidden {
+            collect_packages(&path, packages)?;
+        }
+    }
+    Ok(())
+}
+
+/// Outcome of verifying a single discovered package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyStatus {
+    /// The package matches the package this tool generates for its header.
+    Verified,
+    /// The package has a generator header, but its files differ from the generated ones.
+    Modified,
+    /// The package has no generator header, so it was not generated by this tool.
+    NotGenerated,
+    /// The package could not be verified, e.g. because the RPC service failed.
+    Failed,
+}
+
+impl fmt::Display for VerifyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            VerifyStatus::Verified => "verified",
+            VerifyStatus::Modified => "modified",
+            VerifyStatus::NotGenerated => "not generated",
+            VerifyStatus::Failed => "failed",
+        })
+    }
+}
+
+/// Result of verifying a single discovered package.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyEntry {
+    /// Folder of the package.
+    pub path: String,
+
+    /// Outcome of the verification.
+    pub status: VerifyStatus,
+
+    /// File name of the verified main module.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+
+    /// How a modified package differs from the generated one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<ModificationReport>,
+
+    /// Error message for a package that could not be verified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Per-package results of a `verify --recursive` run.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyReport {
+    /// Folder the packages were discovered in.
+    pub root: String,
+
+    /// Results, sorted by package path.
+    pub entries: Vec<VerifyEntry>,
+}
+
+impl VerifyReport {
+    /// Returns the number of packages with the given status.
+    pub fn count(&self, status: VerifyStatus) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == status)
+            .count()
+    }
+
+    /// Returns whether every package was verified.
+    pub fn all_verified(&self) -> bool {
+        self.count(VerifyStatus::Verified) == self.entries.len()
+    }
+}
+
+impl fmt::Display for VerifyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  {:<14} PACKAGE", "STATUS")?;
+        for entry in &self.entries {
+            let details = match (&entry.report, &entry.error, &entry.file_name) {
+                (Some(report), _, _) => format!(
+                    " ({} missing, {} extra, {} modified)",
+                    report.missing.len(),
+                    report.extra.len(),
+                    report.modified.len()
+                ),
+                (_, Some(error), _) => format!(": {}", error.lines().next().unwrap_or_default()),
+                (_, _, Some(file_name)) => format!(" ({})", file_name),
+                _ => String::new(),
+            };
+            writeln!(
+                f,
+                "  {:<14} {}{}",
+                entry.status.to_string(),
+                entry.path,
+                details
+            )?;
+        }
+
+        write!(
+            f,
+            "{} verified, {} modified, {} not generated, {} failed",
+            self.count(VerifyStatus::Verified),
+            self.count(VerifyStatus::Modified),
+            self.count(VerifyStatus::NotGenerated),
+            self.count(VerifyStatus::Failed)
+        )
+    }
+}
@@ -0,0 +1,437 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, io, time::Duration};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+};
+use tokio_rustls::rustls::pki_types::ServerName;
+use url::{Position, Url};
+
+use crate::{
+    constants::{DEFAULT_RPC_TIMEOUT_SECS, FULLNODE_URLS, MAX_FULLNODE_RESPONSE_BYTES},
+    errors::TokenGenErrors,
+    utils::{onchain::CompiledPackage, tls::TlsOptions},
+    Result,
+};
+
+/// `CoinMetadata` object of a coin type, as returned by `suix_getCoinMetadata`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinMetadata {
+    /// ID of the metadata object.
+    pub id: Option<String>,
+    pub decimals: u8,
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    pub icon_url: Option<String>,
+}
+
+/**
+ * Client of the JSON-RPC API of a Sui fullnode.
+ *
+ * Requests are sent as HTTP/1.1 `POST`s, over TLS for `https` URLs, with one connection per
+ * request, to the path and query of the URL. Every request gets the configured deadline. Responses
+ * are capped at `MAX_FULLNODE_RESPONSE_BYTES`; redirects are reported as errors rather than
+ * followed, and proxies are not used, so the URL must reach the fullnode directly.
+ */
+#[derive(Debug, Clone)]
+pub struct FullnodeClient {
+    url: Url,
+    timeout: Duration,
+}
+
+impl FullnodeClient {
+    /**
+     * Creates a client for the fullnode at a URL.
+     *
+     * # Parameters
+     * - `url`: The `http` or `https` URL of the fullnode JSON-RPC API.
+     *
+     * # Returns
+     * - `Ok(FullnodeClient)`: The client.
+     * - `Err(TokenGenErrors::InvalidUrl)`: If the URL is not an `http` or `https` URL with a host.
+     */
+    pub fn new(url: &str) -> Result<Self> {
+        let parsed_url = Url::parse(url).map_err(|e| TokenGenErrors::InvalidUrl(e.to_string()))?;
+        if !matches!(parsed_url.scheme(), "http" | "https") || parsed_url.host_str().is_none() {
+            return Err(TokenGenErrors::InvalidUrl(format!(
+                "{} is not an http or https fullnode URL",
+                url
+            )));
+        }
+
+        Ok(Self {
+            url: parsed_url,
+            timeout: Duration::from_secs(DEFAULT_RPC_TIMEOUT_SECS),
+        })
+    }
+
+    /**
+     * Creates a client for the public fullnode of an environment, unless a URL is given.
+     *
+     * # Parameters
+     * - `environment`: The blockchain environment (`mainnet`, `devnet`, `testnet`).
+     * - `url`: A fullnode URL overriding the public one, e.g. a local network or fixture server.
+     */
+    pub fn for_environment(environment: &str, url: Option<&str>) -> Result<Self> {
+        let url = match url {
+            Some(url) => url,
+            None => FULLNODE_URLS
+                .iter()
+                .find(|(name, _)| *name == environment)
+                .map(|(_, url)| *url)
+                .ok_or_else(|| {
+                    TokenGenErrors::InvalidInput(format!(
+                        "No fullnode is known for environment {}",
+                        environment
+                    ))
+                })?,
+        };
+        Self::new(url)
+    }
+
+    /// Sets the deadline of every request.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the URL of the fullnode.
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /**
+     * Calls a JSON-RPC method of the fullnode.
+     *
+     * # Parameters
+     * - `method`: The method name, e.g. `sui_getObject`.
+     * - `params`: The positional parameters.
+     *
+     * # Returns
+     * - `Ok(Value)`: The `result` of the response.
+     * - `Err(TokenGenErrors::FullnodeError)`: If the request fails, times out or returns an error.
+     */
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let body = tokio::time::timeout(self.timeout, self.post(request.to_string().as_bytes()))
+            .await
+            .map_err(|_| {
+                TokenGenErrors::FullnodeError(format!(
+                    "{} timed out after {:?}",
+                    method, self.timeout
+                ))
+            })?
+            .map_err(|e| TokenGenErrors::FullnodeError(format!("{}: {}", self.url, e)))?;
+
+        let mut response: Value = serde_json::from_slice(&body).map_err(|e| {
+            TokenGenErrors::FullnodeError(format!("Invalid response to {}: {}", method, e))
+        })?;
+        if let Some(error) = response.get("error") {
+            let message = error
+                .get("message")
+                .and_then(Value::as_str)
+                .map_or_else(|| error.to_string(), str::to_string);
+            return Err(TokenGenErrors::FullnodeError(format!(
+                "{}: {}",
+                method, message
+            )));
+        }
+
+        Ok(response["result"].take())
+    }
+
+    /**
+     * Fetches the bytecode of every module of a published package.
+     *
+     * # Parameters
+     * - `package_id`: The normalized ID of the package.
+     *
+     * # Returns
+     * - `Ok(BTreeMap<String, Vec<u8>>)`: The bytecode of each module, by module name.
+     * - `Err(TokenGenErrors::VerificationError)`: If the object does not exist or is not a package.
+     */
+    pub async fn get_package_modules(&self, package_id: &str) -> Result<BTreeMap<String, Vec<u8>>> {
+        let object = self
+            .call("sui_getObject", json!([package_id, { "showBcs": true }]))
+            .await?;
+
+        let bcs = &object["data"]["bcs"];
+        if bcs["dataType"] != "package" {
+            return Err(TokenGenErrors::VerificationError(format!(
+                "No package found at {}",
+                package_id
+            )));
+        }
+
+        bcs["moduleMap"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(name, bytecode)| {
+                let bytecode = bytecode
+                    .as_str()
+                    .and_then(|bytecode| STANDARD.decode(bytecode).ok())
+                    .ok_or_else(|| {
+                        TokenGenErrors::FullnodeError(format!(
+                            "Invalid bytecode of module {}",
+                            name
+                        ))
+                    })?;
+                Ok((name.clone(), bytecode))
+            })
+            .collect()
+    }
+
+    /**
+     * Fetches the `CoinMetadata` of a coin type.
+     *
+     * # Parameters
+     * - `coin_type`: The coin type, e.g. `0x2::sui::SUI`.
+     *
+     * # Returns
+     * - `Ok(Some(CoinMetadata))`: The metadata.
+     * - `Ok(None)`: If the coin type has no metadata.
+     */
+    pub async fn get_coin_metadata(&self, coin_type: &str) -> Result<Option<CoinMetadata>> {
+        let metadata = self
+            .call("suix_getCoinMetadata", json!([coin_type]))
+            .await?;
+        if metadata.is_null() {
+            return Ok(None);
+        }
+
+        serde_json::from_value(metadata).map(Some).map_err(|e| {
+            TokenGenErrors::FullnodeError(format!("Invalid CoinMetadata of {}: {}", coin_type, e))
+        })
+    }
+
+    /**
+     * Fetches the total supply of a coin type, in base units.
+     *
+     * # Parameters
+     * - `coin_type`: The coin type, e.g. `0x2::sui::SUI`.
+     *
+     * # Returns
+     * - `Ok(u64)`: The amount of coins in circulation.
+     * - `Err(TokenGenErrors::FullnodeError)`: If the request fails or the supply is missing.
+     */
+    pub async fn get_total_supply(&self, coin_type: &str) -> Result<u64> {
+        let supply = self.call("suix_getTotalSupply", json!([coin_type])).await?;
+
+        // Amounts are returned as decimal strings, since they may not fit in a JSON number.
+        supply["value"]
+            .as_str()
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| {
+                TokenGenErrors::FullnodeError(format!("Invalid total supply of {}", coin_type))
+            })
+    }
+
+    /**
+     * Returns whether an object is immutable, i.e. frozen.
+     *
+     * # Parameters
+     * - `object_id`: The ID of the object.
+     */
+    pub async fn is_object_immutable(&self, object_id: &str) -> Result<bool> {
+        let object = self
+            .call("sui_getObject", json!([object_id, { "showOwner": true }]))
+            .await?;
+        Ok(object["data"]["owner"] == "Immutable")
+    }
+
+    /**
+     * Builds the transaction data publishing a package, with a gas coin chosen by the fullnode.
+     *
+     * # Parameters
+     * - `sender`: The address of the publisher, which pays for gas.
+     * - `package`: The compiled package.
+     * - `gas_budget`: The gas budget, in MIST.
+     *
+     * # Returns
+     * - `Ok(Vec<u8>)`: The BCS-encoded transaction data, to be signed by the sender.
+     * - `Err(TokenGenErrors::FullnodeError)`: If the fullnode cannot build the transaction.
+     */
+    pub async fn unsafe_publish(
+        &self,
+        sender: &str,
+        package: &CompiledPackage,
+        gas_budget: u64,
+    ) -> Result<Vec<u8>> {
+        let modules: Vec<String> = package
+            .modules
+            .iter()
+            .map(|module| STANDARD.encode(module))
+            .collect();
+        let transaction = self
+            .call(
+                "unsafe_publish",
+                json!([
+                    sender,
+                    modules,
+                    package.dependencies,
+                    Value::Null,
+                    gas_budget.to_string()
+                ]),
+            )
+            .await?;
+
+        transaction["txBytes"]
+            .as_str()
+            .and_then(|tx_bytes| STANDARD.decode(tx_bytes).ok())
+            .ok_or_else(|| {
+                TokenGenErrors::FullnodeError("unsafe_publish returned no txBytes".to_string())
+            })
+    }
+
+    /**
+     * Executes a signed transaction and waits until the fullnode has applied it.
+     *
+     * # Parameters
+     * - `tx_bytes`: The BCS-encoded transaction data.
+     * - `signature`: The serialized signature of the sender.
+     *
+     * # Returns
+     * - `Ok(Value)`: The transaction block response, with its effects and object changes.
+     * - `Err(TokenGenErrors::FullnodeError)`: If the fullnode rejects the transaction.
+     */
+    pub async fn execute_transaction(&self, tx_bytes: &[u8], signature: &str) -> Result<Value> {
+        self.call(
+            "sui_executeTransactionBlock",
+            json!([
+                STANDARD.encode(tx_bytes),
+                [signature],
+                { "showEffects": true, "showObjectChanges": true },
+                "WaitForLocalExecution"
+            ]),
+        )
+        .await
+    }
+
+    // Sends a request body to the fullnode and returns the response body.
+    async fn post(&self, body: &[u8]) -> io::Result<Vec<u8>> {
+        let host = self.url.host_str().unwrap_or_default();
+        let port = self
+            .url
+            .port_or_known_default()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "The URL has no port"))?;
+
+        let mut request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            &self.url[Position::BeforePath..Position::AfterQuery],
+            host,
+            body.len()
+        )
+        .into_bytes();
+        request.extend_from_slice(body);
+
+        let stream = TcpStream::connect(format!("{}:{}", host, port)).await?;
+        let response = if self.url.scheme() == "https" {
+            let server_name = ServerName::try_from(host.trim_matches(['[', ']']).to_string())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            let connector = TlsOptions::default().connector().map_err(io::Error::from)?;
+            exchange(connector.connect(server_name, stream).await?, &request).await?
+        } else {
+            exchange(stream, &request).await?
+        };
+
+        parse_response(&response)
+    }
+}
+
+// Writes a request and reads the whole response, until the server closes the connection or the
+// response exceeds `MAX_FULLNODE_RESPONSE_BYTES`.
+async fn exchange<S: AsyncRead + AsyncWrite + Unpin>(
+    mut stream: S,
+    request: &[u8],
+) -> io::Result<Vec<u8>> {
+    stream.write_all(request).await?;
+    stream.flush().await?;
+
+    let mut response = Vec::new();
+    stream
+        .take(MAX_FULLNODE_RESPONSE_BYTES + 1)
+        .read_to_end(&mut response)
+        .await?;
+    if response.len() as u64 > MAX_FULLNODE_RESPONSE_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("The response exceeds {} bytes", MAX_FULLNODE_RESPONSE_BYTES),
+        ));
+    }
+    Ok(response)
+}
+
+// Checks the status of an HTTP response and returns its body, decoding chunked bodies.
+fn parse_response(response: &[u8]) -> io::Result<Vec<u8>> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(|| invalid("Incomplete HTTP response"))?;
+    let head = String::from_utf8_lossy(&response[..header_end]);
+    let body = &response[header_end + 4..];
+
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(|| invalid("Invalid HTTP status line"))?;
+    let header = |name: &str| {
+        head.lines().skip(1).find_map(|line| {
+            line.split_once(':')
+                .filter(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.trim().to_string())
+        })
+    };
+    if (300..400).contains(&status) {
+        return Err(invalid(&format!(
+            "HTTP status {}, redirects are not followed; use the target URL {} instead",
+            status,
+            header("location").unwrap_or_default()
+        )));
+    }
+    if !(200..300).contains(&status) {
+        return Err(invalid(&format!("HTTP status {}", status)));
+    }
+
+    let chunked =
+        header("transfer-encoding").is_some_and(|value| value.eq_ignore_ascii_case("chunked"));
+    if !chunked {
+        return Ok(body.to_vec());
+    }
+
+    let mut decoded = Vec::new();
+    let mut rest = body;
+    loop {
+        let line_end = rest
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .ok_or_else(|| invalid("Incomplete chunked body"))?;
+        let size = std::str::from_utf8(&rest[..line_end])
+            .ok()
+            .and_then(|line| usize::from_str_radix(line.split(';').next()?.trim(), 16).ok())
+            .ok_or_else(|| invalid("Invalid chunk size"))?;
+        if size == 0 {
+            return Ok(decoded);
+        }
+
+        let chunk = rest
+            .get(line_end + 2..line_end + 2 + size)
+            .ok_or_else(|| invalid("Incomplete chunked body"))?;
+        decoded.extend_from_slice(chunk);
+        rest = rest.get(line_end + 4 + size..).unwrap_or_default();
+    }
+}
@@ -1,9 +1,8 @@
-use crate::{
-    constants::{SUB_FOLDER, TEST_FOLDER},
-    utils::helpers::sanitize_name,
-    Result,
+use crate::{errors::TokenGenErrors, utils::client::responses::GeneratedFile, Result};
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
 };
-use std::fs;
 
 /// Handles file and directory operations for token contract generation
 pub struct ContractGenerator {
@@ -17,104 +16,70 @@ impl ContractGenerator {
     }
 
     /**
-     * Creates a contract file within a specific subfolder of the base folder.
+     * Writes the files of a generated package into the base folder.
      *
-     * This function performs the following:
-     * 1. Sanitizes the provided contract name to ensure it contains only alphanumeric characters.
-     * 2. Constructs the path to the target file location (base folder + subfolder).
-     * 3. Writes the provided token template content into the target file.
+     * The layout is taken from the files themselves, so packages with additional modules,
+     * documentation or scripts are written as they were generated.
      *
      * # Parameters
-     * - `name`: The name of the token, used to generate a sanitized file name.
-     * - `base_folder`: The root folder where the contract file will be created.
-     * - `token_template`: The content of the token contract to be written to the file.
-     * - `sub_folder`: The subfolder inside the base folder where the file will be created.
+     * - `files`: The files to write, with paths relative to the base folder.
      *
      * # Returns
-     * - `Ok(())` if the file is created successfully.
+     * - `Ok(Vec<String>)` with the paths of the created files, in the given order.
+     * - `Err(TokenGenErrors::FailedToCreateTokenContract)` if there are no files, or a path is
+     *   absolute or leaves the base folder.
      * - `Err` if there is an issue with file creation.
      */
-    pub fn create_contract_file(
-        &self,
-        name: &str,
-        token_template: &str,
-        sub_folder: &str,
-    ) -> Result<()> {
-        // Sanitize the name to create a safe file name (alphanumeric only).
-        let slug: String = sanitize_name(name);
+    pub fn write_files(&self, files: &[GeneratedFile]) -> Result<Vec<String>> {
+        if files.is_empty() {
+            return Err(TokenGenErrors::FailedToCreateTokenContract(
+                "no files were generated".to_string(),
+            ));
+        }
 
-        // Construct the path for the contract file.
-        let sources_folder: String = format!("{}/{}", self.base_folder, sub_folder);
-        let file_name: String = format!("{}/{}.move", sources_folder, slug.to_lowercase());
+        // Check every path before writing, so an unsafe file does not leave a partial package.
+        let paths = files
+            .iter()
+            .map(|file| self.file_path(&file.path))
+            .collect::<Result<Vec<_>>>()?;
 
-        // Write the token template content to the file.
-        fs::write(&file_name, token_template)?;
-        Ok(())
+        paths
+            .iter()
+            .zip(files)
+            .map(|(path, file)| {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, &file.contents)?;
+                Ok(path.display().to_string())
+            })
+            .collect()
     }
 
     /**
-     * Creates the base folder for the contract and its subfolders.
-     *
-     * This function sets up the required directory structure for the token contract:
-     * - A main folder for the contract (base folder).
-     * - Subfolders such as `sources` and `tests` for organizing contract files.
-     *
-     * # Parameters
-     * - `base_folder`: The root folder where subfolders will be created.
-     *
-     * # Returns
-     * - `Ok(())` if all directories are created successfully.
-     * - `Err` if any directory creation fails.
-     */
-    pub fn create_base_folder(&self) -> Result<()> {
-        // Create the main `sources` and `tests` subdirectories.
-        self.create_dir(SUB_FOLDER)?;
-        self.create_dir(TEST_FOLDER)?;
-        Ok(())
-    }
-
-    /**
-     * Creates a `Move.toml` file from the RPC response.
-     *
-     * This function generates a `Move.toml` file, which is essential for defining the Move package structure.
-     *
-     * # Parameters
-     * - `package_name`: The name of the package, used to determine the output file location.
-     * - `toml_content`: The content of the `Move.toml` file to be written.
-     *
-     * # Returns
-     * - `Ok(())` if the file is created successfully.
-     * - `Err` if there is an issue with file creation.
-     */
-    pub fn create_move_toml(&self, toml_content: &str) -> Result<()> {
-        // Construct the file path for `Move.toml`.
-        let file_path: String = format!("{}/Move.toml", self.base_folder);
-
-        // Write the provided TOML content to the file.
-        fs::write(&file_path, toml_content)?;
-        Ok(())
-    }
-
-    /**
-     * Creates a directory within the base folder.
-     *
-     * This utility function constructs the full directory path and creates it.
-     * It ensures the directory structure is created recursively if it does not already exist.
+     * Resolves the path of a generated file within the base folder.
      *
      * # Parameters
-     * - `base_folder`: The root folder where the directory will be created.
-     * - `sub_folder`: The subfolder to be created within the base folder.
+     * - `relative_path`: The path of the file relative to the base folder.
      *
      * # Returns
-     * - `Ok(())` if the directory is created successfully.
-     * - `Err` if there is an issue with directory creation.
+     * - `Ok(PathBuf)` with the path of the file.
+     * - `Err(TokenGenErrors::FailedToCreateTokenContract)` if the path is empty, absolute, or
+     *   contains `..` components.
      */
-    pub fn create_dir(&self, sub_folder: &str) -> Result<()> {
-        // Construct the directory path.
-        let dir: String = format!("{}/{}", self.base_folder, sub_folder);
+    fn file_path(&self, relative_path: &str) -> Result<PathBuf> {
+        let path = Path::new(relative_path);
+        let is_safe = path.components().next().is_some()
+            && path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
 
-        // Create the directory and all necessary parent directories.
-        fs::create_dir_all(&dir)?;
-        Ok(())
+        if !is_safe {
+            return Err(TokenGenErrors::FailedToCreateTokenContract(format!(
+                "unsafe file path: {}",
+                relative_path
+            )));
+        }
+        Ok(Path::new(&self.base_folder).join(path))
     }
 }
@@ -0,0 +1,188 @@
+use git2::{
+    build::{CheckoutBuilder, RepoBuilder},
+    Cred, FetchOptions, Oid, RemoteCallbacks, Repository,
+};
+use std::path::{Component, Path, PathBuf};
+use url::Url;
+
+use crate::{
+    errors::TokenGenErrors,
+    utils::{atomic::AtomicFileOperation, helpers::unique_temp_dir},
+    Result,
+};
+
+/// A Move package in a Git repository, as given to `verify --url`.
+#[derive(Debug, Clone, Default)]
+pub struct GitSource {
+    /// URL of the repository: `https://`, `ssh://`, `git://`, `file://` or `user@host:path`.
+    pub url: String,
+    /// Branch, tag or commit to check out. Defaults to the remote `HEAD`.
+    pub reference: Option<String>,
+    /// Folder of the package inside the repository. Defaults to the repository root.
+    pub subdir: Option<String>,
+}
+
+impl GitSource {
+    /**
+     * Creates a source for the package at the root of the default branch of a repository.
+     *
+     * # Arguments
+     * - `url`: The URL of the repository.
+     */
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /**
+     * Returns the web link of a file of the package at the given commit.
+     *
+     * Links are only known for GitHub and GitLab repositories cloned over HTTP(S).
+     *
+     * # Arguments
+     * - `file`: The path of the file relative to the package folder.
+     * - `commit`: The checked-out commit.
+     */
+    pub fn file_link(&self, file: &str, commit: &str) -> Option<String> {
+        let url = Url::parse(&self.url).ok()?;
+        if !matches!(url.scheme(), "http" | "https")
+            || !matches!(
+                url.host_str()?.trim_start_matches("www."),
+                "github.com" | "gitlab.com"
+            )
+        {
+            return None;
+        }
+
+        let repository = self.url.trim_end_matches('/').trim_end_matches(".git");
+        let folder = self
+            .subdir
+            .as_deref()
+            .map(|subdir| format!("{}/", subdir.trim_matches('/')))
+            .unwrap_or_default();
+        Some(format!("{}/blob/{}/{}{}", repository, commit, folder, file))
+    }
+}
+
+/// A repository cloned into a temporary directory, removed when dropped.
+pub struct GitCheckout {
+    /// Folder of the package inside the clone.
+    pub package_path: PathBuf,
+    /// Full hash of the checked-out commit.
+    pub commit: String,
+    // Never committed, so the clone is removed once the checkout is dropped.
+    _cleanup: AtomicFileOperation,
+}
+
+impl GitCheckout {
+    /**
+     * Clones a repository into a temporary directory and checks out the requested reference.
+     *
+     * The reference is looked up as a remote branch, then as a tag, then as a full or
+     * abbreviated commit hash. SSH repositories authenticate with the SSH agent.
+     *
+     * # Arguments
+     * - `source`: The repository, reference and package folder.
+     *
+     * # Returns
+     * - `Ok(GitCheckout)`: The checkout, with the path of the package folder.
+     * - `Err(TokenGenErrors::GitError)`: If cloning fails or the reference does not exist.
+     * - `Err(TokenGenErrors::InvalidPath)`: If the package folder is outside the repository or missing.
+     */
+    pub fn new(source: &GitSource) -> Result<Self> {
+        let subdir = source.subdir.as_deref().map(validate_subdir).transpose()?;
+
+        let clone_dir = unique_temp_dir("clone");
+        let clone_path = clone_dir
+            .to_str()
+            .ok_or(TokenGenErrors::PathConversionError)?;
+        let cleanup = AtomicFileOperation::new(clone_path);
+
+        // libgit2 asks again after every rejected credential, so only offer the agent's key once.
+        let mut callbacks = RemoteCallbacks::new();
+        let mut attempts = 0;
+        callbacks.credentials(move |_, username, _| {
+            attempts += 1;
+            if attempts > 1 {
+                return Err(git2::Error::from_str(
+                    "SSH authentication failed: the remote rejected the key from the SSH agent",
+                ));
+            }
+            Cred::ssh_key_from_agent(username.unwrap_or("git"))
+        });
+        let mut fetch_options = FetchOptions::new();
+        fetch_options.remote_callbacks(callbacks);
+
+        let repo = RepoBuilder::new()
+            .fetch_options(fetch_options)
+            .clone(&source.url, &clone_dir)?;
+
+        let commit = match &source.reference {
+            Some(reference) => {
+                let commit = resolve_reference(&repo, reference)?;
+                repo.checkout_tree(
+                    &repo.find_object(commit, None)?,
+                    Some(CheckoutBuilder::new().force()),
+                )?;
+                repo.set_head_detached(commit)?;
+                commit
+            }
+            None => repo.head()?.peel_to_commit()?.id(),
+        };
+
+        let package_path = match subdir {
+            Some(subdir) => clone_dir.join(subdir),
+            None => clone_dir.clone(),
+        };
+        if !package_path.is_dir() {
+            return Err(TokenGenErrors::InvalidPath(format!(
+                "{} not found in the repository",
+                source.subdir.as_deref().unwrap_or_default()
+            )));
+        }
+
+        Ok(Self {
+            package_path,
+            commit: commit.to_string(),
+            _cleanup: cleanup,
+        })
+    }
+}
+
+// Finds the commit of a remote branch, tag or commit hash.
+fn resolve_reference(repo: &Repository, reference: &str) -> Result<Oid> {
+    let candidates = [
+        format!("refs/remotes/origin/{}", reference),
+        format!("refs/tags/{}", reference),
+        reference.to_string(),
+    ];
+
+    for candidate in &candidates {
+        if let Ok(object) = repo.revparse_single(candidate) {
+            return Ok(object.peel_to_commit()?.id());
+        }
+    }
+
+    Err(TokenGenErrors::GitError(git2::Error::from_str(&format!(
+        "reference '{}' is not a branch, tag or commit of the repository",
+        reference
+    ))))
+}
+
+// Rejects package folders that would leave the repository.
+fn validate_subdir(subdir: &str) -> Result<&Path> {
+    let path = Path::new(subdir);
+    if path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        Ok(path)
+    } else {
+        Err(TokenGenErrors::InvalidPath(format!(
+            "{} must be a relative path inside the repository",
+            subdir
+        )))
+    }
+}
@@ -1,14 +1,24 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
 use url::Url;
 
-use crate::{errors::TokenGenErrors, Result};
+use crate::{constants::TLS_SCHEME, errors::TokenGenErrors, Result};
+
+// Counter used to give every temporary directory of this process its own name.
+static TEMP_DIR_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 // Define regex patterns as constants using Lazy
-static REPOSITORY_URL_PATTERN: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"^https?://(www\.)?(github|gitlab)\.com/[\w\-]+/[\w\-]+/?$")
-        .expect("Invalid pattern")
-});
+// Matches the scp-like syntax of SSH repositories, e.g. `git@git.example.com:team/token.git`.
+static SCP_URL_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[\w.\-]+@[\w.\-]+:[\w.\-~/]*[\w\-]$").expect("Invalid pattern"));
+
+// Matches a Sui object or package ID, with or without leading zeros.
+static SUI_ADDRESS_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^0x[0-9a-fA-F]{1,64}$").expect("Invalid pattern"));
 
 static RPC_URL_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^([0-9]{1,3}\.){3}[0-9]{1,3}:\d+$").expect("Invalid pattern"));
@@ -29,7 +39,37 @@ pub fn sanitize_name(name: &str) -> String {
 }
 
 /**
- * Validates whether a given URL is a valid GitHub or GitLab repository URL.
+ * Converts a supply in whole tokens to base units, the amount held by coins on chain.
+ *
+ * # Arguments
+ * - `supply`: The supply, in whole tokens.
+ * - `decimals`: The number of decimal places of the token.
+ *
+ * # Returns
+ * - `Some(u64)`: The supply in base units.
+ * - `None`: If it does not fit in a coin balance (`u64`).
+ */
+pub fn to_base_units(supply: u64, decimals: u8) -> Option<u64> {
+    10u64.checked_pow(decimals.into())?.checked_mul(supply)
+}
+
+/**
+ * Returns a new path in the system temporary directory, unique within this process.
+ *
+ * # Arguments
+ * - `prefix`: Names what the directory is for, e.g. `clone` or `build`.
+ */
+pub fn unique_temp_dir(prefix: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "sui-token-gen-{}-{}-{}",
+        prefix,
+        std::process::id(),
+        TEMP_DIR_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/**
+ * Validates whether a given URL can be cloned as a Git repository.
  *
  * # Arguments
  * - `url`: A string slice representing the URL to be validated.
@@ -39,27 +79,128 @@ pub fn sanitize_name(name: &str) -> String {
  * - `Err(TokenGenErrors::InvalidGitUrl)`: If the URL is invalid.
  *
  * # Validation Criteria
- * - The URL must start with `http` or `https`.
- * - The domain must be `github.com` or `gitlab.com`.
- * - The path must follow the pattern `/user/repository`, where `user` and `repository` consist of alphanumeric characters, underscores, or hyphens.
- * - An optional trailing `/` is allowed.
+ * - `http`, `https`, `ssh` and `git` URLs must have a host and a repository path, on any server
+ *   (e.g. GitHub, GitLab, Gitea or Bitbucket).
+ * - `file` URLs must have a path.
+ * - The scp-like SSH syntax `user@host:path` is accepted as well.
  */
 pub fn is_valid_repository_url(url: &str) -> Result<()> {
-    if !REPOSITORY_URL_PATTERN.is_match(url) {
+    if SCP_URL_PATTERN.is_match(url) {
+        return Ok(());
+    }
+
+    let parsed_url = Url::parse(url).map_err(|_| TokenGenErrors::InvalidGitUrl)?;
+    let has_path = !parsed_url.path().trim_matches('/').is_empty();
+    let is_valid = match parsed_url.scheme() {
+        "http" | "https" | "ssh" | "git" => {
+            parsed_url.host_str().is_some_and(|host| !host.is_empty()) && has_path
+        }
+        "file" => has_path,
+        _ => false,
+    };
+
+    if !is_valid {
         return Err(TokenGenErrors::InvalidGitUrl);
     }
     Ok(())
 }
 
-/// Validates and extracts the RPC URL format.
+/**
+ * Returns whether a repository URL points to the local filesystem.
+ *
+ * The URL is parsed, so spellings such as `FILE:///path` or `file:/path` are detected as well.
+ *
+ * # Arguments
+ * - `url`: A repository URL accepted by `is_valid_repository_url`.
+ */
+pub fn is_local_repository_url(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|url| url.scheme() == "file")
+}
+
+/**
+ * Returns the lowercase host of a repository URL, including the scp-like `user@host:path` syntax.
+ *
+ * # Arguments
+ * - `url`: A repository URL accepted by `is_valid_repository_url`.
+ *
+ * # Returns
+ * - `Some(String)`: The host the repository is cloned from.
+ * - `None`: If the URL has no host, e.g. a `file://` URL.
+ */
+pub fn repository_host(url: &str) -> Option<String> {
+    if SCP_URL_PATTERN.is_match(url) {
+        let (_, rest) = url.split_once('@')?;
+        let (host, _) = rest.split_once(':')?;
+        return Some(host.to_lowercase());
+    }
+
+    Url::parse(url)
+        .ok()?
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .map(str::to_lowercase)
+}
+
+/**
+ * Validates a Sui address and normalizes it to `0x` followed by 64 lowercase hex digits.
+ *
+ * # Arguments
+ * - `address`: The package or object ID, e.g. `0x2` or a full 32-byte ID.
+ *
+ * # Returns
+ * - `Ok(String)`: The normalized address.
+ * - `Err(TokenGenErrors::InvalidInput)`: If the address is not `0x` followed by 1 to 64 hex digits.
+ */
+pub fn normalize_sui_address(address: &str) -> Result<String> {
+    if !SUI_ADDRESS_PATTERN.is_match(address) {
+        return Err(TokenGenErrors::InvalidInput(format!(
+            "Invalid Sui address '{}', expected 0x followed by up to 64 hex digits",
+            address
+        )));
+    }
+    Ok(format!("0x{:0>64}", address[2..].to_lowercase()))
+}
+
+/**
+ * Validates an RPC address and normalizes it to `host:port`.
+ *
+ * Accepts a bare `host:port` address, where the host is a hostname, an IPv4 address or a
+ * bracketed IPv6 address (e.g. `tokengen.internal:5001`, `[::1]:5001`), or a URL such as
+ * `http://localhost:5001`. Hostnames are resolved when connecting. A `tls://host:port` address
+ * selects the TLS transport and keeps its scheme, and so does an `https://` URL, which is never
+ * connected to in plaintext.
+ *
+ * # Arguments
+ * - `url`: The RPC address provided with `--rpc` or from the configuration.
+ *
+ * # Returns
+ * - `Ok(String)`: The `host:port` address, with IPv6 hosts in brackets, prefixed with `tls://` for TLS.
+ * - `Err(TokenGenErrors::InvalidRpcUrl)`: If the address has no host or port, or cannot be parsed.
+ */
 pub fn validate_rpc_url(url: &str) -> Result<String> {
     if RPC_URL_PATTERN.is_match(url) {
         return Ok(url.to_string());
     }
 
-    let parsed_url = Url::parse(url).map_err(|_| TokenGenErrors::InvalidRpcUrl)?;
-    let host = parsed_url.host_str().ok_or(TokenGenErrors::InvalidRpcUrl)?;
-    let port = parsed_url.port().ok_or(TokenGenErrors::InvalidRpcUrl)?;
+    // Bare addresses are parsed with a scheme that has no default port, so the port is kept as given.
+    let parsed_url = if url.contains("://") {
+        Url::parse(url)
+    } else {
+        Url::parse(&format!("tcp://{}", url))
+    }
+    .map_err(|_| TokenGenErrors::InvalidRpcUrl)?;
+
+    let host = parsed_url
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .ok_or(TokenGenErrors::InvalidRpcUrl)?;
+    let port = parsed_url
+        .port_or_known_default()
+        .ok_or(TokenGenErrors::InvalidRpcUrl)?;
+
+    if url.starts_with(TLS_SCHEME) || parsed_url.scheme() == "https" {
+        return Ok(format!("{}{}:{}", TLS_SCHEME, host, port));
+    }
 
     Ok(format!("{}:{}", host, port))
 }
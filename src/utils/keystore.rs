@@ -0,0 +1,129 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use std::{env, fs, path::PathBuf};
+
+use crate::{
+    constants::SUI_KEYSTORE_PATH, errors::TokenGenErrors, utils::helpers::normalize_sui_address,
+    Result,
+};
+
+// BLAKE2b with a 32-byte digest, which Sui uses for addresses and transaction digests.
+type Blake2b256 = Blake2b<U32>;
+
+// Signature scheme flag of Ed25519 keys, prefixed to keys, signatures and address preimages.
+const ED25519_FLAG: u8 = 0x00;
+
+// Intent of a transaction signature: transaction data, version 0, for the Sui app.
+const TRANSACTION_INTENT: [u8; 3] = [0, 0, 0];
+
+/// Ed25519 key pair of a Sui account, used to sign transactions.
+#[derive(Debug)]
+pub struct SuiKeypair {
+    key: Ed25519KeyPair,
+}
+
+impl SuiKeypair {
+    /**
+     * Creates a key pair from its 32-byte Ed25519 private key.
+     *
+     * # Parameters
+     * - `seed`: The private key.
+     *
+     * # Returns
+     * - `Ok(SuiKeypair)`: The key pair.
+     * - `Err(TokenGenErrors::InvalidKeystore)`: If the private key is not 32 bytes long.
+     */
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        let key = Ed25519KeyPair::from_seed_unchecked(seed).map_err(|_| {
+            TokenGenErrors::InvalidKeystore(format!(
+                "Expected a 32-byte Ed25519 private key, found {} bytes",
+                seed.len()
+            ))
+        })?;
+        Ok(Self { key })
+    }
+
+    /// Returns the Sui address of the key, `0x` followed by 64 lowercase hex digits.
+    pub fn address(&self) -> String {
+        let mut hasher = Blake2b256::new();
+        hasher.update([ED25519_FLAG]);
+        hasher.update(self.key.public_key().as_ref());
+        format!("0x{:x}", hasher.finalize())
+    }
+
+    /**
+     * Signs transaction data, as returned by the `unsafe_*` methods of a fullnode.
+     *
+     * # Parameters
+     * - `tx_bytes`: The BCS-encoded transaction data.
+     *
+     * # Returns
+     * The serialized signature accepted by `sui_executeTransactionBlock`: the base64 encoding of
+     * the scheme flag, the signature of the transaction intent digest and the public key.
+     */
+    pub fn sign_transaction(&self, tx_bytes: &[u8]) -> String {
+        let mut hasher = Blake2b256::new();
+        hasher.update(TRANSACTION_INTENT);
+        hasher.update(tx_bytes);
+
+        let mut signature = vec![ED25519_FLAG];
+        signature.extend_from_slice(self.key.sign(&hasher.finalize()).as_ref());
+        signature.extend_from_slice(self.key.public_key().as_ref());
+        STANDARD.encode(signature)
+    }
+}
+
+/// Returns the keystore of the Sui CLI, `~/.sui/sui_config/sui.keystore`, if the home folder is known.
+pub fn default_keystore_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(SUI_KEYSTORE_PATH))
+}
+
+/**
+ * Loads a key from a Sui keystore file.
+ *
+ * The keystore is a JSON list of base64-encoded keys, each made of a signature scheme flag and the
+ * private key, as written by `sui keytool`.
+ *
+ * # Parameters
+ * - `path`: The keystore file.
+ * - `sender`: The address of the key to load. Defaults to the first key of the keystore.
+ *
+ * # Returns
+ * - `Ok(SuiKeypair)`: The key.
+ * - `Err(TokenGenErrors::InvalidKeystore)`: If the keystore cannot be read or parsed, or holds no
+ *   Ed25519 key for the sender.
+ */
+pub fn load_keypair(path: &str, sender: Option<&str>) -> Result<SuiKeypair> {
+    let invalid =
+        |message: String| TokenGenErrors::InvalidKeystore(format!("{}: {}", path, message));
+
+    let content = fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+    let keys: Vec<String> = serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+    let sender = sender.map(normalize_sui_address).transpose()?;
+
+    for key in &keys {
+        let bytes = STANDARD
+            .decode(key)
+            .map_err(|e| invalid(format!("Invalid key: {}", e)))?;
+
+        // Keys of other signature schemes cannot sign here, but may be skipped for another sender.
+        let keypair = match bytes.split_first() {
+            Some((&ED25519_FLAG, seed)) => SuiKeypair::from_seed(seed)?,
+            _ if sender.is_some() => continue,
+            _ => return Err(invalid("Only Ed25519 keys are supported".to_string())),
+        };
+
+        if sender
+            .as_ref()
+            .map_or(true, |sender| *sender == keypair.address())
+        {
+            return Ok(keypair);
+        }
+    }
+
+    Err(invalid(match sender {
+        Some(sender) => format!("No Ed25519 key found for {}", sender),
+        None => "The keystore holds no key".to_string(),
+    }))
+}
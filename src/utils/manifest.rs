@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, fs, path::Path};
+
+use crate::{
+    errors::TokenGenErrors,
+    utils::{
+        helpers::sanitize_name,
+        prompts::TokenInfo,
+        validation::{
+            validate_decimals, validate_description, validate_environment, validate_icon_url,
+            validate_max_supply, validate_name, validate_symbol,
+        },
+    },
+    Result,
+};
+
+/// List of tokens to create in a single `create --manifest` run.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenManifest {
+    /// Tokens to create, in order.
+    pub tokens: Vec<ManifestEntry>,
+}
+
+/// A token listed in a manifest. Optional fields default as in non-interactive mode.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestEntry {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub description: Option<String>,
+    pub icon_url: Option<String>,
+    pub max_supply: Option<u64>,
+    pub is_frozen: Option<bool>,
+    pub environment: Option<String>,
+}
+
+impl ManifestEntry {
+    /// Converts the entry to a token configuration, using `environment` when the entry has none.
+    fn into_token(self, environment: &str) -> TokenInfo {
+        TokenInfo {
+            decimals: self.decimals,
+            symbol: self.symbol,
+            name: self.name,
+            description: self.description.unwrap_or_default(),
+            is_frozen: self.is_frozen.unwrap_or_default(),
+            environment: self.environment.unwrap_or_else(|| environment.to_string()),
+            icon_url: self.icon_url,
+            max_supply: self.max_supply,
+        }
+    }
+}
+
+/**
+ * Reads a token manifest and validates every entry.
+ *
+ * The format is chosen from the file extension: `.toml`, `.json`, or `.yaml`/`.yml`. Each entry
+ * needs a `name`, `symbol` and `decimals`; `description` defaults to empty, `icon_url` and
+ * `max_supply` to none, `is_frozen` to `false` and `environment` to the given default. Entries are checked with the same
+ * rules as the prompts, and entries that would be generated into the same folder, or into a folder
+ * that already exists unless `overwrite` is set, are rejected, so nothing is created from a
+ * manifest that cannot be created in full.
+ *
+ * # Parameters
+ * - `path`: Path to the manifest file.
+ * - `environment`: Environment for entries that do not specify one.
+ * - `overwrite`: Whether entries may be generated into folders that already exist.
+ *
+ * # Returns
+ * - `Ok(Vec<TokenInfo>)`: The tokens to create, in manifest order.
+ * - `Err(TokenGenErrors::InvalidManifest)`: If the manifest cannot be parsed or an entry is invalid.
+ * - `Err(TokenGenErrors::FileIoError)`: If the manifest cannot be read.
+ */
+pub fn load_manifest(path: &str, environment: &str, overwrite: bool) -> Result<Vec<TokenInfo>> {
+    let content = fs::read_to_string(path)?;
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let manifest: TokenManifest = match extension.as_str() {
+        "toml" => toml::from_str(&content).map_err(|e| e.to_string()),
+        "json" => serde_json::from_str(&content).map_err(|e| e.to_string()),
+        "yaml" | "yml" => serde_yaml::from_str(&content).map_err(|e| e.to_string()),
+        _ => Err("Unsupported manifest format, expected a .toml, .json, .yaml or .yml file".into()),
+    }
+    .map_err(TokenGenErrors::InvalidManifest)?;
+
+    if manifest.tokens.is_empty() {
+        return Err(TokenGenErrors::InvalidManifest(
+            "The manifest does not list any tokens".to_string(),
+        ));
+    }
+
+    let tokens: Vec<TokenInfo> = manifest
+        .tokens
+        .into_iter()
+        .map(|entry| entry.into_token(environment))
+        .collect();
+
+    validate_manifest(&tokens, overwrite)?;
+    Ok(tokens)
+}
+
+// Checks every entry and reports all problems at once, prefixed with the 1-based entry number.
+fn validate_manifest(tokens: &[TokenInfo], overwrite: bool) -> Result<()> {
+    let mut folders: HashMap<String, usize> = HashMap::new();
+    let mut errors = Vec::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        let entry = index + 1;
+        let mut entry_errors: Vec<String> = [
+            validate_name(&token.name),
+            validate_symbol(&token.symbol),
+            validate_decimals(token.decimals),
+            validate_description(&token.description),
+            validate_environment(&token.environment),
+            token.icon_url.as_deref().map_or(Ok(()), validate_icon_url),
+            token.max_supply.map_or(Ok(()), |max_supply| {
+                validate_max_supply(max_supply, token.decimals)
+            }),
+        ]
+        .into_iter()
+        .filter_map(|result| result.err())
+        .collect();
+
+        // Entries with the same folder would overwrite each other.
+        let folder = sanitize_name(&token.name).to_lowercase();
+        if !overwrite && Path::new(&folder).exists() {
+            entry_errors.push(format!(
+                "Folder '{}' already exists, use --force to overwrite it",
+                folder
+            ));
+        }
+        if let Some(first) = folders.get(&folder) {
+            entry_errors.push(format!(
+                "Generates the same folder '{}' as entry {}",
+                folder, first
+            ));
+        } else {
+            folders.insert(folder, entry);
+        }
+
+        errors.extend(
+            entry_errors
+                .into_iter()
+                .map(|error| format!("entry {}: {}", entry, error)),
+        );
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(TokenGenErrors::InvalidManifest(errors.join("; ")))
+    }
+}
+
+/// Outcome of a single manifest entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    /// The package was generated.
+    Created,
+    /// Generating the package failed; nothing was left behind.
+    Failed,
+    /// The entry was not attempted because an earlier entry failed.
+    Skipped,
+}
+
+/// Result of creating a single manifest entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchEntry {
+    /// Token parameters of the entry.
+    pub token: TokenInfo,
+
+    /// Outcome of the entry.
+    pub status: BatchStatus,
+
+    /// Folder the package was generated into.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Paths of the written files.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+
+    /// Error message for a failed entry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Per-entry results of a `create --manifest` run.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchReport {
+    /// Path of the manifest the tokens were read from.
+    pub manifest: String,
+
+    /// Results in manifest order.
+    pub entries: Vec<BatchEntry>,
+}
+
+impl BatchReport {
+    /// Returns the number of entries with the given status.
+    pub fn count(&self, status: BatchStatus) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == status)
+            .count()
+    }
+}
+
+impl fmt::Display for BatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            let label = format!("{} ({})", entry.token.name, entry.token.symbol);
+            match entry.status {
+                BatchStatus::Created => writeln!(
+                    f,
+                    "  created  {}: {}",
+                    label,
+                    entry.path.as_deref().unwrap_or_default()
+                )?,
+                BatchStatus::Failed => writeln!(
+                    f,
+                    "  failed   {}: {}",
+                    label,
+                    entry.error.as_deref().unwrap_or_default()
+                )?,
+                BatchStatus::Skipped => writeln!(f, "  skipped  {}", label)?,
+            }
+        }
+
+        write!(
+            f,
+            "{} created, {} failed, {} skipped",
+            self.count(BatchStatus::Created),
+            self.count(BatchStatus::Failed),
+            self.count(BatchStatus::Skipped)
+        )
+    }
+}
@@ -1,7 +1,20 @@
 pub mod atomic;
 pub mod client;
+pub mod config;
 pub mod constants;
+pub mod deployment;
+pub mod diff_report;
+pub mod discovery;
+pub mod fullnode;
 pub mod generation;
+pub mod git;
 pub mod helpers;
+pub mod keystore;
+pub mod manifest;
+pub mod onchain;
 pub mod prompts;
+pub mod templates;
+pub mod tls;
+pub mod transaction;
+pub mod validation;
 pub mod verify_helper;
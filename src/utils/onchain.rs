@@ -0,0 +1,351 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+use crate::{
+    constants::SUI_BINARY,
+    errors::TokenGenErrors,
+    utils::{
+        atomic::AtomicFileOperation,
+        client::responses::GeneratedFile,
+        fullnode::FullnodeClient,
+        generation::ContractGenerator,
+        helpers::{normalize_sui_address, sanitize_name, to_base_units, unique_temp_dir},
+        prompts::TokenInfo,
+        templates::generate_contract,
+        validation::validate_token,
+    },
+    Result,
+};
+
+// Magic bytes opening every Move module, followed by the little-endian bytecode version.
+const MOVE_MAGIC: [u8; 4] = [0xa1, 0x1c, 0xeb, 0x0b];
+
+/// Bytecode of a compiled package, as published by `unsafe_publish`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompiledPackage {
+    /// Bytecode of each module, compiled for the unpublished address `0x0`.
+    pub modules: Vec<Vec<u8>>,
+    /// IDs of the packages the package depends on, e.g. `0x1` and `0x2`.
+    pub dependencies: Vec<String>,
+}
+
+/// Compiles a generated package to the bytecode of its modules.
+pub trait BytecodeCompiler {
+    /**
+     * Compiles the package in a folder.
+     *
+     * # Parameters
+     * - `package_path`: The package folder, containing `Move.toml`.
+     *
+     * # Returns
+     * - `Ok(CompiledPackage)`: The bytecode of each module and the dependencies of the package.
+     * - `Err(TokenGenErrors)`: If the package cannot be compiled.
+     */
+    fn build(&self, package_path: &str) -> Result<CompiledPackage>;
+
+    /// Returns the compiler version, if known. Compiled bytecode depends on it, so it is named
+    /// when a package does not match.
+    fn version(&self) -> Option<String> {
+        None
+    }
+
+    /**
+     * Compiles the package made of the given files.
+     *
+     * The files are written to a temporary folder, which is removed once they are compiled.
+     *
+     * # Parameters
+     * - `files`: The files of the package, with paths relative to the package folder.
+     *
+     * # Returns
+     * - `Ok(Vec<Vec<u8>>)`: The bytecode of each module, compiled for the unpublished address `0x0`.
+     * - `Err(TokenGenErrors)`: If the package cannot be compiled.
+     */
+    fn compile(&self, files: &[GeneratedFile]) -> Result<Vec<Vec<u8>>> {
+        let build_dir = unique_temp_dir("build");
+        let build_path = build_dir
+            .to_str()
+            .ok_or(TokenGenErrors::PathConversionError)?;
+
+        // Never committed, so the package is removed once it is compiled.
+        let _cleanup = AtomicFileOperation::new(build_path);
+        ContractGenerator::new(build_path.to_string()).write_files(files)?;
+
+        Ok(self.build(build_path)?.modules)
+    }
+}
+
+/// Compiles packages with `sui move build --dump-bytecode-as-base64`.
+#[derive(Debug, Clone)]
+pub struct SuiCliCompiler {
+    /// Path or name of the `sui` binary.
+    pub binary: String,
+}
+
+impl Default for SuiCliCompiler {
+    fn default() -> Self {
+        Self {
+            binary: SUI_BINARY.to_string(),
+        }
+    }
+}
+
+// Output of `sui move build --dump-bytecode-as-base64`.
+#[derive(Deserialize)]
+struct BuildOutput {
+    modules: Vec<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+impl BytecodeCompiler for SuiCliCompiler {
+    fn build(&self, package_path: &str) -> Result<CompiledPackage> {
+        let output = Command::new(&self.binary)
+            .args([
+                "move",
+                "build",
+                "--dump-bytecode-as-base64",
+                "--path",
+                package_path,
+            ])
+            .output()
+            .map_err(|e| {
+                TokenGenErrors::CompilerError(format!("Unable to run {}: {}", self.binary, e))
+            })?;
+        if !output.status.success() {
+            return Err(TokenGenErrors::CompilerError(format!(
+                "{} move build failed: {}",
+                self.binary,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        // The bytecode is printed as a JSON object on the last line, after the build log.
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let build: BuildOutput = stdout
+            .lines()
+            .rev()
+            .find(|line| line.trim_start().starts_with('{'))
+            .and_then(|line| serde_json::from_str(line).ok())
+            .ok_or_else(|| {
+                TokenGenErrors::CompilerError(format!(
+                    "{} move build printed no bytecode",
+                    self.binary
+                ))
+            })?;
+
+        let modules = build
+            .modules
+            .iter()
+            .map(|module| {
+                STANDARD
+                    .decode(module)
+                    .map_err(|e| TokenGenErrors::CompilerError(e.to_string()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(CompiledPackage {
+            modules,
+            dependencies: build.dependencies,
+        })
+    }
+
+    fn version(&self) -> Option<String> {
+        let output = Command::new(&self.binary).arg("--version").output().ok()?;
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !version.is_empty()).then_some(version)
+    }
+}
+
+/// A coin published by a package generated with this tool, as verified by `verify --address`.
+#[derive(Debug, Clone, Serialize)]
+pub struct OnChainToken {
+    /// Normalized ID of the package.
+    pub package_id: String,
+    /// Name of the coin module.
+    pub module: String,
+    /// Full coin type, `<package>::<module>::<WITNESS>`.
+    pub coin_type: String,
+    /// ID of the `CoinMetadata` object, if the fullnode reports it.
+    pub metadata_id: Option<String>,
+    /// Token parameters read from the `CoinMetadata`, with its frozen status and fixed supply.
+    pub token: TokenInfo,
+}
+
+/**
+ * Verifies that a published package is the coin package this tool generates for its metadata.
+ *
+ * This function performs the following steps:
+ * 1. Validates and normalizes the package ID.
+ * 2. Fetches the module bytecode of the package and the `CoinMetadata` of its coin type,
+ *    whether the metadata object is frozen, and the total supply of the coin.
+ * 3. Checks the metadata with the same rules as `create` input, since its owner can change it and
+ *    it is rendered into Move code.
+ * 4. Generates the package for the name, symbol, decimals, description, icon URL and frozen
+ *    status claimed by the metadata and compiles it. When the total supply is a whole number of
+ *    tokens, the package with that fixed supply is tried first, then the unlimited one.
+ * 5. Compares the compiled module with the published one, with the package ID of the published
+ *    module replaced by the unpublished address `0x0`. A match with the fixed supply package
+ *    proves that the supply was minted once at publication and can never change.
+ *
+ * # Parameters
+ * - `address`: The package ID.
+ * - `environment`: The blockchain environment the package is published on.
+ * - `fullnode`: The fullnode of the environment.
+ * - `compiler`: Compiles the generated package.
+ *
+ * # Returns
+ * - `Ok(OnChainToken)`: The verified coin.
+ * - `Err(TokenGenErrors::InvalidInput)`: If the address is not a valid Sui address.
+ * - `Err(TokenGenErrors::VerificationError)`: If the package is missing, has no `CoinMetadata`,
+ *   its metadata could not have been generated, or its bytecode differs from the generated package.
+ * - `Err(TokenGenErrors::FullnodeError)`: If a fullnode request fails.
+ */
+pub async fn verify_onchain_package(
+    address: &str,
+    environment: &str,
+    fullnode: &FullnodeClient,
+    compiler: &impl BytecodeCompiler,
+) -> Result<OnChainToken> {
+    let package_id = normalize_sui_address(address)?;
+    let modules = fullnode.get_package_modules(&package_id).await?;
+
+    // Generated packages publish a single coin module.
+    let (module, bytecode) = match modules.into_iter().collect::<Vec<_>>().as_slice() {
+        [(module, bytecode)] => (module.clone(), bytecode.clone()),
+        modules => {
+            return Err(TokenGenErrors::VerificationError(format!(
+                "Package {} has {} modules, but generated packages have one",
+                package_id,
+                modules.len()
+            )))
+        }
+    };
+
+    let coin_type = format!("{}::{}::{}", package_id, module, module.to_uppercase());
+    let metadata = fullnode
+        .get_coin_metadata(&coin_type)
+        .await?
+        .ok_or_else(|| {
+            TokenGenErrors::VerificationError(format!("No CoinMetadata found for {}", coin_type))
+        })?;
+    let is_frozen = match &metadata.id {
+        Some(id) => fullnode.is_object_immutable(id).await?,
+        None => false,
+    };
+
+    let token = TokenInfo {
+        decimals: metadata.decimals,
+        symbol: metadata.symbol,
+        name: metadata.name,
+        description: metadata.description,
+        is_frozen,
+        environment: environment.to_string(),
+        icon_url: metadata.icon_url,
+        max_supply: None,
+    };
+    validate_token(&token).map_err(|e| {
+        let reason = match e {
+            TokenGenErrors::InvalidInput(reason) => reason,
+            e => e.to_string(),
+        };
+        TokenGenErrors::VerificationError(format!(
+            "The CoinMetadata of {} is not valid for a generated token: {}",
+            coin_type, reason
+        ))
+    })?;
+    if sanitize_name(&token.name).to_lowercase() != module {
+        return Err(TokenGenErrors::VerificationError(format!(
+            "Module {} is not the module generated for a token named {}",
+            module, token.name
+        )));
+    }
+
+    // A fixed supply coin holds exactly its max supply, so it is only tried for whole tokens.
+    let total_supply = fullnode.get_total_supply(&coin_type).await?;
+    let fixed_supply = to_base_units(1, token.decimals)
+        .filter(|unit| total_supply > 0 && total_supply % unit == 0)
+        .map(|unit| total_supply / unit);
+
+    // The bytecode version in the module header follows the compiler release, not the source, so
+    // it is ignored; any other difference the release makes still fails the comparison.
+    let unpublished = without_bytecode_version(&unpublish(&bytecode, &package_id));
+    for max_supply in fixed_supply.into_iter().map(Some).chain([None]) {
+        let token = TokenInfo {
+            max_supply,
+            ..token.clone()
+        };
+        let files = generate_contract(&token)?.into_files(&token.name);
+        let compiled: Vec<Vec<u8>> = compiler
+            .compile(&files)?
+            .iter()
+            .map(|module| without_bytecode_version(module))
+            .collect();
+        if compiled == [unpublished.clone()] {
+            return Ok(OnChainToken {
+                package_id,
+                module,
+                coin_type,
+                metadata_id: metadata.id,
+                token,
+            });
+        }
+    }
+
+    Err(TokenGenErrors::VerificationError(format!(
+        "The bytecode of {}::{} differs from the module generated for its metadata by {}. \
+         The comparison relies on the local compiler producing the same bytecode as the one that \
+         published the package, so a different sui release can also cause a mismatch",
+        package_id,
+        module,
+        compiler
+            .version()
+            .unwrap_or_else(|| "an unknown compiler version".to_string())
+    )))
+}
+
+/**
+ * Clears the bytecode version in the header of a Move module.
+ *
+ * Modules start with a magic number and the bytecode version, which differs between compiler
+ * releases even for the same source. Bytes that are not a Move module are returned unchanged.
+ *
+ * # Parameters
+ * - `bytecode`: The compiled or published module.
+ */
+pub fn without_bytecode_version(bytecode: &[u8]) -> Vec<u8> {
+    let mut normalized = bytecode.to_vec();
+    if normalized.len() >= 8 && normalized[..4] == MOVE_MAGIC {
+        normalized[4..8].fill(0);
+    }
+    normalized
+}
+
+/**
+ * Replaces the package ID in a published module with the unpublished address `0x0`.
+ *
+ * Publishing a package substitutes its ID for the `0x0` self address of its modules, so this
+ * turns the published bytecode back into the compiler's output.
+ *
+ * # Parameters
+ * - `bytecode`: The published module.
+ * - `package_id`: The normalized package ID.
+ */
+pub fn unpublish(bytecode: &[u8], package_id: &str) -> Vec<u8> {
+    let id: Vec<u8> = (2..package_id.len())
+        .step_by(2)
+        .filter_map(|i| u8::from_str_radix(&package_id[i..i + 2], 16).ok())
+        .collect();
+
+    let mut unpublished = bytecode.to_vec();
+    let mut offset = 0;
+    while let Some(position) = unpublished[offset..]
+        .windows(id.len())
+        .position(|window| window == id)
+    {
+        let start = offset + position;
+        unpublished[start..start + id.len()].fill(0);
+        offset = start + id.len();
+    }
+    unpublished
+}
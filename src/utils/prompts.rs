@@ -1,25 +1,23 @@
-use inquire::{required, Confirm, Select, Text};
-use once_cell::sync::Lazy;
-use regex::Regex;
+use inquire::{Confirm, Select, Text};
+use std::{
+    io::{self, IsTerminal},
+    path::Path,
+};
 
 use crate::{
-    constants::{CANCEL_ERROR_MESSAGE, FROZEN_OPTIONS},
+    constants::{CANCEL_ERROR_MESSAGE, FROZEN_OPTIONS, SUPPORTED_ENVIRONMENTS},
     errors::TokenGenErrors,
     CreateTokenParams, Result,
 };
 
-use super::{constants::DEFAULT_ENVIRONMENT, helpers::sanitize_name};
-
-// Define regex as Lazy static variables
-static VALID_NAME_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^[a-zA-Z0-9\s]+$").expect("Invalid pattern"));
-
-static SYMBOL_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^[a-zA-Z0-9]+$").expect("Invalid pattern"));
-
-static DESCRIPTION_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"^[a-zA-Z0-9\s.,'\!?;:(){}\[\]\-\_@#$%&*+=|~]+$").expect("Invalid pattern")
-});
+use super::{
+    constants::DEFAULT_ENVIRONMENT,
+    helpers::sanitize_name,
+    validation::{
+        validate_decimals, validate_description, validate_icon_url, validate_max_supply,
+        validate_name, validate_params, validate_symbol,
+    },
+};
 
 const DEFAULT_INDEX: usize = 1;
 
@@ -32,6 +30,10 @@ pub struct TokenInfo {
     pub description: String, // Optional description of the token.
     pub is_frozen: bool,     // Indicates if metadata is frozen.
     pub environment: String, // Blockchain environment (e.g., mainnet, devnet, testnet).
+    #[serde(default)]
+    pub icon_url: Option<String>, // Optional URL of the token logo shown in wallets.
+    #[serde(default)]
+    pub max_supply: Option<u64>, // Optional fixed total supply, in whole tokens.
 }
 
 // Default implementation for `TokenInfo` to provide initial values.
@@ -45,6 +47,8 @@ impl Default for TokenInfo {
             description: String::new(),
             is_frozen: false,
             environment: DEFAULT_ENVIRONMENT.to_string(),
+            icon_url: None,
+            max_supply: None,
         }
     }
 }
@@ -53,8 +57,9 @@ impl Default for TokenInfo {
  * Prompts the user for token-related input and validates their responses.
  *
  * This function collects input parameters for token creation, such as name, symbol, decimals,
- * description, frozen metadata status, and environment selection. It ensures valid user input
- * through regex validation and interactive prompts.
+ * description, icon URL, max supply, frozen metadata status, and environment selection. It ensures valid user input
+ * through regex validation and interactive prompts. In non-interactive mode, or when stdin is not
+ * a terminal, no prompt is shown and the values are taken from `params` alone.
  *
  * # Parameters
  * - `params: &CreateTokenParams`: Predefined token parameters that may contain values already provided by the user.
@@ -64,6 +69,14 @@ impl Default for TokenInfo {
  * - `Err(TokenGenErrors)`: Returns an error if input validation fails or an issue occurs during prompting.
  */
 pub fn get_user_prompt(params: &CreateTokenParams) -> Result<TokenInfo> {
+    // Reject invalid command-line values before prompting for anything else
+    validate_params(params)?;
+
+    // Never prompt when asked not to, or when there is no terminal to prompt on
+    if !is_interactive(params) {
+        return get_token_info_from_params(params);
+    }
+
     // Get the current working directory
     let current_dir = std::env::current_dir().map_err(|_| TokenGenErrors::CurrentDirectoryError)?;
 
@@ -72,14 +85,7 @@ pub fn get_user_prompt(params: &CreateTokenParams) -> Result<TokenInfo> {
         name.clone()
     } else {
         let mut name: String = Text::new("Name: ")
-            .with_validator(required!("Name is required"))
-            .with_validator(&|input| {
-                if VALID_NAME_REGEX.is_match(input) {
-                    Ok(())
-                } else {
-                    Err("Name can only contain alphabets, numbers, and whitespace".into())
-                }
-            })
+            .with_validator(&validate_name)
             .prompt()
             .map_err(TokenGenErrors::PromptError)?;
 
@@ -95,14 +101,7 @@ pub fn get_user_prompt(params: &CreateTokenParams) -> Result<TokenInfo> {
                 break;
             } else {
                 name = Text::new("Please provide a new token name:")
-                    .with_validator(required!("Name is required"))
-                    .with_validator(&|input| {
-                        if VALID_NAME_REGEX.is_match(input) {
-                            Ok(())
-                        } else {
-                            Err("Name can only contain alphabets, numbers, and whitespace".into())
-                        }
-                    })
+                    .with_validator(&validate_name)
                     .prompt()
                     .map_err(TokenGenErrors::PromptError)?;
                 base_folder_path = current_dir.join(sanitize_name(&name).to_lowercase());
@@ -116,18 +115,7 @@ pub fn get_user_prompt(params: &CreateTokenParams) -> Result<TokenInfo> {
         symbol.clone()
     } else {
         Text::new("Symbol: ")
-            .with_validator(required!("Symbol is required"))
-            .with_validator(&|input| {
-                if SYMBOL_REGEX.is_match(input) {
-                    if input.len() <= 5 {
-                        Ok(())
-                    } else {
-                        Err("Symbol must be at most 5 characters long".into())
-                    }
-                } else {
-                    Err("Symbol can only contain alphabets and numbers".into())
-                }
-            })
+            .with_validator(&validate_symbol)
             .prompt()
             .map_err(TokenGenErrors::PromptError)?
     };
@@ -143,10 +131,10 @@ pub fn get_user_prompt(params: &CreateTokenParams) -> Result<TokenInfo> {
                 .with_error_message("Please enter a valid number")
                 .prompt()
             {
-                Ok(value) if value > 0 && value < 100 => break value,
-                Ok(_) => {
-                    eprintln!("Decimals must be between 1 and 99. Please try again.")
-                }
+                Ok(value) => match validate_decimals(value) {
+                    Ok(()) => break value,
+                    Err(e) => eprintln!("{}. Please try again.", e),
+                },
                 Err(e) => {
                     if e.to_string() == CANCEL_ERROR_MESSAGE {
                         return Err(TokenGenErrors::PromptError(e));
@@ -164,15 +152,52 @@ pub fn get_user_prompt(params: &CreateTokenParams) -> Result<TokenInfo> {
     } else {
         Text::new("Description: ")
             .with_help_message("Optional - Provide a brief token description")
-            .with_validator(&|input| {
-                if input.is_empty() || DESCRIPTION_REGEX.is_match(input) {
+            .with_validator(&validate_description)
+            .prompt()
+            .unwrap_or_default()
+    };
+
+    // Prompt for token icon URL (if not provided); an empty answer leaves the icon unset
+    let icon_url = if params.icon_url.is_some() {
+        params.icon_url.clone()
+    } else {
+        let icon_url = Text::new("Icon URL: ")
+            .with_help_message("Optional - Provide the URL of the token logo shown in wallets")
+            .with_validator(&|url: &str| {
+                if url.is_empty() {
                     Ok(())
                 } else {
-                    Err("Description can only contain alphanumeric characters, spaces, and select special characters".into())
+                    validate_icon_url(url)
                 }
             })
             .prompt()
-            .unwrap_or_default()
+            .map_err(TokenGenErrors::PromptError)?;
+        Some(icon_url).filter(|url| !url.is_empty())
+    };
+
+    // Prompt for token max supply (if not provided); an empty answer leaves the supply unlimited
+    let max_supply = if let Some(max_supply) = params.max_supply {
+        // The decimals may have been prompted for, so the flag is checked against them here
+        validate_max_supply(max_supply, decimals).map_err(TokenGenErrors::InvalidInput)?;
+        Some(max_supply)
+    } else {
+        let max_supply = Text::new("Max supply: ")
+            .with_help_message(
+                "Optional - Whole tokens minted to you once, after which minting is disabled",
+            )
+            .with_validator(&|supply: &str| {
+                if supply.is_empty() {
+                    return Ok(());
+                }
+                supply
+                    .parse::<u64>()
+                    .map_err(|_| "Please enter a valid number".to_string())
+                    .and_then(|supply| validate_max_supply(supply, decimals))
+            })
+            .prompt()
+            // A cancelled prompt must not fall back to an unlimited supply.
+            .map_err(TokenGenErrors::PromptError)?;
+        max_supply.parse::<u64>().ok()
     };
 
     // Prompt for frozen metadata status (if not provided)
@@ -189,8 +214,7 @@ pub fn get_user_prompt(params: &CreateTokenParams) -> Result<TokenInfo> {
     let environment = if let Some(ref environment) = params.environment {
         environment.clone()
     } else {
-        let env_options = vec!["mainnet", "devnet", "testnet"];
-        let env_option = Select::new("Select environment:", &env_options)
+        let env_option = Select::new("Select environment:", &SUPPORTED_ENVIRONMENTS)
             .with_starting_cursor(DEFAULT_INDEX)
             .prompt()
             .map_err(TokenGenErrors::PromptError)?;
@@ -205,5 +229,93 @@ pub fn get_user_prompt(params: &CreateTokenParams) -> Result<TokenInfo> {
         description,
         is_frozen,
         environment,
+        icon_url,
+        max_supply,
+    })
+}
+
+/// Returns whether the user may be prompted: not disabled with `--non-interactive`, and stdin is a terminal.
+pub fn is_interactive(params: &CreateTokenParams) -> bool {
+    !params.non_interactive && io::stdin().is_terminal()
+}
+
+/**
+ * Decides whether a token package may be written into a folder that already exists.
+ *
+ * `--force` always allows it. Otherwise the user is asked, unless prompting is disabled, in which
+ * case the folder is left alone. A name chosen at the prompt was already confirmed there.
+ *
+ * # Parameters
+ * - `params: &CreateTokenParams`: The command-line parameters.
+ * - `folder`: The existing package folder.
+ *
+ * # Returns
+ * - `Ok(true)`: If the folder may be overwritten.
+ * - `Ok(false)`: If it must be left alone.
+ * - `Err(TokenGenErrors::PromptError)`: If the prompt fails.
+ */
+pub fn confirm_overwrite(params: &CreateTokenParams, folder: &Path) -> Result<bool> {
+    if params.force {
+        return Ok(true);
+    }
+    if !is_interactive(params) {
+        return Ok(false);
+    }
+    if params.name.is_none() {
+        return Ok(true);
+    }
+
+    Confirm::new(&format!(
+        "The folder {} already exists. Do you want to overwrite it?",
+        folder.display()
+    ))
+    .with_default(false)
+    .prompt()
+    .map_err(TokenGenErrors::PromptError)
+}
+
+/**
+ * Builds the token configuration from command-line parameters without prompting.
+ *
+ * Name, symbol and decimals are required. The description defaults to empty, the icon URL to
+ * none, the max supply to unlimited, frozen metadata to `false` and the environment to the default environment. All missing required values are
+ * reported together in a single error.
+ *
+ * # Parameters
+ * - `params: &CreateTokenParams`: Token parameters provided on the command line.
+ *
+ * # Returns
+ * - `Ok(TokenInfo)`: The token configuration.
+ * - `Err(TokenGenErrors::InvalidInput)`: Lists every missing required flag.
+ */
+pub fn get_token_info_from_params(params: &CreateTokenParams) -> Result<TokenInfo> {
+    let missing: Vec<&str> = [
+        ("--name", params.name.is_none()),
+        ("--symbol", params.symbol.is_none()),
+        ("--decimals", params.decimals.is_none()),
+    ]
+    .into_iter()
+    .filter_map(|(flag, is_missing)| is_missing.then_some(flag))
+    .collect();
+
+    if !missing.is_empty() {
+        return Err(TokenGenErrors::InvalidInput(format!(
+            "Missing required values for non-interactive mode: {}",
+            missing.join(", ")
+        )));
+    }
+
+    Ok(TokenInfo {
+        decimals: params.decimals.unwrap_or_default(),
+        symbol: params.symbol.clone().unwrap_or_default(),
+        name: params.name.clone().unwrap_or_default(),
+        description: params.description.clone().unwrap_or_default(),
+        is_frozen: params.is_frozen.unwrap_or_default(),
+        environment: params
+            .environment
+            .clone()
+            .unwrap_or_else(|| DEFAULT_ENVIRONMENT.to_string()),
+        icon_url: params.icon_url.clone(),
+        max_supply: params.max_supply,
     })
 }
@@ -0,0 +1,127 @@
+use once_cell::sync::Lazy;
+use tera::{Context, Tera};
+
+use crate::{
+    constants::{SUB_FOLDER, TEST_FOLDER},
+    errors::TokenGenErrors,
+    utils::{
+        client::responses::{FileRole, GeneratedFile},
+        helpers::{sanitize_name, to_base_units},
+        prompts::TokenInfo,
+        validation::validate_token,
+    },
+    Result,
+};
+
+// Template names registered with the embedded Tera instance.
+const TOKEN_TEMPLATE: &str = "token.move";
+const TEST_TEMPLATE: &str = "token_tests.move";
+const TOML_TEMPLATE: &str = "Move.toml";
+
+// Templates compiled into the binary so contracts can be rendered without the RPC service.
+static TEMPLATES: Lazy<Tera> = Lazy::new(|| {
+    let mut tera = Tera::default();
+    tera.add_raw_templates(vec![
+        (TOKEN_TEMPLATE, include_str!("../templates/token.move.tera")),
+        (
+            TEST_TEMPLATE,
+            include_str!("../templates/token_tests.move.tera"),
+        ),
+        (TOML_TEMPLATE, include_str!("../templates/Move.toml.tera")),
+    ])
+    .expect("Invalid template");
+    tera
+});
+
+/// Sources of a generated token package.
+#[derive(Debug, Clone)]
+pub struct GeneratedContract {
+    /// Content of the coin module written to `sources/`.
+    pub token: String,
+    /// Content of the package `Move.toml`.
+    pub toml: String,
+    /// Content of the test module written to `tests/`.
+    pub test: String,
+}
+
+impl GeneratedContract {
+    /// Lays out the sources as the files of the RPC `create` response for the named token.
+    pub fn into_files(self, name: &str) -> Vec<GeneratedFile> {
+        let module = format!("{}.move", sanitize_name(name).to_lowercase());
+        vec![
+            GeneratedFile {
+                path: "Move.toml".to_string(),
+                contents: self.toml,
+                role: FileRole::Manifest,
+            },
+            GeneratedFile {
+                path: format!("{}/{}", SUB_FOLDER, module),
+                contents: self.token,
+                role: FileRole::Module,
+            },
+            GeneratedFile {
+                path: format!("{}/{}", TEST_FOLDER, module),
+                contents: self.test,
+                role: FileRole::Test,
+            },
+        ]
+    }
+}
+
+/**
+ * Renders the coin module, test module and `Move.toml` for a token from the embedded templates.
+ *
+ * The output is byte-identical to the sources returned by the RPC `create` method, so packages
+ * generated offline can still be verified by the RPC service. The token is validated first, since
+ * its values are pasted unescaped into Move byte string literals.
+ *
+ * # Parameters
+ * - `token`: The token configuration collected from the user.
+ *
+ * # Returns
+ * - `Ok(GeneratedContract)`: The rendered package sources.
+ * - `Err(TokenGenErrors::InvalidInput)`: If a value is invalid, e.g. the max supply does not fit
+ *   in a coin balance.
+ * - `Err(TokenGenErrors::TeraError)`: If a template fails to render.
+ */
+pub fn generate_contract(token: &TokenInfo) -> Result<GeneratedContract> {
+    validate_token(token)?;
+    let mut context = template_context(token);
+
+    // A fixed supply is minted in base units, so it must fit in a coin balance.
+    if let Some(max_supply) = token.max_supply {
+        let total_supply = to_base_units(max_supply, token.decimals).ok_or_else(|| {
+            TokenGenErrors::InvalidInput(format!(
+                "Max supply of {} tokens with {} decimals does not fit in a coin balance",
+                max_supply, token.decimals
+            ))
+        })?;
+        context.insert("total_supply", &total_supply);
+    }
+
+    Ok(GeneratedContract {
+        token: TEMPLATES.render(TOKEN_TEMPLATE, &context)?,
+        toml: TEMPLATES.render(TOML_TEMPLATE, &context)?,
+        test: TEMPLATES.render(TEST_TEMPLATE, &context)?,
+    })
+}
+
+/// Builds the Tera context shared by all templates, including the identifiers derived from the token name.
+fn template_context(token: &TokenInfo) -> Context {
+    let slug = sanitize_name(&token.name);
+
+    let mut context = Context::new();
+    context.insert("name", &token.name);
+    context.insert("symbol", &token.symbol);
+    context.insert("decimals", &token.decimals);
+    context.insert("description", &token.description);
+    context.insert("is_frozen", &token.is_frozen);
+    context.insert("environment", &token.environment);
+    context.insert("icon_url", &token.icon_url);
+    context.insert("max_supply", &token.max_supply);
+    context.insert("address", &slug);
+    context.insert("module", &slug.to_lowercase());
+    context.insert("package", &slug.to_lowercase());
+    context.insert("witness", &slug.to_uppercase());
+    context
+}
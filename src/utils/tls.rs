@@ -0,0 +1,151 @@
+use std::{fs::File, io::BufReader, sync::Arc};
+use tokio_rustls::{
+    rustls::{
+        pki_types::{CertificateDer, PrivateKeyDer},
+        server::WebPkiClientVerifier,
+        ClientConfig, RootCertStore, ServerConfig,
+    },
+    TlsAcceptor, TlsConnector,
+};
+
+use crate::{errors::TokenGenErrors, Result};
+
+/// Certificates used to connect to the RPC service over a `tls://` address.
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    /// PEM bundle of CA certificates trusted for the server. Defaults to the system roots.
+    pub ca_cert: Option<String>,
+
+    /// PEM certificate chain presented to the server for client authentication.
+    pub client_cert: Option<String>,
+
+    /// PEM private key of the client certificate.
+    pub client_key: Option<String>,
+}
+
+impl TlsOptions {
+    /**
+     * Builds the TLS connector used for `tls://` RPC addresses.
+     *
+     * The server certificate is verified against the CA bundle if one is configured, otherwise
+     * against the system's trusted roots. A client certificate is presented when both the
+     * certificate and its key are configured.
+     *
+     * # Returns
+     * - `Ok(TlsConnector)`: The connector.
+     * - `Err(TokenGenErrors::InvalidTlsConfig)`: If a certificate or key cannot be loaded, no
+     *   CA bundle is configured and the system has no usable roots, or only one of the client
+     *   certificate and key is configured.
+     */
+    pub fn connector(&self) -> Result<TlsConnector> {
+        let mut roots = RootCertStore::empty();
+        match &self.ca_cert {
+            Some(path) => add_roots(&mut roots, load_certs(path)?, path)?,
+            None => {
+                // Unreadable system certificates are skipped, as long as some roots are found.
+                let native = rustls_native_certs::load_native_certs();
+                roots.add_parsable_certificates(native.certs);
+                if roots.is_empty() {
+                    let reason = native
+                        .errors
+                        .first()
+                        .map_or_else(|| "none are installed".to_string(), ToString::to_string);
+                    return Err(TokenGenErrors::InvalidTlsConfig(format!(
+                        "No trusted root certificates could be loaded from the system ({}), pass a CA bundle with --ca-cert",
+                        reason
+                    )));
+                }
+            }
+        }
+
+        let builder = ClientConfig::builder().with_root_certificates(roots);
+        let config = match (&self.client_cert, &self.client_key) {
+            (Some(cert), Some(key)) => builder
+                .with_client_auth_cert(load_certs(cert)?, load_private_key(key)?)
+                .map_err(|e| TokenGenErrors::InvalidTlsConfig(e.to_string()))?,
+            (None, None) => builder.with_no_client_auth(),
+            _ => {
+                return Err(TokenGenErrors::InvalidTlsConfig(
+                    "A client certificate and its key must be provided together".to_string(),
+                ))
+            }
+        };
+
+        Ok(TlsConnector::from(Arc::new(config)))
+    }
+}
+
+/**
+ * Builds the TLS acceptor used by the reference server.
+ *
+ * # Parameters
+ * - `cert`: Path to the PEM certificate chain of the server.
+ * - `key`: Path to the PEM private key of the server.
+ * - `client_ca`: Path to a PEM bundle of CAs; if set, clients must present a certificate signed by one of them.
+ *
+ * # Returns
+ * - `Ok(TlsAcceptor)`: The acceptor.
+ * - `Err(TokenGenErrors::InvalidTlsConfig)`: If a certificate or key cannot be loaded or used.
+ */
+pub fn server_acceptor(cert: &str, key: &str, client_ca: Option<&str>) -> Result<TlsAcceptor> {
+    let builder = match client_ca {
+        Some(path) => {
+            let mut roots = RootCertStore::empty();
+            add_roots(&mut roots, load_certs(path)?, path)?;
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+                .build()
+                .map_err(|e| TokenGenErrors::InvalidTlsConfig(e.to_string()))?;
+            ServerConfig::builder().with_client_cert_verifier(verifier)
+        }
+        None => ServerConfig::builder().with_no_client_auth(),
+    };
+
+    let config = builder
+        .with_single_cert(load_certs(cert)?, load_private_key(key)?)
+        .map_err(|e| TokenGenErrors::InvalidTlsConfig(e.to_string()))?;
+
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Reads every certificate from a PEM file.
+pub fn load_certs(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let certs = rustls_pemfile::certs(&mut open_pem(path)?)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| TokenGenErrors::InvalidTlsConfig(format!("{}: {}", path, e)))?;
+
+    if certs.is_empty() {
+        return Err(TokenGenErrors::InvalidTlsConfig(format!(
+            "{}: No certificates found",
+            path
+        )));
+    }
+    Ok(certs)
+}
+
+/// Reads the first private key from a PEM file.
+pub fn load_private_key(path: &str) -> Result<PrivateKeyDer<'static>> {
+    rustls_pemfile::private_key(&mut open_pem(path)?)
+        .map_err(|e| TokenGenErrors::InvalidTlsConfig(format!("{}: {}", path, e)))?
+        .ok_or_else(|| TokenGenErrors::InvalidTlsConfig(format!("{}: No private key found", path)))
+}
+
+// Opens a PEM file, reporting the path if it cannot be read.
+fn open_pem(path: &str) -> Result<BufReader<File>> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| TokenGenErrors::InvalidTlsConfig(format!("{}: {}", path, e)))
+}
+
+// Adds CA certificates to a root store, reporting the bundle path for invalid certificates.
+fn add_roots(
+    roots: &mut RootCertStore,
+    certs: Vec<CertificateDer<'static>>,
+    path: &str,
+) -> Result<()> {
+    for cert in certs {
+        roots
+            .add(cert)
+            .map_err(|e| TokenGenErrors::InvalidTlsConfig(format!("{}: {}", path, e)))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use crate::{
+    errors::TokenGenErrors,
+    utils::{helpers::normalize_sui_address, onchain::CompiledPackage},
+    Result,
+};
+
+// Variant indices of the BCS-encoded Sui transaction types checked here.
+const TRANSACTION_DATA_V1: usize = 0;
+const PROGRAMMABLE_TRANSACTION: usize = 0;
+const CALL_ARG_PURE: usize = 0;
+const COMMAND_TRANSFER_OBJECTS: usize = 1;
+const COMMAND_PUBLISH: usize = 4;
+const ARGUMENT_INPUT: usize = 1;
+const ARGUMENT_RESULT: usize = 2;
+const EXPIRATION_NONE: usize = 0;
+const EXPIRATION_EPOCH: usize = 1;
+
+/**
+ * Checks that transaction data built by a fullnode's `unsafe_publish` only publishes the package.
+ *
+ * The fullnode builds the transaction, so it is decoded before it is signed. It must be a
+ * programmable transaction sent and paid for by `sender` with the requested gas budget, whose
+ * commands publish exactly the given modules and dependencies and transfer the resulting
+ * `UpgradeCap` to the sender, with no other commands and no object inputs.
+ *
+ * # Parameters
+ * - `tx_bytes`: The BCS-encoded transaction data returned by the fullnode.
+ * - `sender`: The address of the publisher.
+ * - `package`: The compiled package that was sent to the fullnode.
+ * - `gas_budget`: The requested gas budget, in MIST.
+ *
+ * # Returns
+ * - `Ok(())`: If the transaction only publishes the package.
+ * - `Err(TokenGenErrors::PublishError)`: If it cannot be decoded or does anything else.
+ */
+pub fn check_publish_transaction(
+    tx_bytes: &[u8],
+    sender: &str,
+    package: &CompiledPackage,
+    gas_budget: u64,
+) -> Result<()> {
+    let sender = address_bytes(sender)?;
+    let dependencies = package
+        .dependencies
+        .iter()
+        .map(|dependency| address_bytes(dependency))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut reader = BcsReader { bytes: tx_bytes };
+    reader.expect_variant(TRANSACTION_DATA_V1, "transaction data version")?;
+    reader.expect_variant(PROGRAMMABLE_TRANSACTION, "transaction kind")?;
+
+    let inputs = (0..reader.length()?)
+        .map(|_| {
+            reader.expect_variant(CALL_ARG_PURE, "input, only pure inputs are expected")?;
+            reader.bytes()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if reader.length()? != 2 {
+        return Err(unexpected("it does not have exactly two commands"));
+    }
+
+    // Command 1: publish the modules with their dependencies, returning the `UpgradeCap`.
+    reader.expect_variant(COMMAND_PUBLISH, "first command, expected Publish")?;
+    let modules = (0..reader.length()?)
+        .map(|_| reader.bytes())
+        .collect::<Result<Vec<_>>>()?;
+    let published_dependencies = (0..reader.length()?)
+        .map(|_| reader.address())
+        .collect::<Result<Vec<_>>>()?;
+    if modules != package.modules {
+        return Err(unexpected(
+            "it publishes other modules than the compiled package",
+        ));
+    }
+    if published_dependencies != dependencies {
+        return Err(unexpected(
+            "it publishes the package with other dependencies",
+        ));
+    }
+
+    // Command 2: transfer the `UpgradeCap` to the sender.
+    reader.expect_variant(
+        COMMAND_TRANSFER_OBJECTS,
+        "second command, expected TransferObjects",
+    )?;
+    if reader.length()? != 1 {
+        return Err(unexpected("it transfers more than the UpgradeCap"));
+    }
+    reader.expect_variant(ARGUMENT_RESULT, "transferred object")?;
+    if reader.u16()? != 0 {
+        return Err(unexpected(
+            "it transfers another object than the UpgradeCap",
+        ));
+    }
+    reader.expect_variant(ARGUMENT_INPUT, "recipient")?;
+    let recipient = inputs
+        .get(usize::from(reader.u16()?))
+        .ok_or_else(|| unexpected("the recipient is not an input"))?;
+    if recipient.as_slice() != sender.as_slice() {
+        return Err(unexpected("it transfers the UpgradeCap to another address"));
+    }
+
+    if reader.address()? != sender {
+        return Err(unexpected("it is sent by another address"));
+    }
+
+    // Gas data: payment coins, owner, price and budget.
+    for _ in 0..reader.length()? {
+        reader.address()?;
+        reader.u64()?;
+        reader.bytes()?;
+    }
+    if reader.address()? != sender {
+        return Err(unexpected("its gas is paid by another address"));
+    }
+    reader.u64()?;
+    let budget = reader.u64()?;
+    if budget != gas_budget {
+        return Err(unexpected(&format!(
+            "its gas budget is {} instead of {}",
+            budget, gas_budget
+        )));
+    }
+
+    match reader.length()? {
+        EXPIRATION_NONE => {}
+        EXPIRATION_EPOCH => {
+            reader.u64()?;
+        }
+        _ => return Err(unexpected("it has an unknown expiration")),
+    }
+    if !reader.bytes.is_empty() {
+        return Err(unexpected("it has trailing data"));
+    }
+    Ok(())
+}
+
+// Reads BCS values from the front of a byte slice.
+struct BcsReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> BcsReader<'a> {
+    // Takes the next `length` bytes.
+    fn take(&mut self, length: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < length {
+            return Err(unexpected("it is truncated"));
+        }
+        let (taken, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    // Reads a ULEB128 value, used for sequence lengths and enum variants.
+    fn length(&mut self) -> Result<usize> {
+        let mut value = 0usize;
+        for shift in (0..32).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= usize::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(unexpected("it has an invalid length"))
+    }
+
+    // Reads an enum variant and checks that it is the expected one.
+    fn expect_variant(&mut self, variant: usize, what: &str) -> Result<()> {
+        if self.length()? != variant {
+            return Err(unexpected(&format!("unexpected {}", what)));
+        }
+        Ok(())
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    // Reads a length-prefixed byte vector.
+    fn bytes(&mut self) -> Result<Vec<u8>> {
+        let length = self.length()?;
+        Ok(self.take(length)?.to_vec())
+    }
+
+    // Reads a 32-byte address or object ID.
+    fn address(&mut self) -> Result<[u8; 32]> {
+        let mut address = [0; 32];
+        address.copy_from_slice(self.take(32)?);
+        Ok(address)
+    }
+}
+
+// Converts a Sui address to its 32 bytes.
+fn address_bytes(address: &str) -> Result<[u8; 32]> {
+    let normalized = normalize_sui_address(address)?;
+    let mut bytes = [0; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&normalized[2 + 2 * i..4 + 2 * i], 16)
+            .map_err(|e| TokenGenErrors::InvalidInput(e.to_string()))?;
+    }
+    Ok(bytes)
+}
+
+// Builds the error for a transaction that does not only publish the package.
+fn unexpected(reason: &str) -> TokenGenErrors {
+    TokenGenErrors::PublishError(format!(
+        "Refusing to sign the transaction built by the fullnode: {}",
+        reason
+    ))
+}
@@ -0,0 +1,189 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use url::Url;
+
+use crate::{
+    constants::SUPPORTED_ENVIRONMENTS,
+    errors::TokenGenErrors,
+    utils::{helpers::to_base_units, prompts::TokenInfo},
+    CreateTokenParams, Result,
+};
+
+// Define regex as Lazy static variables
+pub(crate) static VALID_NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-zA-Z0-9 ]+$").expect("Invalid pattern"));
+
+pub(crate) static SYMBOL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-zA-Z0-9]+$").expect("Invalid pattern"));
+
+pub(crate) static DESCRIPTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[a-zA-Z0-9 .,'\!?;:(){}\[\]\-\_@#$%&*+=|~]+$").expect("Invalid pattern")
+});
+
+// Maximum number of characters in a token symbol.
+const MAX_SYMBOL_LENGTH: usize = 5;
+
+/// Validates a token name: required, alphanumeric characters and spaces only.
+pub fn validate_name(name: &str) -> std::result::Result<(), String> {
+    if name.is_empty() {
+        Err("Name is required".into())
+    } else if !VALID_NAME_REGEX.is_match(name) {
+        Err("Name can only contain alphabets, numbers, and spaces".into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates a token symbol: required, alphanumeric only and at most five characters long.
+pub fn validate_symbol(symbol: &str) -> std::result::Result<(), String> {
+    if symbol.is_empty() {
+        Err("Symbol is required".into())
+    } else if !SYMBOL_REGEX.is_match(symbol) {
+        Err("Symbol can only contain alphabets and numbers".into())
+    } else if symbol.len() > MAX_SYMBOL_LENGTH {
+        Err(format!(
+            "Symbol must be at most {} characters long",
+            MAX_SYMBOL_LENGTH
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates token decimals: between 1 and 99.
+pub fn validate_decimals(decimals: u8) -> std::result::Result<(), String> {
+    if decimals > 0 && decimals < 100 {
+        Ok(())
+    } else {
+        Err("Decimals must be between 1 and 99".into())
+    }
+}
+
+/// Validates a token description: optional, alphanumeric characters, spaces and select special characters.
+pub fn validate_description(description: &str) -> std::result::Result<(), String> {
+    if description.is_empty() || DESCRIPTION_REGEX.is_match(description) {
+        Ok(())
+    } else {
+        Err("Description can only contain alphanumeric characters, spaces, and select special characters".into())
+    }
+}
+
+/// Validates a token icon URL: an `http` or `https` URL that fits in a Move byte string literal.
+pub fn validate_icon_url(icon_url: &str) -> std::result::Result<(), String> {
+    let is_web_url = Url::parse(icon_url)
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some());
+
+    if !is_web_url {
+        Err("Icon URL must be an http or https URL".into())
+    } else if !icon_url
+        .chars()
+        .all(|c| c.is_ascii_graphic() && c != '"' && c != '\\')
+    {
+        Err("Icon URL can only contain printable ASCII characters other than quotes and backslashes".into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates a token max supply, in whole tokens: at least one token, and at most the largest coin balance.
+pub fn validate_max_supply(max_supply: u64, decimals: u8) -> std::result::Result<(), String> {
+    if max_supply == 0 {
+        Err("Max supply must be at least 1".into())
+    } else if to_base_units(max_supply, decimals).is_none() {
+        Err(format!(
+            "Max supply of {} tokens with {} decimals exceeds the largest coin balance of {} base units",
+            max_supply,
+            decimals,
+            u64::MAX
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates a blockchain environment: one of the supported networks.
+pub fn validate_environment(environment: &str) -> std::result::Result<(), String> {
+    if SUPPORTED_ENVIRONMENTS.contains(&environment) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Environment must be one of: {}",
+            SUPPORTED_ENVIRONMENTS.join(", ")
+        ))
+    }
+}
+
+/**
+ * Validates the token values provided on the command line with the same rules as the prompts.
+ *
+ * Values that were not provided are skipped, since they are prompted for (and validated) later.
+ * All invalid values are reported together.
+ *
+ * # Parameters
+ * - `params: &CreateTokenParams`: Token parameters provided on the command line.
+ *
+ * # Returns
+ * - `Ok(())`: If every provided value is valid.
+ * - `Err(TokenGenErrors::InvalidInput)`: With the message of each invalid value.
+ */
+pub fn validate_params(params: &CreateTokenParams) -> Result<()> {
+    let errors: Vec<String> = [
+        params.name.as_deref().map(validate_name),
+        params.symbol.as_deref().map(validate_symbol),
+        params.decimals.map(validate_decimals),
+        params.description.as_deref().map(validate_description),
+        params.icon_url.as_deref().map(validate_icon_url),
+        // Without decimals, the max supply is validated once they are prompted for.
+        params
+            .max_supply
+            .map(|max_supply| validate_max_supply(max_supply, params.decimals.unwrap_or_default())),
+        params.environment.as_deref().map(validate_environment),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|result| result.err())
+    .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(TokenGenErrors::InvalidInput(errors.join("; ")))
+    }
+}
+
+/**
+ * Validates every value of a token configuration with the same rules as the prompts.
+ *
+ * The values are pasted into Move byte string literals when a contract is rendered, so a token
+ * read from a contract header, a manifest or the chain must pass this check before rendering.
+ *
+ * # Parameters
+ * - `token: &TokenInfo`: The token configuration to render.
+ *
+ * # Returns
+ * - `Ok(())`: If every value is valid.
+ * - `Err(TokenGenErrors::InvalidInput)`: With the message of each invalid value.
+ */
+pub fn validate_token(token: &TokenInfo) -> Result<()> {
+    let errors: Vec<String> = [
+        Some(validate_name(&token.name)),
+        Some(validate_symbol(&token.symbol)),
+        Some(validate_decimals(token.decimals)),
+        Some(validate_description(&token.description)),
+        token.icon_url.as_deref().map(validate_icon_url),
+        token
+            .max_supply
+            .map(|max_supply| validate_max_supply(max_supply, token.decimals)),
+        Some(validate_environment(&token.environment)),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|result| result.err())
+    .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(TokenGenErrors::InvalidInput(errors.join("; ")))
+    }
+}
@@ -1,10 +1,27 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
 use std::{
     fs::{self, ReadDir},
     io,
     path::Path,
 };
 
-use crate::{constants::SUB_FOLDER, errors::TokenGenErrors, Result};
+use crate::{
+    constants::{DEFAULT_ENVIRONMENT, SUB_FOLDER, TEST_FOLDER},
+    errors::TokenGenErrors,
+    utils::{
+        client::responses::{FileRole, GeneratedFile, RpcResponseErrors},
+        prompts::TokenInfo,
+        templates::generate_contract,
+        validation::validate_token,
+    },
+    Result,
+};
+
+// Matches the Sui framework revision in a generated `Move.toml`, which encodes the environment.
+static FRAMEWORK_REV_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)^rev\s*=\s*"framework/([a-z]+)"\s*$"#).expect("Invalid pattern")
+});
 
 /**
  * Reads the content of a file at the given path.
@@ -20,28 +37,75 @@ pub fn read_file(file_path: &Path) -> io::Result<String> {
     fs::read_to_string(file_path)
 }
 
+/// A Move package read for verification.
 pub struct VerifyPathStruct {
+    /// Content of the main module, the one carrying the generator header.
     pub content: String,
+    /// File name of the main module.
     pub file_name: String,
+    /// Content of the package `Move.toml`.
     pub toml: String,
+    /// Every verified file of the package: `Move.toml` and the files in `sources/` and `tests/`.
+    pub files: Vec<GeneratedFile>,
+}
+
+impl VerifyPathStruct {
+    /**
+     * Builds a package from its files, selecting `Move.toml` and the main module.
+     *
+     * The main module is the first module in `sources/`, by path, that carries a generator
+     * header, or the first module if none does.
+     *
+     * # Parameters
+     * - `files`: The files of the package, with paths relative to the package folder.
+     *
+     * # Returns
+     * - `Ok(VerifyPathStruct)`: The package.
+     * - `Err(TokenGenErrors::InvalidPathNotDirectory)`: If there is no `Move.toml`.
+     * - `Err(TokenGenErrors::InvalidPathNoMoveFiles)`: If there is no module, or it or `Move.toml` is empty.
+     */
+    pub fn from_files(mut files: Vec<GeneratedFile>) -> Result<Self> {
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let toml = files
+            .iter()
+            .find(|file| file.role == FileRole::Manifest)
+            .ok_or(TokenGenErrors::InvalidPathNotDirectory)?
+            .contents
+            .clone();
+        let module = main_module(&files).ok_or(TokenGenErrors::InvalidPathNoMoveFiles)?;
+
+        if module.contents.is_empty() || toml.is_empty() {
+            return Err(TokenGenErrors::InvalidPathNoMoveFiles);
+        }
+
+        Ok(Self {
+            content: module.contents.clone(),
+            file_name: module
+                .path
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            toml,
+            files,
+        })
+    }
 }
 
 /**
- * Verifies a directory path and validates its structure for a Move project.
+ * Verifies a directory path and reads the Move package it contains.
  *
  * This function performs the following checks:
- * 1. Ensures that the provided path exists, is a directory, and contains a `sources` folder.
- * 2. Checks the `sources` folder for `.move` files, ensuring exactly one `.move` file exists.
- * 3. Validates that the `.move` file is not empty.
- *
- * If all criteria are met, the content of the `.move` file is returned. Otherwise, an appropriate
- * error is returned.
+ * 1. Ensures that the provided path exists, is a directory, and contains a `sources` folder and a `Move.toml`.
+ * 2. Reads `Move.toml` and every file in `sources/` and `tests/`, including subfolders.
+ * 3. Ensures that `sources/` contains a non-empty `.move` file.
  *
  * # Parameters
  * - `path`: A string slice representing the directory path to verify.
  *
  * # Returns
- * - `Ok(VerifyPathStruct)`: The content of the .toml file, `.move` file name & .move file in sources folder if all conditions are satisfied.
+ * - `Ok(VerifyPathStruct)`: The package files, with its `Move.toml` and main module.
  * - `Err(TokenGenErrors)`: If the path or file structure is invalid.
  *
  * # Errors
@@ -66,42 +130,110 @@ pub fn verify_path(path: &str) -> Result<VerifyPathStruct> {
         return Err(TokenGenErrors::InvalidPathNotDirectory);
     }
 
-    let toml_content = read_file(&toml_path)?;
+    // Links could point anywhere, e.g. at a key that would be uploaded to the RPC service.
+    for link in [&toml_path, &sources_folder, &path.join(TEST_FOLDER)] {
+        reject_symlink(link)?;
+    }
 
-    // Read entries from the `sources` folder.
-    let entries = read_dir(&sources_folder)?;
+    let mut files = vec![GeneratedFile {
+        path: "Move.toml".to_string(),
+        contents: read_file(&toml_path)?,
+        role: FileRole::Manifest,
+    }];
+    read_package_files(path, &sources_folder, &mut files)?;
 
-    // Look for the first `.move` file in the `sources` folder.
-    let mut current_content = String::new();
+    // A package without a tests folder is still compared, so its generated tests are missing.
+    let tests_folder = path.join(TEST_FOLDER);
+    if tests_folder.is_dir() {
+        read_package_files(path, &tests_folder, &mut files)?;
+    }
 
-    // Initialize a string to store the name of the first .move file
-    let mut verifying_file_name = String::new();
-    for entry in entries {
+    VerifyPathStruct::from_files(files)
+}
+
+/**
+ * Reads every file below a folder of a package, recursively.
+ *
+ * Files that are not valid UTF-8 are read lossily, so they are still reported as modified or
+ * extra files instead of failing the verification. Symbolic links are never followed, since a
+ * cloned repository could link to any file on the machine.
+ *
+ * # Parameters
+ * - `root`: The package folder, which paths are made relative to.
+ * - `dir`: The folder to read.
+ * - `files`: The list the files are added to.
+ *
+ * # Returns
+ * - `Ok(())`: If every file could be read.
+ * - `Err(TokenGenErrors::FileIoError)`: If a folder or file cannot be read.
+ * - `Err(TokenGenErrors::InvalidPath)`: If the folder holds a symbolic link.
+ */
+fn read_package_files(root: &Path, dir: &Path, files: &mut Vec<GeneratedFile>) -> Result<()> {
+    for entry in read_dir(dir)? {
         let entry = entry?;
         let path = entry.path();
-
-        // Check if the file has a `.move` extension.
-        if path.is_file() && path.extension().is_some_and(|e| e == "move") {
-            // Read the content of the `.move` file.
-            current_content = read_file(&path)?;
-            if let Some(file_name) = path.file_name().and_then(|f| f.to_str()) {
-                verifying_file_name = file_name.to_string();
-            }
-            break; // Stop searching after the first valid `.move` file is found.
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            return Err(symlink_error(&path));
+        }
+        if file_type.is_dir() {
+            read_package_files(root, &path, files)?;
+            continue;
         }
+
+        let relative_path = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        files.push(GeneratedFile {
+            role: file_role(&relative_path),
+            contents: String::from_utf8_lossy(&fs::read(&path)?).into_owned(),
+            path: relative_path,
+        });
     }
+    Ok(())
+}
+
+// Fails if the path is a symbolic link; missing paths are left to the caller.
+fn reject_symlink(path: &Path) -> Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => Err(symlink_error(path)),
+        _ => Ok(()),
+    }
+}
+
+// Builds the error for a symbolic link in a package, which generated packages never contain.
+fn symlink_error(path: &Path) -> TokenGenErrors {
+    TokenGenErrors::InvalidPath(format!(
+        "{} is a symbolic link, which generated packages never contain",
+        path.display()
+    ))
+}
 
-    // Return an error if no `.move` file was found or the file is empty.
-    if current_content.is_empty() || toml_content.is_empty() {
-        return Err(TokenGenErrors::InvalidPathNoMoveFiles);
+// Returns the role of a package file from its relative path.
+fn file_role(path: &str) -> FileRole {
+    let is_move = path.ends_with(".move");
+    if path == "Move.toml" {
+        FileRole::Manifest
+    } else if is_move && path.starts_with(&format!("{}/", SUB_FOLDER)) {
+        FileRole::Module
+    } else if is_move && path.starts_with(&format!("{}/", TEST_FOLDER)) {
+        FileRole::Test
+    } else {
+        FileRole::Other
     }
+}
 
-    // Return the content of the `.move` file.
-    Ok(VerifyPathStruct {
-        content: current_content,
-        file_name: verifying_file_name,
-        toml: toml_content,
-    })
+// Returns the module carrying the generator header, or the first module if none does.
+fn main_module(files: &[GeneratedFile]) -> Option<&GeneratedFile> {
+    let mut modules = files.iter().filter(|file| file.role == FileRole::Module);
+    modules
+        .clone()
+        .find(|file| parse_token_header(&file.contents).is_some())
+        .or_else(|| modules.next())
 }
 
 /**
@@ -120,3 +252,217 @@ pub fn verify_path(path: &str) -> Result<VerifyPathStruct> {
 pub fn read_dir(dir: &Path) -> io::Result<ReadDir> {
     fs::read_dir(dir)
 }
+
+/**
+ * Extracts the token configuration from the header comment block of a generated contract.
+ *
+ * Generated contracts start with a block of `/// key: value` lines (`name`, `decimals`, `symbol`,
+ * `description`, `is_frozen`, and `icon_url` and `max_supply` when set) that records the
+ * parameters the contract was rendered with. The environment is not part of the header and is left at its default.
+ * The values are validated like `create` input, since they are rendered into Move code again.
+ *
+ * # Parameters
+ * - `content`: The content of the `.move` contract file.
+ *
+ * # Returns
+ * - `Some(TokenInfo)`: If every header field is present, well-formed and valid.
+ * - `None`: If the header is missing, malformed or holds a value `create` rejects.
+ */
+pub fn parse_token_header(content: &str) -> Option<TokenInfo> {
+    let mut name = None;
+    let mut decimals = None;
+    let mut symbol = None;
+    let mut description = None;
+    let mut is_frozen = None;
+    let mut icon_url = None;
+    let mut max_supply = None;
+
+    // The header ends at the first line that is not a doc comment.
+    for line in content
+        .lines()
+        .skip_while(|line| line.starts_with("//!"))
+        .take_while(|line| line.starts_with("///"))
+    {
+        let Some((key, value)) = line.trim_start_matches("///").split_once(':') else {
+            continue;
+        };
+        let value = value.strip_prefix(' ').unwrap_or(value).to_string();
+
+        match key.trim() {
+            "name" => name = Some(value),
+            "decimals" => decimals = value.parse::<u8>().ok(),
+            "symbol" => symbol = Some(value),
+            "description" => description = Some(value),
+            "is_frozen" => is_frozen = value.parse::<bool>().ok(),
+            "icon_url" => icon_url = Some(value),
+            "max_supply" => max_supply = Some(value.parse::<u64>()),
+            _ => {}
+        }
+    }
+
+    let token = TokenInfo {
+        name: name?,
+        decimals: decimals?,
+        symbol: symbol?,
+        description: description?,
+        is_frozen: is_frozen?,
+        icon_url,
+        // A malformed max supply is a malformed header, not an unlimited supply.
+        max_supply: max_supply.transpose().ok()?,
+        ..TokenInfo::default()
+    };
+    validate_token(&token).ok()?;
+    Some(token)
+}
+
+/**
+ * Extracts the environment from the Sui framework revision of a generated `Move.toml`.
+ *
+ * # Parameters
+ * - `toml`: The content of the package `Move.toml`.
+ *
+ * # Returns
+ * - `Some(String)`: The environment, e.g. `devnet` for `rev = "framework/devnet"`.
+ * - `None`: If no framework revision is present.
+ */
+pub fn parse_toml_environment(toml: &str) -> Option<String> {
+    FRAMEWORK_REV_PATTERN
+        .captures(toml)
+        .and_then(|captures| captures.get(1))
+        .map(|environment| environment.as_str().to_string())
+}
+
+/**
+ * Verifies a contract and its `Move.toml` without contacting the RPC service.
+ *
+ * The token parameters are read from the contract's header block and the environment from the
+ * framework revision in `Move.toml`. The expected sources are then re-rendered from the embedded
+ * templates and compared with the provided ones.
+ *
+ * # Parameters
+ * - `content`: The content of the `.move` contract file.
+ * - `toml`: The content of the package `Move.toml`.
+ *
+ * # Returns
+ * - `Ok(())`: If both files match the generated sources exactly.
+ * - `Err(RpcResponseErrors::ProgramModified)`: If the header is missing or either file differs.
+ */
+pub fn verify_content_locally(
+    content: &str,
+    toml: &str,
+) -> std::result::Result<(), RpcResponseErrors> {
+    let mut token = parse_token_header(content).ok_or(RpcResponseErrors::ProgramModified)?;
+
+    // The environment only appears in the framework revision of `Move.toml`.
+    token.environment = parse_toml_environment(toml).ok_or(RpcResponseErrors::ProgramModified)?;
+
+    // An invalid environment is as much a modification as an invalid header.
+    let expected = generate_contract(&token).map_err(|_| RpcResponseErrors::ProgramModified)?;
+
+    if expected.token != content || expected.toml != toml {
+        return Err(RpcResponseErrors::ProgramModified);
+    }
+
+    Ok(())
+}
+
+/// How the files of a package differ from the package this tool generates for its header.
+#[derive(Debug, Clone, Default)]
+pub struct PackageComparison {
+    /// Token parameters claimed by the main module's header, with the environment of `Move.toml`.
+    pub claimed: Option<TokenInfo>,
+    /// Files this tool generates for the claimed parameters.
+    pub expected: Vec<GeneratedFile>,
+    /// Generated files that are not in the package.
+    pub missing: Vec<String>,
+    /// Package files that this tool does not generate.
+    pub extra: Vec<String>,
+    /// Package files whose contents differ from the generated ones.
+    pub modified: Vec<String>,
+}
+
+impl PackageComparison {
+    /// Returns whether the package has a header and exactly the generated files.
+    pub fn is_unmodified(&self) -> bool {
+        self.claimed.is_some()
+            && self.missing.is_empty()
+            && self.extra.is_empty()
+            && self.modified.is_empty()
+    }
+}
+
+/**
+ * Compares the files of a package with the package generated from its main module's header.
+ *
+ * The environment is taken from the framework revision in `Move.toml`, falling back to the
+ * default environment when it is missing. Without a valid header, or for an invalid environment,
+ * nothing can be compared, so only `claimed` is left empty.
+ *
+ * # Parameters
+ * - `files`: The package files, with paths relative to the package folder.
+ *
+ * # Returns
+ * - The missing, extra and modified files, by path.
+ */
+pub fn compare_package(files: &[GeneratedFile]) -> PackageComparison {
+    let claimed = main_module(files)
+        .and_then(|module| parse_token_header(&module.contents))
+        .map(|token| TokenInfo {
+            environment: files
+                .iter()
+                .find(|file| file.role == FileRole::Manifest)
+                .and_then(|toml| parse_toml_environment(&toml.contents))
+                .unwrap_or_else(|| DEFAULT_ENVIRONMENT.to_string()),
+            ..token
+        });
+    let Some(expected) = claimed.as_ref().and_then(|token| {
+        let generated = generate_contract(token).ok()?;
+        Some(generated.into_files(&token.name))
+    }) else {
+        return PackageComparison::default();
+    };
+
+    let find =
+        |files: &[GeneratedFile], path: &str| files.iter().find(|file| file.path == path).cloned();
+    let mut comparison = PackageComparison::default();
+    for file in &expected {
+        match find(files, &file.path) {
+            None => comparison.missing.push(file.path.clone()),
+            Some(actual) if actual.contents != file.contents => {
+                comparison.modified.push(file.path.clone())
+            }
+            Some(_) => {}
+        }
+    }
+    comparison.extra = files
+        .iter()
+        .filter(|file| find(&expected, &file.path).is_none())
+        .map(|file| file.path.clone())
+        .collect();
+
+    PackageComparison {
+        claimed,
+        expected,
+        ..comparison
+    }
+}
+
+/**
+ * Verifies every file of a package without contacting the RPC service.
+ *
+ * # Parameters
+ * - `files`: The package files: `Move.toml` and the files in `sources/` and `tests/`.
+ *
+ * # Returns
+ * - `Ok(())`: If the package has exactly the files this tool generates for its header.
+ * - `Err(RpcResponseErrors::ProgramModified)`: If the header is missing, or any file is missing,
+ *   extra or modified, matching the error returned by the RPC `verify_package` method.
+ */
+pub fn verify_package_locally(
+    files: &[GeneratedFile],
+) -> std::result::Result<(), RpcResponseErrors> {
+    if !compare_package(files).is_unmodified() {
+        return Err(RpcResponseErrors::ProgramModified);
+    }
+    Ok(())
+}